@@ -3,12 +3,18 @@
 # for complete details.
 
 import datetime
+import os
 import typing
 
 from cryptography import x509
+from cryptography.x509 import ocsp
 from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import ec, rsa
 from cryptography.hazmat.primitives.asymmetric.padding import PSS, PKCS1v15
-from cryptography.hazmat.primitives.asymmetric.types import PrivateKeyTypes
+from cryptography.hazmat.primitives.asymmetric.types import (
+    CertificateIssuerPublicKeyTypes,
+    PrivateKeyTypes,
+)
 
 def load_pem_x509_certificate(
     data: bytes, backend: typing.Any = None
@@ -60,18 +66,125 @@ class CertificateSigningRequest: ...
 
 class PolicyBuilder:
     def time(self, new_time: datetime.datetime) -> PolicyBuilder: ...
+    def validation_model(
+        self, new_validation_model: x509.verification.ValidationModel
+    ) -> PolicyBuilder: ...
+    def clock_skew(self, new_skew: datetime.timedelta) -> PolicyBuilder: ...
     def store(self, new_store: Store) -> PolicyBuilder: ...
     def max_chain_depth(self, new_max_chain_depth: int) -> PolicyBuilder: ...
+    def max_signature_checks(
+        self, new_max_signature_checks: int
+    ) -> PolicyBuilder: ...
+    def max_candidates(self, new_max_candidates: int) -> PolicyBuilder: ...
+    def max_issuer_fetches(
+        self, new_max_issuer_fetches: int
+    ) -> PolicyBuilder: ...
+    def max_path_building_time(
+        self, new_max_path_building_time: datetime.timedelta
+    ) -> PolicyBuilder: ...
+    def signature_cache_size(
+        self, new_signature_cache_size: int
+    ) -> PolicyBuilder: ...
+    def crls(
+        self,
+        new_crls: list[x509.CertificateRevocationList],
+        *,
+        soft_fail: bool = False,
+    ) -> PolicyBuilder: ...
+    def ocsp_clock_skew(
+        self, new_skew: datetime.timedelta
+    ) -> PolicyBuilder: ...
+    def certificate_policies(
+        self,
+        initial_policy_set: list[x509.ObjectIdentifier] | None = None,
+        *,
+        explicit_policy: bool = False,
+        policy_mapping_inhibit: bool = False,
+        any_policy_inhibit: bool = False,
+    ) -> PolicyBuilder: ...
+    def minimum_rsa_modulus(
+        self, new_minimum_rsa_modulus: int
+    ) -> PolicyBuilder: ...
+    def signature_algorithms(
+        self, new_signature_algorithms: list[x509.ObjectIdentifier]
+    ) -> PolicyBuilder: ...
+    def public_key_algorithms(
+        self, new_public_key_algorithms: list[x509.ObjectIdentifier]
+    ) -> PolicyBuilder: ...
+    def ec_curves(
+        self, new_ec_curves: list[ec.EllipticCurve]
+    ) -> PolicyBuilder: ...
+    def partial_chain(self, new_partial_chain: bool) -> PolicyBuilder: ...
+    def spki_pins(
+        self, new_spki_pins: typing.Iterable[bytes]
+    ) -> PolicyBuilder: ...
+    def issuer_fetcher(
+        self, new_issuer_fetcher: typing.Callable[[str], bytes | None]
+    ) -> PolicyBuilder: ...
+    def certificate_transparency(
+        self,
+        logs: list[CertificateTransparencyLog],
+        *,
+        minimum_scts: int = 2,
+        minimum_operators: int = 2,
+    ) -> PolicyBuilder: ...
+    def ca_extension(
+        self,
+        oid: x509.ObjectIdentifier,
+        *,
+        presence: x509.verification.ExtensionPresence | None = None,
+        criticality: x509.verification.Criticality | None = None,
+        validator: typing.Callable[
+            [x509.Certificate, x509.Extension[x509.ExtensionType] | None],
+            None,
+        ]
+        | None = None,
+    ) -> PolicyBuilder: ...
+    def ee_extension(
+        self,
+        oid: x509.ObjectIdentifier,
+        *,
+        presence: x509.verification.ExtensionPresence | None = None,
+        criticality: x509.verification.Criticality | None = None,
+        validator: typing.Callable[
+            [x509.Certificate, x509.Extension[x509.ExtensionType] | None],
+            None,
+        ]
+        | None = None,
+    ) -> PolicyBuilder: ...
     def build_client_verifier(self) -> ClientVerifier: ...
     def build_server_verifier(
         self, subject: x509.verification.Subject
     ) -> ServerVerifier: ...
+    def build_code_signing_verifier(self) -> ClientVerifier: ...
+    def build_email_protection_verifier(
+        self, subject: x509.RFC822Name
+    ) -> ServerVerifier: ...
+    def build_time_stamping_verifier(self) -> ClientVerifier: ...
+    def build_spiffe_server_verifier(
+        self, subject: x509.UniformResourceIdentifier
+    ) -> ServerVerifier: ...
+    def build_spiffe_client_verifier(
+        self, subject: x509.UniformResourceIdentifier | None = None
+    ) -> ClientVerifier: ...
 
 class VerifiedClient:
     @property
-    def subjects(self) -> list[x509.GeneralName]: ...
+    def subjects(self) -> list[x509.GeneralName] | None: ...
     @property
     def chain(self) -> list[x509.Certificate]: ...
+    @property
+    def trust_anchor(self) -> TrustAnchor | None: ...
+    @property
+    def policies(self) -> list[x509.ObjectIdentifier] | None: ...
+
+class VerifiedChain:
+    @property
+    def chain(self) -> list[x509.Certificate]: ...
+    @property
+    def trust_anchor(self) -> TrustAnchor | None: ...
+    @property
+    def policies(self) -> list[x509.ObjectIdentifier] | None: ...
 
 class ClientVerifier:
     @property
@@ -84,11 +197,34 @@ class ClientVerifier:
         self,
         leaf: x509.Certificate,
         intermediates: list[x509.Certificate],
+        ocsp_responses: list[ocsp.OCSPResponse] | None = None,
     ) -> VerifiedClient: ...
+    def verify_all(
+        self,
+        leaf: x509.Certificate,
+        intermediates: list[x509.Certificate],
+        ocsp_responses: list[ocsp.OCSPResponse] | None = None,
+        *,
+        limit: int = 16,
+        key: typing.Callable[[VerifiedClient], typing.Any] | None = None,
+    ) -> list[VerifiedClient]: ...
+    def verify_many(
+        self,
+        items: list[
+            tuple[x509.Certificate, list[x509.Certificate]]
+            | tuple[
+                x509.Certificate,
+                list[x509.Certificate],
+                list[ocsp.OCSPResponse] | None,
+            ]
+        ],
+        *,
+        max_workers: int | None = None,
+    ) -> list[VerifiedClient | VerificationError]: ...
 
 class ServerVerifier:
     @property
-    def subject(self) -> x509.verification.Subject: ...
+    def subject(self) -> x509.verification.Subject | x509.RFC822Name: ...
     @property
     def validation_time(self) -> datetime.datetime: ...
     @property
@@ -99,10 +235,137 @@ class ServerVerifier:
         self,
         leaf: x509.Certificate,
         intermediates: list[x509.Certificate],
+        ocsp_responses: list[ocsp.OCSPResponse] | None = None,
+    ) -> list[x509.Certificate]: ...
+    def verify_chain(
+        self,
+        leaf: x509.Certificate,
+        intermediates: list[x509.Certificate],
+        ocsp_responses: list[ocsp.OCSPResponse] | None = None,
+    ) -> VerifiedChain: ...
+    def verify_dane(
+        self,
+        leaf: x509.Certificate,
+        intermediates: list[x509.Certificate],
+        tlsa_records: list[TLSARecord],
+        ocsp_responses: list[ocsp.OCSPResponse] | None = None,
     ) -> list[x509.Certificate]: ...
+    def verify_all(
+        self,
+        leaf: x509.Certificate,
+        intermediates: list[x509.Certificate],
+        ocsp_responses: list[ocsp.OCSPResponse] | None = None,
+        *,
+        limit: int = 16,
+        key: typing.Callable[[VerifiedChain], typing.Any] | None = None,
+    ) -> list[VerifiedChain]: ...
+    def verify_many(
+        self,
+        items: list[
+            tuple[x509.Certificate, list[x509.Certificate]]
+            | tuple[
+                x509.Certificate,
+                list[x509.Certificate],
+                list[ocsp.OCSPResponse] | None,
+            ]
+        ],
+        *,
+        max_workers: int | None = None,
+    ) -> list[list[x509.Certificate] | VerificationError]: ...
 
 class Store:
-    def __init__(self, certs: list[x509.Certificate]) -> None: ...
+    def __init__(
+        self, certs: list[x509.Certificate | TrustAnchor]
+    ) -> None: ...
+    @staticmethod
+    def from_pem_bundle(path: str | os.PathLike) -> Store: ...
+    @staticmethod
+    def from_hashed_directory(path: str | os.PathLike) -> Store: ...
+
+class TrustAnchor:
+    def __init__(
+        self,
+        certificate: x509.Certificate,
+        *,
+        name_constraints: x509.NameConstraints | None = None,
+        max_path_length: int | None = None,
+        extended_key_usages: list[x509.ObjectIdentifier] | None = None,
+        spiffe_trust_domain: str | None = None,
+    ) -> None: ...
+    @staticmethod
+    def from_public_key(
+        name: x509.Name,
+        public_key: CertificateIssuerPublicKeyTypes,
+        *,
+        name_constraints: x509.NameConstraints | None = None,
+        max_path_length: int | None = None,
+        extended_key_usages: list[x509.ObjectIdentifier] | None = None,
+        spiffe_trust_domain: str | None = None,
+    ) -> TrustAnchor: ...
+    @property
+    def certificate(self) -> x509.Certificate | None: ...
+    @property
+    def name(self) -> x509.Name: ...
+    def public_key(self) -> CertificateIssuerPublicKeyTypes: ...
+    @property
+    def name_constraints(self) -> x509.NameConstraints | None: ...
+    @property
+    def max_path_length(self) -> int | None: ...
+    @property
+    def extended_key_usages(self) -> list[x509.ObjectIdentifier] | None: ...
+    @property
+    def spiffe_trust_domain(self) -> str | None: ...
+
+class TLSARecord:
+    def __init__(
+        self,
+        usage: x509.verification.TLSAUsage,
+        selector: x509.verification.TLSASelector,
+        matching_type: x509.verification.TLSAMatchingType,
+        data: bytes,
+    ) -> None: ...
+    @property
+    def usage(self) -> x509.verification.TLSAUsage: ...
+    @property
+    def selector(self) -> x509.verification.TLSASelector: ...
+    @property
+    def matching_type(self) -> x509.verification.TLSAMatchingType: ...
+    @property
+    def data(self) -> bytes: ...
+    def matches(self, certificate: x509.Certificate) -> bool: ...
+
+class CertificateTransparencyLog:
+    def __init__(
+        self,
+        log_id: bytes,
+        key: rsa.RSAPublicKey | ec.EllipticCurvePublicKey,
+        operator: str,
+        *,
+        not_before: datetime.datetime | None = None,
+        not_after: datetime.datetime | None = None,
+    ) -> None: ...
+    @property
+    def log_id(self) -> bytes: ...
+    @property
+    def key(self) -> rsa.RSAPublicKey | ec.EllipticCurvePublicKey: ...
+    @property
+    def operator(self) -> str: ...
+    @property
+    def not_before(self) -> datetime.datetime | None: ...
+    @property
+    def not_after(self) -> datetime.datetime | None: ...
+
+class VerificationDiagnostic:
+    @property
+    def certificate(self) -> x509.Certificate: ...
+    @property
+    def depth(self) -> int: ...
+    @property
+    def reason(self) -> x509.verification.FailureReason: ...
+    @property
+    def message(self) -> str: ...
+    @property
+    def candidates(self) -> list[VerificationDiagnostic]: ...
 
 class VerificationError(Exception):
-    pass
+    diagnostic: VerificationDiagnostic
@@ -6,13 +6,30 @@ from __future__ import annotations
 
 import typing
 
+from cryptography import utils
 from cryptography.hazmat.bindings._rust import x509 as rust_x509
-from cryptography.x509.general_name import DNSName, IPAddress
+from cryptography.x509.general_name import (
+    DNSName,
+    IPAddress,
+    UniformResourceIdentifier,
+)
 
 __all__ = [
     "Store",
+    "TrustAnchor",
+    "CertificateTransparencyLog",
     "Subject",
     "VerifiedClient",
+    "VerifiedChain",
+    "VerificationDiagnostic",
+    "FailureReason",
+    "Criticality",
+    "ExtensionPresence",
+    "ValidationModel",
+    "TLSARecord",
+    "TLSAUsage",
+    "TLSASelector",
+    "TLSAMatchingType",
     "ClientVerifier",
     "ServerVerifier",
     "PolicyBuilder",
@@ -20,9 +37,68 @@ __all__ = [
 ]
 
 Store = rust_x509.Store
-Subject = typing.Union[DNSName, IPAddress]
+TrustAnchor = rust_x509.TrustAnchor
+TLSARecord = rust_x509.TLSARecord
+CertificateTransparencyLog = rust_x509.CertificateTransparencyLog
+Subject = typing.Union[DNSName, IPAddress, UniformResourceIdentifier]
 VerifiedClient = rust_x509.VerifiedClient
+VerifiedChain = rust_x509.VerifiedChain
+VerificationDiagnostic = rust_x509.VerificationDiagnostic
 ClientVerifier = rust_x509.ClientVerifier
 ServerVerifier = rust_x509.ServerVerifier
 PolicyBuilder = rust_x509.PolicyBuilder
 VerificationError = rust_x509.VerificationError
+
+
+class FailureReason(utils.Enum):
+    EXPIRED = "expired"
+    NAME_CONSTRAINT_VIOLATION = "name_constraint_violation"
+    EXTENDED_KEY_USAGE_MISMATCH = "extended_key_usage_mismatch"
+    BAD_SIGNATURE = "bad_signature"
+    DEPTH_EXCEEDED = "depth_exceeded"
+    BUDGET_EXHAUSTED = "budget_exhausted"
+    REVOKED = "revoked"
+    REVOCATION_CHECK_FAILED = "revocation_check_failed"
+    CERTIFICATE_POLICY_VIOLATION = "certificate_policy_violation"
+    CERTIFICATE_TRANSPARENCY_VIOLATION = "certificate_transparency_violation"
+    MUST_STAPLE_VIOLATION = "must_staple_violation"
+    DANE_MISMATCH = "dane_mismatch"
+    PIN_MISMATCH = "pin_mismatch"
+    MALFORMED = "malformed"
+    CANDIDATES_EXHAUSTED = "candidates_exhausted"
+    OTHER = "other"
+
+
+class Criticality(utils.Enum):
+    CRITICAL = "critical"
+    AGNOSTIC = "agnostic"
+    NON_CRITICAL = "non_critical"
+
+
+class ExtensionPresence(utils.Enum):
+    REQUIRED = "required"
+    OPTIONAL = "optional"
+    PROHIBITED = "prohibited"
+
+
+class ValidationModel(utils.Enum):
+    SHELL = "shell"
+    CHAIN = "chain"
+
+
+class TLSAUsage(utils.Enum):
+    PKIX_TA = 0
+    PKIX_EE = 1
+    DANE_TA = 2
+    DANE_EE = 3
+
+
+class TLSASelector(utils.Enum):
+    CERT = 0
+    SPKI = 1
+
+
+class TLSAMatchingType(utils.Enum):
+    FULL = 0
+    SHA256 = 1
+    SHA512 = 2
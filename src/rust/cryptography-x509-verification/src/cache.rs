@@ -0,0 +1,186 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+use std::collections::{BTreeMap, HashMap};
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+
+use cryptography_x509::certificate::Certificate;
+use cryptography_x509::common::PSS_SHA256_HASH_ALG;
+
+use crate::ops::CryptoOps;
+
+/// Identifies a signature by the issuer's DER-encoded SPKI and the SHA-256
+/// digest of the child's DER-encoded `TBSCertificate`.
+type SignatureKey = (Vec<u8>, Vec<u8>);
+
+#[derive(Default)]
+struct Entries {
+    // Each key's last use, and the keys by last use, oldest first.
+    last_used: HashMap<SignatureKey, u64>,
+    by_last_use: BTreeMap<u64, SignatureKey>,
+    clock: u64,
+}
+
+impl Entries {
+    fn touch(&mut self, key: &SignatureKey) -> bool {
+        let Some(last_used) = self.last_used.get_mut(key) else {
+            return false;
+        };
+        self.by_last_use.remove(last_used);
+        self.clock += 1;
+        *last_used = self.clock;
+        self.by_last_use.insert(self.clock, key.clone());
+        true
+    }
+}
+
+/// A cache of successful certificate signature verifications, which can be
+/// shared between path builds (including concurrent ones) over common
+/// intermediates.
+///
+/// Only successes are recorded, so a signature that fails to verify is
+/// always checked again. A cache with a capacity evicts the least recently
+/// used signature once it's full.
+#[derive(Default)]
+pub struct SignatureCache {
+    capacity: Option<NonZeroUsize>,
+    skip_leaves: bool,
+    entries: Mutex<Entries>,
+}
+
+impl SignatureCache {
+    /// Returns a cache with no capacity limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a cache that holds at most `capacity` signatures.
+    pub fn with_capacity(capacity: NonZeroUsize) -> Self {
+        SignatureCache {
+            capacity: Some(capacity),
+            ..Self::default()
+        }
+    }
+
+    /// Returns a cache that holds at most `capacity` signatures, and only
+    /// records signatures on the certificates above the leaf of a path.
+    ///
+    /// This suits a cache shared by the verification of many different
+    /// leaves under common intermediates, where each leaf's own signature
+    /// is only ever checked once.
+    pub fn without_leaves(capacity: NonZeroUsize) -> Self {
+        SignatureCache {
+            skip_leaves: true,
+            ..Self::with_capacity(capacity)
+        }
+    }
+
+    /// Returns whether signatures on leaves are left out of the cache.
+    pub(crate) fn skips_leaves(&self) -> bool {
+        self.skip_leaves
+    }
+
+    /// Returns the number of signatures in the cache.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().last_used.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the cache key for `child`'s signature by `issuer`, or `None`
+    /// if the child can't be digested.
+    pub(crate) fn key<B: CryptoOps>(
+        ops: &B,
+        issuer: &Certificate<'_>,
+        child: &Certificate<'_>,
+    ) -> Option<SignatureKey> {
+        let tbs = asn1::write_single(&child.tbs_cert).ok()?;
+        let digest = ops.digest(&PSS_SHA256_HASH_ALG, &tbs).ok()?;
+        Some((issuer.tbs_cert.spki.tlv().full_data().to_vec(), digest))
+    }
+
+    pub(crate) fn contains(&self, key: &SignatureKey) -> bool {
+        self.entries.lock().unwrap().touch(key)
+    }
+
+    pub(crate) fn insert(&self, key: SignatureKey) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.touch(&key) {
+            return;
+        }
+
+        if let Some(capacity) = self.capacity {
+            if entries.last_used.len() >= capacity.get() {
+                // NOTE: `BTreeMap::pop_first` is newer than our MSRV.
+                let oldest = *entries.by_last_use.keys().next().unwrap();
+                let key = entries.by_last_use.remove(&oldest).unwrap();
+                entries.last_used.remove(&key);
+            }
+        }
+        entries.clock += 1;
+        let clock = entries.clock;
+        entries.last_used.insert(key.clone(), clock);
+        entries.by_last_use.insert(clock, key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::SignatureCache;
+    use crate::certificate::tests::PublicKeyErrorOps;
+    use crate::ops::tests::{cert, v1_cert_pem};
+
+    #[test]
+    fn test_signature_cache() {
+        let cert_pem = v1_cert_pem();
+        let cert = cert(&cert_pem);
+        let ops = PublicKeyErrorOps {};
+
+        let cache = SignatureCache::new();
+        assert!(cache.is_empty());
+
+        let key = SignatureCache::key(&ops, &cert, &cert).unwrap();
+        assert!(!cache.contains(&key));
+        cache.insert(key.clone());
+        assert!(cache.contains(&key));
+        cache.insert(key);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_signature_cache_capacity() {
+        let key = |n: u8| (vec![n], vec![n]);
+        let cache = SignatureCache::with_capacity(NonZeroUsize::new(2).unwrap());
+
+        cache.insert(key(1));
+        cache.insert(key(2));
+        // Using 1 makes 2 the least recently used.
+        assert!(cache.contains(&key(1)));
+        cache.insert(key(3));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains(&key(1)));
+        assert!(!cache.contains(&key(2)));
+        assert!(cache.contains(&key(3)));
+
+        // Re-inserting a cached key doesn't evict anything.
+        cache.insert(key(3));
+        assert!(cache.contains(&key(1)));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_signature_cache_without_leaves() {
+        assert!(!SignatureCache::new().skips_leaves());
+        let cache = SignatureCache::without_leaves(NonZeroUsize::new(1).unwrap());
+        assert!(cache.skips_leaves());
+        cache.insert((vec![1], vec![1]));
+        cache.insert((vec![2], vec![2]));
+        assert_eq!(cache.len(), 1);
+    }
+}
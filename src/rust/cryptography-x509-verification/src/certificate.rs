@@ -12,6 +12,8 @@ pub(crate) fn cert_is_self_issued(cert: &Certificate<'_>) -> bool {
 
 #[cfg(test)]
 pub(crate) mod tests {
+    use cryptography_x509::common::{AlgorithmIdentifier, SubjectPublicKeyInfo, WithTlv};
+
     use super::cert_is_self_issued;
     use crate::certificate::Certificate;
     use crate::ops::tests::{cert, v1_cert_pem};
@@ -61,6 +63,13 @@ Xw4nMqk=
             Err(())
         }
 
+        fn spki_public_key(
+            &self,
+            _spki: &WithTlv<'_, SubjectPublicKeyInfo<'_>>,
+        ) -> Result<Self::Key, Self::Err> {
+            Err(())
+        }
+
         fn verify_signed_by(
             &self,
             _cert: &Certificate<'_>,
@@ -68,6 +77,24 @@ Xw4nMqk=
         ) -> Result<(), Self::Err> {
             Ok(())
         }
+
+        fn verify_signature(
+            &self,
+            _key: &Self::Key,
+            _signature_algorithm: &AlgorithmIdentifier<'_>,
+            _signature: &[u8],
+            _data: &[u8],
+        ) -> Result<(), Self::Err> {
+            Ok(())
+        }
+
+        fn digest(
+            &self,
+            _hash_algorithm: &AlgorithmIdentifier<'_>,
+            _data: &[u8],
+        ) -> Result<Vec<u8>, Self::Err> {
+            Ok(vec![])
+        }
     }
 
     #[test]
@@ -87,5 +114,9 @@ Xw4nMqk=
 
         assert!(ops.public_key(&cert).is_err());
         assert!(ops.verify_signed_by(&cert, &()).is_ok());
+        assert!(ops
+            .verify_signature(&(), &cert.signature_alg, &[], &[])
+            .is_ok());
+        assert!(ops.digest(&cert.signature_alg, &[]).is_ok());
     }
 }
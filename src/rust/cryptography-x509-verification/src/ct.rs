@@ -0,0 +1,328 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Certificate Transparency (RFC 6962) policy enforcement during path
+//! validation.
+
+use std::collections::HashSet;
+
+use cryptography_x509::certificate::Certificate;
+use cryptography_x509::common::{
+    AlgorithmIdentifier, AlgorithmParameters, Asn1ReadableOrWritable, SubjectPublicKeyInfo,
+    WithTlv, PSS_SHA256_HASH_ALG,
+};
+use cryptography_x509::extensions::{Extension, Extensions};
+use cryptography_x509::oid::PRECERT_SIGNED_CERTIFICATE_TIMESTAMPS_OID;
+
+use crate::ops::{CryptoOps, VerificationCertificate};
+use crate::policy::{unix_seconds, Policy};
+use crate::{FailureReason, ValidationError};
+
+/// A Certificate Transparency log that a `CTPolicy` accepts SCTs from.
+pub struct CTLog<B: CryptoOps> {
+    /// The log's ID: the SHA-256 hash of its DER-encoded public key.
+    pub id: [u8; 32],
+
+    /// The log's public key, which SCTs from the log are verified with.
+    pub key: B::Key,
+
+    /// The log's operator. SCTs from logs that share an operator are not
+    /// independent of each other.
+    pub operator: String,
+
+    /// If present, only SCTs with a timestamp at or after this time are
+    /// accepted from the log.
+    pub not_before: Option<asn1::DateTime>,
+
+    /// If present, only SCTs with a timestamp before this time are
+    /// accepted from the log, e.g. because the log has been retired.
+    pub not_after: Option<asn1::DateTime>,
+}
+
+/// A `CTPolicy` describes the embedded SCTs that an EE certificate must
+/// carry to be accepted.
+pub struct CTPolicy<B: CryptoOps> {
+    /// The logs that SCTs are accepted from. SCTs from other logs are
+    /// ignored.
+    pub logs: Vec<CTLog<B>>,
+
+    /// The minimum number of distinct logs that must have issued a valid
+    /// SCT for the certificate.
+    pub minimum_scts: usize,
+
+    /// The minimum number of distinct log operators that must be among
+    /// those logs.
+    pub minimum_operators: usize,
+}
+
+impl<B: CryptoOps> CTPolicy<B> {
+    pub fn new(logs: Vec<CTLog<B>>, minimum_scts: usize, minimum_operators: usize) -> Self {
+        Self {
+            logs,
+            minimum_scts,
+            minimum_operators,
+        }
+    }
+}
+
+// RFC 6962 3.2: Structure of the Signed Certificate Timestamp.
+const SCT_VERSION_V1: u8 = 0;
+const SIGNATURE_TYPE_CERTIFICATE_TIMESTAMP: u8 = 0;
+const LOG_ENTRY_TYPE_PRECERT: u16 = 1;
+// RFC 5246 7.4.1.4.1: HashAlgorithm and SignatureAlgorithm.
+const HASH_ALGORITHM_SHA256: u8 = 4;
+const SIGNATURE_ALGORITHM_RSA: u8 = 1;
+const SIGNATURE_ALGORITHM_ECDSA: u8 = 3;
+
+/// A v1 SCT, as embedded in a certificate's SCT list extension.
+struct Sct<'a> {
+    log_id: &'a [u8],
+    timestamp: u64,
+    extensions: &'a [u8],
+    hash_algorithm: u8,
+    signature_algorithm: u8,
+    signature: &'a [u8],
+}
+
+struct TLSReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> TLSReader<'a> {
+    fn read_exact(&mut self, length: usize) -> Option<&'a [u8]> {
+        if length > self.data.len() {
+            return None;
+        }
+        let (result, data) = self.data.split_at(length);
+        self.data = data;
+        Some(result)
+    }
+
+    fn read_byte(&mut self) -> Option<u8> {
+        Some(self.read_exact(1)?[0])
+    }
+
+    fn read_length_prefixed(&mut self) -> Option<&'a [u8]> {
+        let length = u16::from_be_bytes(self.read_exact(2)?.try_into().ok()?);
+        self.read_exact(length.into())
+    }
+}
+
+/// Parses a `SignedCertificateTimestampList` (RFC 6962 3.3). SCTs of
+/// unknown versions are skipped; any other malformation is an error.
+fn parse_sct_list(data: &[u8]) -> Option<Vec<Sct<'_>>> {
+    let mut list = TLSReader {
+        data: TLSReader { data }.read_length_prefixed()?,
+    };
+
+    let mut scts = vec![];
+    while !list.data.is_empty() {
+        let mut sct = TLSReader {
+            data: list.read_length_prefixed()?,
+        };
+        if sct.read_byte()? != SCT_VERSION_V1 {
+            continue;
+        }
+        scts.push(Sct {
+            log_id: sct.read_exact(32)?,
+            timestamp: u64::from_be_bytes(sct.read_exact(8)?.try_into().ok()?),
+            extensions: sct.read_length_prefixed()?,
+            hash_algorithm: sct.read_byte()?,
+            signature_algorithm: sct.read_byte()?,
+            signature: sct.read_length_prefixed()?,
+        });
+        if !sct.data.is_empty() {
+            return None;
+        }
+    }
+    Some(scts)
+}
+
+/// Builds the data that a log signs when issuing `sct` for a precertificate
+/// with the given `TBSCertificate` (RFC 6962 3.2).
+fn precert_signed_data(sct: &Sct<'_>, issuer_key_hash: &[u8], tbs_der: &[u8]) -> Vec<u8> {
+    let mut data = vec![SCT_VERSION_V1, SIGNATURE_TYPE_CERTIFICATE_TIMESTAMP];
+    data.extend_from_slice(&sct.timestamp.to_be_bytes());
+    data.extend_from_slice(&LOG_ENTRY_TYPE_PRECERT.to_be_bytes());
+    data.extend_from_slice(issuer_key_hash);
+    data.extend_from_slice(&(tbs_der.len() as u32).to_be_bytes()[1..]);
+    data.extend_from_slice(tbs_der);
+    data.extend_from_slice(&(sct.extensions.len() as u16).to_be_bytes());
+    data.extend_from_slice(sct.extensions);
+    data
+}
+
+/// Reconstructs the DER-encoded `TBSCertificate` of `cert`'s precertificate,
+/// i.e. `cert`'s `TBSCertificate` with the SCT list extension removed.
+fn precert_tbs_der(
+    cert: &Certificate<'_>,
+    extensions: &Extensions<'_>,
+) -> Result<Vec<u8>, ValidationError> {
+    let mut tbs = cert.tbs_cert.clone();
+    let filtered = extensions
+        .iter()
+        .filter(|e| e.extn_id != PRECERT_SIGNED_CERTIFICATE_TIMESTAMPS_OID)
+        .collect::<Vec<Extension<'_>>>();
+    tbs.raw_extensions = Some(Asn1ReadableOrWritable::new_write(
+        asn1::SequenceOfWriter::new(filtered),
+    ));
+    asn1::write_single(&tbs)
+        .map_err(|_| ValidationError::Other("unable to encode precertificate".to_string()))
+}
+
+/// Checks that `leaf`, as issued by the holder of `issuer_spki`, carries
+/// enough valid embedded SCTs to satisfy `ct`.
+pub(crate) fn permits<B: CryptoOps>(
+    policy: &Policy<'_, B>,
+    ct: &CTPolicy<B>,
+    leaf: &VerificationCertificate<'_, B>,
+    issuer_spki: &WithTlv<'_, SubjectPublicKeyInfo<'_>>,
+) -> Result<(), ValidationError> {
+    let mut logs = HashSet::new();
+    let mut operators = HashSet::new();
+
+    let extensions = leaf.certificate().extensions()?;
+    if let Some(ext) = extensions.get_extension(&PRECERT_SIGNED_CERTIFICATE_TIMESTAMPS_OID) {
+        let scts = parse_sct_list(ext.value::<&[u8]>()?).ok_or_else(|| {
+            ValidationError::Rejected(
+                FailureReason::Malformed,
+                "malformed SCT list extension".to_string(),
+            )
+        })?;
+
+        let tbs_der = precert_tbs_der(leaf.certificate(), &extensions)?;
+        let issuer_key_hash = policy
+            .ops
+            .digest(&PSS_SHA256_HASH_ALG, issuer_spki.tlv().full_data())
+            .map_err(|_| ValidationError::Other("unable to hash issuer public key".to_string()))?;
+        let now = i128::from(unix_seconds(&policy.validation_time)) * 1000;
+
+        for sct in &scts {
+            let Some(log) = ct.logs.iter().find(|l| l.id == sct.log_id) else {
+                continue;
+            };
+
+            // SCTs from the future, or from outside of the log's accepted
+            // period, are not valid.
+            let timestamp = i128::from(sct.timestamp);
+            let before = |dt: &asn1::DateTime| timestamp < i128::from(unix_seconds(dt)) * 1000;
+            if timestamp > now
+                || log.not_before.as_ref().map_or(false, before)
+                || !log.not_after.as_ref().map_or(true, before)
+            {
+                continue;
+            }
+
+            // RFC 6962 2.1.4: logs sign with SHA-256, and either ECDSA or RSA.
+            let params = match (sct.hash_algorithm, sct.signature_algorithm) {
+                (HASH_ALGORITHM_SHA256, SIGNATURE_ALGORITHM_ECDSA) => {
+                    AlgorithmParameters::EcDsaWithSha256(None)
+                }
+                (HASH_ALGORITHM_SHA256, SIGNATURE_ALGORITHM_RSA) => {
+                    AlgorithmParameters::RsaWithSha256(Some(()))
+                }
+                _ => continue,
+            };
+            let signature_algorithm = AlgorithmIdentifier {
+                oid: asn1::DefinedByMarker::marker(),
+                params,
+            };
+
+            if policy
+                .ops
+                .verify_signature(
+                    &log.key,
+                    &signature_algorithm,
+                    sct.signature,
+                    &precert_signed_data(sct, &issuer_key_hash, &tbs_der),
+                )
+                .is_ok()
+            {
+                logs.insert(sct.log_id);
+                operators.insert(log.operator.as_str());
+            }
+        }
+    }
+
+    if logs.len() < ct.minimum_scts || operators.len() < ct.minimum_operators {
+        return Err(ValidationError::Rejected(
+            FailureReason::CertificateTransparencyViolation,
+            format!(
+                "certificate has valid SCTs from {} log(s) and {} operator(s), but policy requires {} and {}",
+                logs.len(),
+                operators.len(),
+                ct.minimum_scts,
+                ct.minimum_operators,
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_sct_list, precert_signed_data};
+
+    fn sct(version: u8, log_id: u8, timestamp: u64, signature: &[u8]) -> Vec<u8> {
+        let mut sct = vec![version];
+        sct.extend_from_slice(&[log_id; 32]);
+        sct.extend_from_slice(&timestamp.to_be_bytes());
+        sct.extend_from_slice(&[0, 0, 4, 3]);
+        sct.extend_from_slice(&(signature.len() as u16).to_be_bytes());
+        sct.extend_from_slice(signature);
+        sct
+    }
+
+    fn sct_list(scts: &[Vec<u8>]) -> Vec<u8> {
+        let mut list = vec![];
+        for sct in scts {
+            list.extend_from_slice(&(sct.len() as u16).to_be_bytes());
+            list.extend_from_slice(sct);
+        }
+        let mut data = (list.len() as u16).to_be_bytes().to_vec();
+        data.extend_from_slice(&list);
+        data
+    }
+
+    #[test]
+    fn test_parse_sct_list() {
+        let data = sct_list(&[sct(0, 1, 1234, b"sig"), sct(1, 2, 5678, b"v2")]);
+        let scts = parse_sct_list(&data).unwrap();
+        // The SCT with an unknown version is skipped.
+        assert_eq!(scts.len(), 1);
+        assert_eq!(scts[0].log_id, &[1; 32]);
+        assert_eq!(scts[0].timestamp, 1234);
+        assert_eq!(scts[0].extensions, b"");
+        assert_eq!(scts[0].hash_algorithm, 4);
+        assert_eq!(scts[0].signature_algorithm, 3);
+        assert_eq!(scts[0].signature, b"sig");
+
+        assert_eq!(parse_sct_list(&sct_list(&[])).unwrap().len(), 0);
+
+        // Truncated list, truncated SCT, and trailing data within an SCT.
+        assert!(parse_sct_list(&data[..data.len() - 1]).is_none());
+        let mut truncated = sct(0, 1, 1234, b"sig");
+        truncated.pop();
+        assert!(parse_sct_list(&sct_list(&[truncated])).is_none());
+        let mut trailing = sct(0, 1, 1234, b"sig");
+        trailing.push(0);
+        assert!(parse_sct_list(&sct_list(&[trailing])).is_none());
+        assert!(parse_sct_list(b"").is_none());
+    }
+
+    #[test]
+    fn test_precert_signed_data() {
+        let data = sct_list(&[sct(0, 1, 0x0102030405060708, b"sig")]);
+        let scts = parse_sct_list(&data).unwrap();
+        let signed = precert_signed_data(&scts[0], &[0xaa; 32], b"tbs");
+
+        let mut expected = vec![0, 0, 1, 2, 3, 4, 5, 6, 7, 8, 0, 1];
+        expected.extend_from_slice(&[0xaa; 32]);
+        expected.extend_from_slice(&[0, 0, 3]);
+        expected.extend_from_slice(b"tbs");
+        expected.extend_from_slice(&[0, 0]);
+        assert_eq!(signed, expected);
+    }
+}
@@ -0,0 +1,213 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! DANE TLSA records (RFC 6698, RFC 7671).
+
+use cryptography_x509::certificate::Certificate;
+use cryptography_x509::common::{
+    SubjectPublicKeyInfo, WithTlv, PSS_SHA256_HASH_ALG, PSS_SHA512_HASH_ALG,
+};
+
+use crate::ops::{CryptoOps, VerificationCertificate};
+
+/// The certificate usage field of a TLSA record (RFC 6698 2.1.1).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TlsaUsage {
+    /// The record matches a CA certificate in a chain that is also valid
+    /// under the usual path validation rules.
+    PkixTa,
+    /// The record matches the EE certificate, which must also be valid
+    /// under the usual path validation rules.
+    PkixEe,
+    /// The record matches a trust anchor for the EE certificate, in place
+    /// of the usual trust store.
+    DaneTa,
+    /// The record matches the EE certificate, which is trusted without
+    /// any path validation.
+    DaneEe,
+}
+
+/// The selector field of a TLSA record (RFC 6698 2.1.2): the part of a
+/// certificate that the record matches.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TlsaSelector {
+    /// The full DER-encoded certificate.
+    Certificate,
+    /// The DER-encoded `SubjectPublicKeyInfo`.
+    SubjectPublicKeyInfo,
+}
+
+/// The matching type field of a TLSA record (RFC 6698 2.1.3): how the
+/// selected data is compared to the record's data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TlsaMatchingType {
+    /// The selected data itself.
+    Full,
+    /// The SHA-256 digest of the selected data.
+    Sha256,
+    /// The SHA-512 digest of the selected data.
+    Sha512,
+}
+
+/// A DANE TLSA record.
+#[derive(Clone, Debug)]
+pub struct TlsaRecord<'a> {
+    pub usage: TlsaUsage,
+    pub selector: TlsaSelector,
+    pub matching_type: TlsaMatchingType,
+    /// The certificate association data.
+    pub data: &'a [u8],
+}
+
+impl<'a> TlsaRecord<'a> {
+    /// Returns the trust anchor public key published by this record, if it's
+    /// a DANE-TA record that carries a full `SubjectPublicKeyInfo` (RFC 7671
+    /// 5.2.2). Such an anchor needn't be presented by the server.
+    pub fn trust_anchor_key(&self) -> Option<WithTlv<'a, SubjectPublicKeyInfo<'a>>> {
+        match (self.usage, self.selector, self.matching_type) {
+            (TlsaUsage::DaneTa, TlsaSelector::SubjectPublicKeyInfo, TlsaMatchingType::Full) => {
+                asn1::parse_single(self.data).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true if `cert` matches this record's selector, matching type
+    /// and data. The record's usage isn't considered.
+    pub fn matches<B: CryptoOps>(&self, ops: &B, cert: &Certificate<'_>) -> bool {
+        let selected = match self.selector {
+            TlsaSelector::Certificate => match asn1::write_single(cert) {
+                Ok(der) => der,
+                Err(_) => return false,
+            },
+            TlsaSelector::SubjectPublicKeyInfo => cert.tbs_cert.spki.tlv().full_data().to_vec(),
+        };
+
+        let hash_alg = match self.matching_type {
+            TlsaMatchingType::Full => return selected == self.data,
+            TlsaMatchingType::Sha256 => &PSS_SHA256_HASH_ALG,
+            TlsaMatchingType::Sha512 => &PSS_SHA512_HASH_ALG,
+        };
+        ops.digest(hash_alg, &selected)
+            .map_or(false, |digest| digest == self.data)
+    }
+
+    /// Returns true if this is a PKIX-TA or PKIX-EE record that `chain`, a
+    /// complete candidate chain in leaf-first order, satisfies.
+    pub(crate) fn permits_chain<B: CryptoOps>(
+        &self,
+        ops: &B,
+        chain: &[VerificationCertificate<'_, B>],
+    ) -> bool {
+        match self.usage {
+            // RFC 7671 5.3: The EE certificate must match.
+            TlsaUsage::PkixEe => chain
+                .first()
+                .map_or(false, |leaf| self.matches(ops, leaf.certificate())),
+            // RFC 7671 5.4: Some CA certificate in the chain (including
+            // the trust anchor) must match.
+            TlsaUsage::PkixTa => chain
+                .iter()
+                .skip(1)
+                .any(|cert| self.matches(ops, cert.certificate())),
+            TlsaUsage::DaneTa | TlsaUsage::DaneEe => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TlsaMatchingType, TlsaRecord, TlsaSelector, TlsaUsage};
+    use crate::certificate::tests::PublicKeyErrorOps;
+    use crate::ops::tests::{cert, v1_cert_pem};
+    use crate::ops::VerificationCertificate;
+
+    #[test]
+    fn test_tlsa_record_matches() {
+        let cert_pem = v1_cert_pem();
+        let cert = cert(&cert_pem);
+        let ops = PublicKeyErrorOps {};
+        let spki = cert.tbs_cert.spki.tlv().full_data();
+
+        let record = |selector, data| TlsaRecord {
+            usage: TlsaUsage::DaneEe,
+            selector,
+            matching_type: TlsaMatchingType::Full,
+            data,
+        };
+        assert!(record(TlsaSelector::Certificate, cert_pem.contents()).matches(&ops, &cert));
+        assert!(record(TlsaSelector::SubjectPublicKeyInfo, spki).matches(&ops, &cert));
+        assert!(!record(TlsaSelector::Certificate, spki).matches(&ops, &cert));
+        assert!(!record(TlsaSelector::SubjectPublicKeyInfo, b"").matches(&ops, &cert));
+    }
+
+    #[test]
+    fn test_tlsa_record_permits_chain() {
+        let cert_pem = v1_cert_pem();
+        let cert = VerificationCertificate::new(cert(&cert_pem), ());
+        let ops = PublicKeyErrorOps {};
+
+        let record = |usage| TlsaRecord {
+            usage,
+            selector: TlsaSelector::Certificate,
+            matching_type: TlsaMatchingType::Full,
+            data: cert_pem.contents(),
+        };
+        assert!(record(TlsaUsage::PkixEe).permits_chain(&ops, std::slice::from_ref(&cert)));
+        assert!(!record(TlsaUsage::PkixTa).permits_chain(&ops, std::slice::from_ref(&cert)));
+        assert!(record(TlsaUsage::PkixTa).permits_chain(&ops, &[cert.clone(), cert.clone()]));
+        assert!(!record(TlsaUsage::DaneEe).permits_chain(&ops, &[cert]));
+    }
+
+    #[test]
+    fn test_tlsa_record_trust_anchor_key() {
+        let cert_pem = v1_cert_pem();
+        let cert = cert(&cert_pem);
+        let spki = cert.tbs_cert.spki.tlv().full_data();
+
+        let record = |usage, selector, matching_type, data| TlsaRecord {
+            usage,
+            selector,
+            matching_type,
+            data,
+        };
+        let key = record(
+            TlsaUsage::DaneTa,
+            TlsaSelector::SubjectPublicKeyInfo,
+            TlsaMatchingType::Full,
+            spki,
+        )
+        .trust_anchor_key();
+        assert!(key.unwrap() == cert.tbs_cert.spki);
+
+        for r in [
+            record(
+                TlsaUsage::PkixTa,
+                TlsaSelector::SubjectPublicKeyInfo,
+                TlsaMatchingType::Full,
+                spki,
+            ),
+            record(
+                TlsaUsage::DaneTa,
+                TlsaSelector::Certificate,
+                TlsaMatchingType::Full,
+                cert_pem.contents(),
+            ),
+            record(
+                TlsaUsage::DaneTa,
+                TlsaSelector::SubjectPublicKeyInfo,
+                TlsaMatchingType::Sha256,
+                spki,
+            ),
+            record(
+                TlsaUsage::DaneTa,
+                TlsaSelector::SubjectPublicKeyInfo,
+                TlsaMatchingType::Full,
+                b"not a key",
+            ),
+        ] {
+            assert!(r.trust_anchor_key().is_none());
+        }
+    }
+}
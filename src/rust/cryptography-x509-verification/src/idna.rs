@@ -0,0 +1,249 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Conversion between the Unicode ("U-label") and ASCII ("A-label") forms of
+//! internationalized domain name labels, per [RFC 5890] and [RFC 3492].
+//!
+//! This is not a complete IDNA2008 implementation: labels are case-folded
+//! with Unicode's default lowercase mapping, but are not NFC-normalized or
+//! checked against the IDNA2008 code point tables.
+//!
+//! [RFC 5890]: https://datatracker.ietf.org/doc/html/rfc5890
+//! [RFC 3492]: https://datatracker.ietf.org/doc/html/rfc3492
+
+const ACE_PREFIX: &str = "xn--";
+
+// RFC 3492 5: Parameter values for Punycode.
+const BASE: u32 = 36;
+const TMIN: u32 = 1;
+const TMAX: u32 = 26;
+const SKEW: u32 = 38;
+const DAMP: u32 = 700;
+const INITIAL_BIAS: u32 = 72;
+const INITIAL_N: u32 = 0x80;
+
+/// Converts `domain` to its ASCII form, lowercasing it and replacing each
+/// U-label with the equivalent A-label. A-labels already in `domain` are
+/// lowercased and checked for validity.
+///
+/// The result is not checked for DNS name validity; callers should parse it
+/// as a `DNSName`.
+pub(crate) fn to_ascii(domain: &str) -> Option<String> {
+    let labels = domain
+        .split('.')
+        .map(|label| {
+            if !label.is_ascii() {
+                return Some(format!(
+                    "{ACE_PREFIX}{}",
+                    punycode_encode(&label.to_lowercase())?
+                ));
+            }
+
+            let label = label.to_ascii_lowercase();
+            if let Some(encoded) = label.strip_prefix(ACE_PREFIX) {
+                // RFC 5890 2.3.2.1: an A-label must decode to a U-label, and
+                // that U-label must encode back to the same A-label.
+                let decoded = punycode_decode(encoded)?;
+                if decoded.is_ascii()
+                    || decoded != decoded.to_lowercase()
+                    || punycode_encode(&decoded)? != encoded
+                {
+                    return None;
+                }
+            }
+            Some(label)
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(labels.join("."))
+}
+
+// RFC 3492 6.1: Bias adaptation function.
+fn adapt(delta: u32, num_points: u32, first_time: bool) -> u32 {
+    let mut delta = if first_time { delta / DAMP } else { delta / 2 };
+    delta += delta / num_points;
+
+    let mut k = 0;
+    while delta > ((BASE - TMIN) * TMAX) / 2 {
+        delta /= BASE - TMIN;
+        k += BASE;
+    }
+
+    k + (((BASE - TMIN + 1) * delta) / (delta + SKEW))
+}
+
+fn threshold(k: u32, bias: u32) -> u32 {
+    if k <= bias {
+        TMIN
+    } else if k >= bias + TMAX {
+        TMAX
+    } else {
+        k - bias
+    }
+}
+
+fn encode_digit(d: u32) -> char {
+    match d {
+        0..=25 => char::from(b'a' + d as u8),
+        _ => char::from(b'0' + (d - 26) as u8),
+    }
+}
+
+fn decode_digit(c: u8) -> Option<u32> {
+    match c {
+        b'a'..=b'z' => Some(u32::from(c - b'a')),
+        b'A'..=b'Z' => Some(u32::from(c - b'A')),
+        b'0'..=b'9' => Some(u32::from(c - b'0') + 26),
+        _ => None,
+    }
+}
+
+// RFC 3492 6.3: Encoding procedure.
+fn punycode_encode(input: &str) -> Option<String> {
+    let input = input.chars().map(u32::from).collect::<Vec<_>>();
+    let mut output = input
+        .iter()
+        .filter(|&&c| c < INITIAL_N)
+        .map(|&c| char::from(c as u8))
+        .collect::<String>();
+
+    let basic_len = output.len() as u32;
+    let mut handled = basic_len;
+    if basic_len > 0 {
+        output.push('-');
+    }
+
+    let mut n = INITIAL_N;
+    let mut delta: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    while (handled as usize) < input.len() {
+        let m = *input.iter().filter(|&&c| c >= n).min()?;
+        delta = delta.checked_add((m - n).checked_mul(handled + 1)?)?;
+        n = m;
+
+        for &c in &input {
+            if c < n {
+                delta = delta.checked_add(1)?;
+            }
+            if c == n {
+                let mut q = delta;
+                let mut k = BASE;
+                loop {
+                    let t = threshold(k, bias);
+                    if q < t {
+                        break;
+                    }
+                    output.push(encode_digit(t + (q - t) % (BASE - t)));
+                    q = (q - t) / (BASE - t);
+                    k += BASE;
+                }
+                output.push(encode_digit(q));
+                bias = adapt(delta, handled + 1, handled == basic_len);
+                delta = 0;
+                handled += 1;
+            }
+        }
+
+        delta = delta.checked_add(1)?;
+        n = n.checked_add(1)?;
+    }
+
+    Some(output)
+}
+
+// RFC 3492 6.2: Decoding procedure.
+fn punycode_decode(input: &str) -> Option<String> {
+    let (basic, extended) = match input.rfind('-') {
+        Some(idx) => (&input[..idx], &input[idx + 1..]),
+        None => ("", input),
+    };
+    if !basic.is_ascii() {
+        return None;
+    }
+
+    let mut output = basic.chars().collect::<Vec<_>>();
+    let mut n = INITIAL_N;
+    let mut i: u32 = 0;
+    let mut bias = INITIAL_BIAS;
+    let mut digits = extended.bytes();
+    while digits.len() > 0 {
+        let old_i = i;
+        let mut w: u32 = 1;
+        let mut k = BASE;
+        loop {
+            let digit = decode_digit(digits.next()?)?;
+            i = i.checked_add(digit.checked_mul(w)?)?;
+            let t = threshold(k, bias);
+            if digit < t {
+                break;
+            }
+            w = w.checked_mul(BASE - t)?;
+            k += BASE;
+        }
+
+        let len = output.len() as u32 + 1;
+        bias = adapt(i - old_i, len, old_i == 0);
+        n = n.checked_add(i / len)?;
+        i %= len;
+        output.insert(i as usize, char::from_u32(n)?);
+        i += 1;
+    }
+
+    Some(output.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{punycode_decode, punycode_encode, to_ascii};
+
+    #[test]
+    fn test_punycode() {
+        // Samples from RFC 3492 7.1, plus some common labels.
+        for (unicode, ascii) in &[
+            ("bücher", "bcher-kva"),
+            ("münchen", "mnchen-3ya"),
+            ("日本語", "wgv71a119e"),
+            ("例え", "r8jz45g"),
+            (
+                "\u{5dc}\u{5de}\u{5d4}\u{5d4}\u{5dd}\u{5e4}\u{5e9}\u{5d5}\u{5d8}\u{5dc}\u{5d0}\u{5de}\u{5d3}\u{5d1}\u{5e8}\u{5d9}\u{5dd}\u{5e2}\u{5d1}\u{5e8}\u{5d9}\u{5ea}",
+                "4dbcagdahymbxekheh6e0a7fei0b",
+            ),
+            (
+                "3\u{5e74}b\u{7d44}\u{91d1}\u{516b}\u{5148}\u{751f}",
+                "3b-ww4c5e180e575a65lsy2b",
+            ),
+            ("abc", "abc-"),
+        ] {
+            assert_eq!(punycode_encode(unicode).as_deref(), Some(*ascii));
+            assert_eq!(punycode_decode(ascii).as_deref(), Some(*unicode));
+        }
+
+        for bad in &["bcher-kv!", "bücher-kva", "99999999999"] {
+            assert_eq!(punycode_decode(bad), None, "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_to_ascii() {
+        for (domain, ascii) in &[
+            ("example.com", Some("example.com")),
+            ("EXAMPLE.com", Some("example.com")),
+            ("bücher.example", Some("xn--bcher-kva.example")),
+            ("BÜCHER.example", Some("xn--bcher-kva.example")),
+            ("mail.日本語.jp", Some("mail.xn--wgv71a119e.jp")),
+            // A-labels are passed through, after validation.
+            ("xn--bcher-kva.example", Some("xn--bcher-kva.example")),
+            ("XN--BCHER-KVA.example", Some("xn--bcher-kva.example")),
+            ("xn--Bcher-kva.example", Some("xn--bcher-kva.example")),
+            // Not valid punycode.
+            ("xn--b!cher.example", None),
+            // Decodes to an all-ASCII label.
+            ("xn--abc-.example", None),
+            // Decodes to a label containing uppercase.
+            ("xn--bcher-2pa.example", None),
+        ] {
+            assert_eq!(to_ascii(domain).as_deref(), *ascii, "{domain}");
+        }
+    }
+}
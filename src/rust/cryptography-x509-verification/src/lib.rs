@@ -6,28 +6,53 @@
 #![deny(rust_2018_idioms, clippy::undocumented_unsafe_blocks)]
 #![allow(unknown_lints, clippy::result_large_err)]
 
+pub mod cache;
 pub mod certificate;
+pub mod ct;
+pub mod dane;
+mod idna;
 pub mod ops;
 pub mod policy;
+pub mod policy_tree;
+pub mod revocation;
 pub mod trust_store;
 pub mod types;
 
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::time::Instant;
 use std::vec;
 
 use cryptography_x509::extensions::{DuplicateExtensionsError, Extensions};
+use cryptography_x509::ocsp_resp::BasicOCSPResponse;
 use cryptography_x509::{
-    extensions::{NameConstraints, SubjectAlternativeName},
-    name::GeneralName,
-    oid::{NAME_CONSTRAINTS_OID, SUBJECT_ALTERNATIVE_NAME_OID},
+    certificate::Certificate,
+    common::Asn1ReadableOrWritable,
+    extensions::{
+        AuthorityKeyIdentifier, NameConstraints, SequenceOfAccessDescriptions,
+        SubjectAlternativeName,
+    },
+    name::{GeneralName, NameReadable},
+    oid::{
+        AUTHORITY_INFORMATION_ACCESS_OID, AUTHORITY_KEY_IDENTIFIER_OID, CA_ISSUERS_OID,
+        EKU_ANY_KEY_USAGE_OID, NAME_CONSTRAINTS_OID, SMTP_UTF8_MAILBOX_OID,
+        SUBJECT_ALTERNATIVE_NAME_OID,
+    },
 };
 use types::{RFC822Constraint, RFC822Name};
 
+use crate::cache::SignatureCache;
 use crate::certificate::cert_is_self_issued;
+use crate::dane::{TlsaRecord, TlsaUsage};
 use crate::ops::{CryptoOps, VerificationCertificate};
 use crate::policy::Policy;
-use crate::trust_store::Store;
+use crate::trust_store::{AnchorConstraints, CertificatePool, KeyAnchor, Store};
 use crate::types::DNSName;
-use crate::types::{DNSConstraint, IPAddress, IPConstraint};
+use crate::types::{
+    DNSConstraint, DirectoryNameConstraint, IPAddress, IPConstraint, SmtpUTF8Mailbox,
+    SmtpUTF8MailboxConstraint, SpiffeId, URIConstraint, URIName,
+};
 use crate::ApplyNameConstraintStatus::{Applied, Skipped};
 
 #[derive(Debug)]
@@ -36,23 +61,158 @@ pub enum ValidationError {
     Malformed(asn1::ParseError),
     DuplicateExtension(DuplicateExtensionsError),
     FatalError(&'static str),
+    Revoked(String),
+    Rejected(FailureReason, String),
     Other(String),
 }
 
-struct Budget {
+impl ValidationError {
+    /// Returns a machine-readable classification of this error.
+    pub fn reason(&self) -> FailureReason {
+        match self {
+            ValidationError::CandidatesExhausted(_) => FailureReason::CandidatesExhausted,
+            ValidationError::Malformed(_) | ValidationError::DuplicateExtension(_) => {
+                FailureReason::Malformed
+            }
+            // NOTE: All fatal errors are currently budget exhaustion, except
+            // for an aborted issuer fetch, which the fetcher's caller is
+            // expected to report itself.
+            ValidationError::FatalError(_) => FailureReason::BudgetExhausted,
+            ValidationError::Revoked(_) => FailureReason::Revoked,
+            ValidationError::Rejected(reason, _) => *reason,
+            ValidationError::Other(_) => FailureReason::Other,
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::CandidatesExhausted(_) => {
+                write!(f, "no candidate issuer led to a trust anchor")
+            }
+            ValidationError::Malformed(e) => write!(f, "malformed certificate: {e}"),
+            ValidationError::DuplicateExtension(e) => {
+                write!(f, "certificate contains duplicate extension: {}", e.0)
+            }
+            ValidationError::FatalError(msg) => write!(f, "{msg}"),
+            ValidationError::Revoked(msg)
+            | ValidationError::Rejected(_, msg)
+            | ValidationError::Other(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+/// A machine-readable classification of why a certificate was rejected
+/// during path validation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailureReason {
+    /// The certificate is not valid at the validation time.
+    Expired,
+    /// The certificate violates a name constraint in the path.
+    NameConstraintViolation,
+    /// The certificate doesn't have the extended key usage the policy requires.
+    ExtendedKeyUsageMismatch,
+    /// The certificate's signature couldn't be verified by its issuer.
+    BadSignature,
+    /// The path exceeds the policy's maximum depth, or an issuer's path
+    /// length constraint.
+    DepthExceeded,
+    /// Path building exceeded one of its resource limits.
+    BudgetExhausted,
+    /// The certificate has been revoked.
+    Revoked,
+    /// The certificate's revocation status couldn't be established.
+    RevocationCheckFailed,
+    /// The path isn't valid for any acceptable certificate policy.
+    CertificatePolicyViolation,
+    /// The leaf doesn't carry the SCTs required by the policy's
+    /// Certificate Transparency policy.
+    CertificateTransparencyViolation,
+    /// The certificate requires a stapled OCSP response (RFC 7633
+    /// "must-staple"), but none was supplied.
+    MustStapleViolation,
+    /// The chain doesn't match any of the DANE TLSA records it was
+    /// validated against.
+    DaneMismatch,
+    /// None of the chain's public keys are pinned.
+    PinMismatch,
+    /// The certificate is malformed.
+    Malformed,
+    /// None of the certificate's candidate issuers led to a trust anchor.
+    CandidatesExhausted,
+    /// The certificate was rejected for another reason, such as a violation
+    /// of the policy's certificate profile.
+    Other,
+}
+
+pub(crate) struct Budget {
     name_constraint_checks: usize,
+    issuer_fetches: usize,
+    signature_checks: usize,
+    candidates: usize,
+    deadline: Option<Instant>,
 }
 
 impl Budget {
     // Same limit as other validators
     const DEFAULT_NAME_CONSTRAINT_CHECK_LIMIT: usize = 1 << 20;
 
-    fn new() -> Budget {
+    fn new<B: CryptoOps>(policy: &Policy<'_, B>) -> Budget {
         Budget {
             name_constraint_checks: Self::DEFAULT_NAME_CONSTRAINT_CHECK_LIMIT,
+            issuer_fetches: policy.max_issuer_fetches,
+            signature_checks: policy.max_signature_checks,
+            candidates: policy.max_candidates,
+            // NOTE: A time limit too large to represent is no limit at all.
+            deadline: policy
+                .max_path_building_time
+                .and_then(|limit| Instant::now().checked_add(limit)),
+        }
+    }
+
+    fn check_deadline(&self) -> Result<(), ValidationError> {
+        match self.deadline {
+            Some(deadline) if Instant::now() >= deadline => Err(ValidationError::FatalError(
+                "Exceeded maximum path building time",
+            )),
+            _ => Ok(()),
         }
     }
 
+    fn candidate(&mut self) -> Result<(), ValidationError> {
+        self.check_deadline()?;
+        self.candidates = self
+            .candidates
+            .checked_sub(1)
+            .ok_or(ValidationError::FatalError(
+                "Exceeded maximum candidate issuer limit",
+            ))?;
+        Ok(())
+    }
+
+    pub(crate) fn signature_check(&mut self) -> Result<(), ValidationError> {
+        self.check_deadline()?;
+        self.signature_checks =
+            self.signature_checks
+                .checked_sub(1)
+                .ok_or(ValidationError::FatalError(
+                    "Exceeded maximum signature check limit",
+                ))?;
+        Ok(())
+    }
+
+    fn issuer_fetch(&mut self) -> Result<(), ValidationError> {
+        self.check_deadline()?;
+        self.issuer_fetches =
+            self.issuer_fetches
+                .checked_sub(1)
+                .ok_or(ValidationError::FatalError(
+                    "Exceeded maximum issuer fetch limit",
+                ))?;
+        Ok(())
+    }
+
     fn name_constraint_check(&mut self) -> Result<(), ValidationError> {
         self.name_constraint_checks =
             self.name_constraint_checks
@@ -79,11 +239,13 @@ impl From<DuplicateExtensionsError> for ValidationError {
 struct NameChain<'a, 'chain> {
     child: Option<&'a NameChain<'a, 'chain>>,
     sans: SubjectAlternativeName<'chain>,
+    subject: Option<NameReadable<'chain>>,
 }
 
 impl<'a, 'chain> NameChain<'a, 'chain> {
     fn new(
         child: Option<&'a NameChain<'a, 'chain>>,
+        cert: &Certificate<'chain>,
         extensions: &Extensions<'chain>,
         self_issued_intermediate: bool,
     ) -> Result<Self, ValidationError> {
@@ -97,7 +259,20 @@ impl<'a, 'chain> NameChain<'a, 'chain> {
             _ => asn1::parse_single(b"\x30\x00")?,
         };
 
-        Ok(Self { child, sans })
+        // Per RFC 5280 4.2.1.10: directoryName constraints are also applied
+        // to the subject, which is skipped when empty (as is typical for
+        // certificates that identify their subject solely via SANs).
+        let subject = cert.tbs_cert.subject.unwrap_read();
+        let subject = match (self_issued_intermediate, subject.clone().next()) {
+            (false, Some(_)) => Some(subject.clone()),
+            _ => None,
+        };
+
+        Ok(Self {
+            child,
+            sans,
+            subject,
+        })
     }
 
     fn evaluate_single_constraint(
@@ -112,14 +287,17 @@ impl<'a, 'chain> NameChain<'a, 'chain> {
             (GeneralName::DNSName(pattern), GeneralName::DNSName(name)) => {
                 match (DNSConstraint::new(pattern.0), DNSName::new(name.0)) {
                     (Some(pattern), Some(name)) => Ok(Applied(pattern.matches(&name))),
-                    (_, None) => Err(ValidationError::Other(format!(
-                        "unsatisfiable DNS name constraint: malformed SAN {}",
-                        name.0
-                    ))),
-                    (None, _) => Err(ValidationError::Other(format!(
-                        "malformed DNS name constraint: {}",
-                        pattern.0
-                    ))),
+                    (_, None) => Err(ValidationError::Rejected(
+                        FailureReason::NameConstraintViolation,
+                        format!(
+                            "unsatisfiable DNS name constraint: malformed SAN {}",
+                            name.0
+                        ),
+                    )),
+                    (None, _) => Err(ValidationError::Rejected(
+                        FailureReason::NameConstraintViolation,
+                        format!("malformed DNS name constraint: {}", pattern.0),
+                    )),
                 }
             }
             (GeneralName::IPAddress(pattern), GeneralName::IPAddress(name)) => {
@@ -128,83 +306,176 @@ impl<'a, 'chain> NameChain<'a, 'chain> {
                     IPAddress::from_bytes(name),
                 ) {
                     (Some(pattern), Some(name)) => Ok(Applied(pattern.matches(&name))),
-                    (_, None) => Err(ValidationError::Other(format!(
-                        "unsatisfiable IP name constraint: malformed SAN {:?}",
-                        name,
-                    ))),
-                    (None, _) => Err(ValidationError::Other(format!(
-                        "malformed IP name constraints: {:?}",
-                        pattern
-                    ))),
+                    (_, None) => Err(ValidationError::Rejected(
+                        FailureReason::NameConstraintViolation,
+                        format!("unsatisfiable IP name constraint: malformed SAN {:?}", name,),
+                    )),
+                    (None, _) => Err(ValidationError::Rejected(
+                        FailureReason::NameConstraintViolation,
+                        format!("malformed IP name constraints: {:?}", pattern),
+                    )),
                 }
             }
             (GeneralName::RFC822Name(pattern), GeneralName::RFC822Name(name)) => {
                 match (RFC822Constraint::new(pattern.0), RFC822Name::new(name.0)) {
                     (Some(pattern), Some(name)) => Ok(Applied(pattern.matches(&name))),
-                    (_, None) => Err(ValidationError::Other(format!(
-                        "unsatisfiable RFC822 name constraint: malformed SAN {:?}",
+                    (_, None) => Err(ValidationError::Rejected(
+                        FailureReason::NameConstraintViolation,
+                        format!(
+                            "unsatisfiable RFC822 name constraint: malformed SAN {:?}",
+                            name.0,
+                        ),
+                    )),
+                    (None, _) => Err(ValidationError::Rejected(
+                        FailureReason::NameConstraintViolation,
+                        format!("malformed RFC822 name constraints: {:?}", pattern.0),
+                    )),
+                }
+            }
+            (GeneralName::DirectoryName(pattern), GeneralName::DirectoryName(name)) => {
+                let pattern = DirectoryNameConstraint::new(pattern.unwrap_read().clone());
+                Ok(Applied(pattern.matches(name.unwrap_read())))
+            }
+            (
+                GeneralName::UniformResourceIdentifier(pattern),
+                GeneralName::UniformResourceIdentifier(name),
+            ) => match (URIConstraint::new(pattern.0), URIName::new(name.0)) {
+                (Some(pattern), Some(name)) => Ok(Applied(pattern.matches(&name))),
+                (_, None) => Err(ValidationError::Rejected(
+                    FailureReason::NameConstraintViolation,
+                    format!(
+                        "unsatisfiable URI name constraint: SAN has no domain name host {:?}",
                         name.0,
-                    ))),
-                    (None, _) => Err(ValidationError::Other(format!(
-                        "malformed RFC822 name constraints: {:?}",
-                        pattern.0
-                    ))),
+                    ),
+                )),
+                (None, _) => Err(ValidationError::Rejected(
+                    FailureReason::NameConstraintViolation,
+                    format!("malformed URI name constraint: {:?}", pattern.0),
+                )),
+            },
+            // Per RFC 9598 6, rfc822Name constraints also apply to
+            // SmtpUTF8Mailbox names. SmtpUTF8Mailbox constraints are likewise
+            // applied to both forms of email address.
+            (
+                GeneralName::RFC822Name(_) | GeneralName::OtherName(_),
+                GeneralName::RFC822Name(_) | GeneralName::OtherName(_),
+            ) if is_smtp_utf8_mailbox(constraint) || is_smtp_utf8_mailbox(san) => {
+                let (Some(pattern), Some(name)) = (email_address(constraint), email_address(san))
+                else {
+                    return Ok(Skipped);
+                };
+
+                // An undecodable SmtpUTF8Mailbox is treated as empty, which
+                // is never a valid address or constraint.
+                let pattern = pattern.unwrap_or_default();
+                let name = name.unwrap_or_default();
+                match (
+                    SmtpUTF8MailboxConstraint::new(pattern),
+                    SmtpUTF8Mailbox::new(name),
+                ) {
+                    (Some(pattern), Some(name)) => Ok(Applied(pattern.matches(&name))),
+                    (_, None) => Err(ValidationError::Rejected(
+                        FailureReason::NameConstraintViolation,
+                        format!(
+                            "unsatisfiable SmtpUTF8Mailbox name constraint: malformed SAN {:?}",
+                            name,
+                        ),
+                    )),
+                    (None, _) => Err(ValidationError::Rejected(
+                        FailureReason::NameConstraintViolation,
+                        format!("malformed SmtpUTF8Mailbox name constraint: {:?}", pattern),
+                    )),
                 }
             }
             // All other matching pairs of (constraint, name) are currently unsupported.
             (GeneralName::OtherName(_), GeneralName::OtherName(_))
             | (GeneralName::X400Address(_), GeneralName::X400Address(_))
-            | (GeneralName::DirectoryName(_), GeneralName::DirectoryName(_))
             | (GeneralName::EDIPartyName(_), GeneralName::EDIPartyName(_))
-            | (
-                GeneralName::UniformResourceIdentifier(_),
-                GeneralName::UniformResourceIdentifier(_),
-            )
-            | (GeneralName::RegisteredID(_), GeneralName::RegisteredID(_)) => Err(
-                ValidationError::Other("unsupported name constraint".to_string()),
-            ),
+            | (GeneralName::RegisteredID(_), GeneralName::RegisteredID(_)) => {
+                Err(ValidationError::Rejected(
+                    FailureReason::NameConstraintViolation,
+                    "unsupported name constraint".to_string(),
+                ))
+            }
             _ => Ok(Skipped),
         }
     }
 
+    /// Returns the chain's leaf, i.e. its innermost child.
+    fn leaf(&self) -> &Self {
+        self.child.map_or(self, |child| child.leaf())
+    }
+
+    // `constraints` are the name constraints of the certificate at the head
+    // of this chain. Its subject is exempt from them: per RFC 5280 6.1.3, a
+    // certificate's name constraints only apply to the certificates below it.
     fn evaluate_constraints(
         &self,
         constraints: &NameConstraints<'chain>,
         budget: &mut Budget,
+    ) -> Result<(), ValidationError> {
+        self.evaluate_constraints_inner(constraints, true, budget)
+    }
+
+    fn evaluate_constraints_inner(
+        &self,
+        constraints: &NameConstraints<'chain>,
+        is_constraining_cert: bool,
+        budget: &mut Budget,
     ) -> Result<(), ValidationError> {
         if let Some(child) = self.child {
-            child.evaluate_constraints(constraints, budget)?;
+            child.evaluate_constraints_inner(constraints, false, budget)?;
         }
 
         for san in self.sans.clone() {
-            // If there are no applicable constraints, the SAN is considered valid so the default is true.
-            let mut permit = true;
-            if let Some(permitted_subtrees) = &constraints.permitted_subtrees {
-                for p in permitted_subtrees.unwrap_read().clone() {
-                    let status = self.evaluate_single_constraint(&p.base, &san, budget)?;
-                    if status.is_applied() {
-                        permit = status.is_match();
-                        if permit {
-                            break;
-                        }
+            self.evaluate_name(constraints, &san, "SAN", budget)?;
+        }
+
+        if let (false, Some(subject)) = (is_constraining_cert, &self.subject) {
+            let subject =
+                GeneralName::DirectoryName(Asn1ReadableOrWritable::new_read(subject.clone()));
+            self.evaluate_name(constraints, &subject, "subject", budget)?;
+        }
+
+        Ok(())
+    }
+
+    fn evaluate_name(
+        &self,
+        constraints: &NameConstraints<'chain>,
+        name: &GeneralName<'chain>,
+        kind: &str,
+        budget: &mut Budget,
+    ) -> Result<(), ValidationError> {
+        // If there are no applicable constraints, the name is considered valid so the default is true.
+        let mut permit = true;
+        if let Some(permitted_subtrees) = &constraints.permitted_subtrees {
+            for p in permitted_subtrees.unwrap_read().clone() {
+                let status = self.evaluate_single_constraint(&p.base, name, budget)?;
+                if status.is_applied() {
+                    permit = status.is_match();
+                    if permit {
+                        break;
                     }
                 }
             }
+        }
 
-            if !permit {
-                return Err(ValidationError::Other(
-                    "no permitted name constraints matched SAN".into(),
-                ));
-            }
+        if !permit {
+            return Err(ValidationError::Rejected(
+                FailureReason::NameConstraintViolation,
+                format!("no permitted name constraints matched {kind}"),
+            ));
+        }
 
-            if let Some(excluded_subtrees) = &constraints.excluded_subtrees {
-                for e in excluded_subtrees.unwrap_read().clone() {
-                    let status = self.evaluate_single_constraint(&e.base, &san, budget)?;
-                    if status.is_match() {
-                        return Err(ValidationError::Other(
-                            "excluded name constraint matched SAN".into(),
-                        ));
-                    }
+        if let Some(excluded_subtrees) = &constraints.excluded_subtrees {
+            for e in excluded_subtrees.unwrap_read().clone() {
+                let status = self.evaluate_single_constraint(&e.base, name, budget)?;
+                if status.is_match() {
+                    return Err(ValidationError::Rejected(
+                        FailureReason::NameConstraintViolation,
+                        format!("excluded name constraint matched {kind}"),
+                    ));
                 }
             }
         }
@@ -213,24 +484,415 @@ impl<'a, 'chain> NameChain<'a, 'chain> {
     }
 }
 
+fn is_smtp_utf8_mailbox(name: &GeneralName<'_>) -> bool {
+    matches!(name, GeneralName::OtherName(other) if other.type_id == SMTP_UTF8_MAILBOX_OID)
+}
+
+// Returns the email address in an rfc822Name or SmtpUTF8Mailbox name, or
+// `None` if the name is of another form.
+pub(crate) fn email_address<'a>(
+    name: &GeneralName<'a>,
+) -> Option<Result<&'a str, asn1::ParseError>> {
+    match name {
+        GeneralName::RFC822Name(address) => Some(Ok(address.0)),
+        GeneralName::OtherName(other) if other.type_id == SMTP_UTF8_MAILBOX_OID => {
+            // SmtpUTF8Mailbox ::= UTF8String (SIZE (1..MAX))
+            Some(
+                other
+                    .value
+                    .parse::<asn1::Utf8String<'a>>()
+                    .map(|a| a.as_str()),
+            )
+        }
+        _ => None,
+    }
+}
+
 pub type Chain<'c, B> = Vec<VerificationCertificate<'c, B>>;
 
+/// A record of a certificate that path building attempted to build a path
+/// through, and why no valid path was found through it.
+pub struct Diagnostic<'chain, B: CryptoOps> {
+    /// The certificate that was attempted.
+    pub certificate: VerificationCertificate<'chain, B>,
+
+    /// The certificate's depth in the attempted path. The leaf is at depth 0.
+    pub depth: u8,
+
+    /// Why the certificate was rejected. This is
+    /// `FailureReason::CandidatesExhausted` if the certificate itself was
+    /// acceptable, but none of its candidate issuers were.
+    pub reason: FailureReason,
+
+    /// A human-readable description of the failure.
+    pub message: String,
+
+    /// The attempts made through each of the certificate's candidate
+    /// issuers, in the order that they were tried.
+    pub candidates: Vec<Diagnostic<'chain, B>>,
+}
+
+impl<'chain, B: CryptoOps> Diagnostic<'chain, B> {
+    fn new(
+        certificate: &VerificationCertificate<'chain, B>,
+        depth: u8,
+        error: &ValidationError,
+        candidates: Vec<Diagnostic<'chain, B>>,
+    ) -> Self {
+        Diagnostic {
+            certificate: certificate.clone(),
+            depth,
+            reason: error.reason(),
+            message: error.to_string(),
+            candidates,
+        }
+    }
+}
+
+/// The result of a failed path validation.
+pub struct VerificationFailure<'chain, B: CryptoOps> {
+    /// The error that caused path validation to fail.
+    pub error: ValidationError,
+
+    /// The tree of attempted paths, rooted at the leaf.
+    pub diagnostic: Diagnostic<'chain, B>,
+}
+
+/// A chain that has been successfully validated, along with the state
+/// produced by validating it.
+pub struct VerifiedChain<'c, B: CryptoOps> {
+    /// The chain, in leaf-first order. Unless `anchor` is set, the last
+    /// member is the trust anchor.
+    pub chain: Chain<'c, B>,
+
+    /// The trust anchor without a certificate that issued the last member
+    /// of `chain`, if the chain ends at one.
+    pub anchor: Option<KeyAnchor<'c, B>>,
+
+    /// The authority-constrained policy set (RFC 5280 6.1.6) of the chain,
+    /// if the policy enables certificate policy processing.
+    pub policies: Option<Vec<asn1::ObjectIdentifier>>,
+}
+
+/// A source of issuer certificates beyond those supplied to path
+/// validation, such as the certificates published at the Authority
+/// Information Access (AIA) `caIssuers` URIs of a certificate.
+pub trait IssuerFetcher<'chain, B: CryptoOps> {
+    /// Returns the certificates published at `uri`.
+    fn fetch(&self, uri: &str)
+        -> Result<Vec<VerificationCertificate<'chain, B>>, IssuerFetchError>;
+}
+
+/// The ways in which an `IssuerFetcher` can fail.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IssuerFetchError {
+    /// The certificates couldn't be fetched, for the given reason. Path
+    /// building continues without them, and the URI isn't fetched again.
+    Failed(String),
+    /// Path building must stop, e.g. because the fetcher was interrupted.
+    Aborted,
+}
+
 pub fn verify<'chain, B: CryptoOps>(
     leaf: &VerificationCertificate<'chain, B>,
     intermediates: impl IntoIterator<Item = VerificationCertificate<'chain, B>>,
+    ocsp_responses: &[&BasicOCSPResponse<'_>],
+    policy: &Policy<'_, B>,
+    store: &Store<'chain, B>,
+    fetcher: Option<&dyn IssuerFetcher<'chain, B>>,
+    signature_cache: Option<&SignatureCache>,
+) -> Result<VerifiedChain<'chain, B>, VerificationFailure<'chain, B>> {
+    let mut chains = verify_all(
+        leaf,
+        intermediates,
+        ocsp_responses,
+        policy,
+        store,
+        fetcher,
+        signature_cache,
+        NonZeroUsize::new(1).unwrap(),
+    )?;
+    // NOTE: `verify_all` only succeeds with at least one chain.
+    Ok(chains.swap_remove(0))
+}
+
+/// Like `verify`, but returns up to `limit` valid chains instead of just
+/// the first.
+///
+/// Chains are returned in the order they're found, which is the order
+/// `verify` would consider them in. Path building stops once `limit` chains
+/// have been found; the same `Budget` applies to the entire search, so a
+/// fatal budget error fails the call even if some chains were found. Callers
+/// that want a different preference (e.g. the shortest chain, or a
+/// particular trust anchor) should use `verify_ranked` instead, since the
+/// chains found first needn't be the ones they prefer.
+///
+/// If a `fetcher` is supplied, it's consulted for the AIA `caIssuers` URIs
+/// of any certificate whose supplied candidate issuers all fail.
+///
+/// If a `signature_cache` is supplied, certificate signatures that it has
+/// already seen verify are not checked again, and don't count against the
+/// signature check budget.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_all<'chain, B: CryptoOps>(
+    leaf: &VerificationCertificate<'chain, B>,
+    intermediates: impl IntoIterator<Item = VerificationCertificate<'chain, B>>,
+    ocsp_responses: &[&BasicOCSPResponse<'_>],
     policy: &Policy<'_, B>,
     store: &Store<'chain, B>,
-) -> Result<Chain<'chain, B>, ValidationError> {
-    let builder = ChainBuilder::new(intermediates.into_iter().collect(), policy, store);
+    fetcher: Option<&dyn IssuerFetcher<'chain, B>>,
+    signature_cache: Option<&SignatureCache>,
+    limit: NonZeroUsize,
+) -> Result<Vec<VerifiedChain<'chain, B>>, VerificationFailure<'chain, B>> {
+    let builder = ChainBuilder::new(
+        CertificatePool::new(intermediates),
+        ocsp_responses,
+        policy,
+        store,
+        fetcher,
+        signature_cache,
+    );
 
-    let mut budget = Budget::new();
-    builder.build_chain(leaf, &mut budget)
+    let mut budget = Budget::new(policy);
+    let mut chains = ChainSink::new(Some(limit));
+    builder.build_chain(leaf, &mut chains, &mut budget)?;
+    Ok(chains.chains)
 }
 
-struct ChainBuilder<'a, 'chain, B: CryptoOps> {
+/// Like `verify_all`, but returns the best `limit` valid chains according
+/// to `rank`, rather than the first `limit` that are found.
+///
+/// Every valid chain is enumerated before any are ranked, within the same
+/// `Budget` as `verify_all`. If the budget runs out after at least one
+/// chain was found, the search ends there; otherwise the fatal budget error
+/// is returned. `rank` is then called once with the chains found, in the
+/// order they were found, and should sort them best first.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_ranked<'chain, B: CryptoOps>(
+    leaf: &VerificationCertificate<'chain, B>,
+    intermediates: impl IntoIterator<Item = VerificationCertificate<'chain, B>>,
+    ocsp_responses: &[&BasicOCSPResponse<'_>],
+    policy: &Policy<'_, B>,
+    store: &Store<'chain, B>,
+    fetcher: Option<&dyn IssuerFetcher<'chain, B>>,
+    signature_cache: Option<&SignatureCache>,
+    limit: NonZeroUsize,
+    rank: impl FnOnce(&mut Vec<VerifiedChain<'chain, B>>),
+) -> Result<Vec<VerifiedChain<'chain, B>>, VerificationFailure<'chain, B>> {
+    let builder = ChainBuilder::new(
+        CertificatePool::new(intermediates),
+        ocsp_responses,
+        policy,
+        store,
+        fetcher,
+        signature_cache,
+    );
+
+    let mut budget = Budget::new(policy);
+    let mut chains = ChainSink::new(None);
+    match builder.build_chain(leaf, &mut chains, &mut budget) {
+        Ok(()) => (),
+        Err(VerificationFailure {
+            error: ValidationError::FatalError(..),
+            ..
+        }) if !chains.chains.is_empty() => (),
+        Err(e) => return Err(e),
+    }
+
+    let mut chains = chains.chains;
+    rank(&mut chains);
+    chains.truncate(limit.get());
+    Ok(chains)
+}
+
+/// Validates `leaf` against DANE TLSA `records` (RFC 6698, RFC 7671), as
+/// an alternative to `verify`. The records are tried by usage:
+///
+/// * A DANE-EE record that matches `leaf` trusts it outright, without path
+///   building or any other checks (RFC 7671 5.1). The resulting chain is
+///   just `leaf`.
+/// * The intermediates that match a DANE-TA record are used as the trust
+///   anchors for path building, in place of `store` (RFC 7671 5.2). They
+///   needn't be self-issued. A DANE-TA record that carries a full public key
+///   is an anchor itself, even if no certificate for it is presented: it
+///   terminates any path whose last certificate is signed by that key
+///   (RFC 7671 5.2.2). Callers that want the same for DANE-TA records that
+///   carry a full certificate should parse it and include it in
+///   `intermediates`, where it can also serve as a chain element.
+/// * If there are any PKIX-TA or PKIX-EE records, path building uses
+///   `store` as usual, but also requires the chain to match one of them.
+///
+/// If none of these succeed, the failure from the last one attempted is
+/// returned.
+#[allow(clippy::too_many_arguments)]
+pub fn verify_dane<'chain, B: CryptoOps>(
+    leaf: &VerificationCertificate<'chain, B>,
     intermediates: Vec<VerificationCertificate<'chain, B>>,
+    ocsp_responses: &[&BasicOCSPResponse<'_>],
+    policy: &Policy<'_, B>,
+    store: &Store<'chain, B>,
+    records: &[TlsaRecord<'chain>],
+    fetcher: Option<&dyn IssuerFetcher<'chain, B>>,
+    signature_cache: Option<&SignatureCache>,
+) -> Result<VerifiedChain<'chain, B>, VerificationFailure<'chain, B>> {
+    let matches = |usage: TlsaUsage, cert: &VerificationCertificate<'chain, B>| {
+        records
+            .iter()
+            .any(|r| r.usage == usage && r.matches(&policy.ops, cert.certificate()))
+    };
+
+    if matches(TlsaUsage::DaneEe, leaf) {
+        return Ok(VerifiedChain {
+            chain: vec![leaf.clone()],
+            anchor: None,
+            policies: None,
+        });
+    }
+
+    let mut failure = None;
+
+    let anchors = intermediates
+        .iter()
+        .filter(|cert| matches(TlsaUsage::DaneTa, cert))
+        .cloned()
+        .collect::<Vec<_>>();
+    // NOTE: A key published in DNS carries no name, so it's offered under
+    // each issuer name that the presented certificates use.
+    let mut issuer_names = vec![];
+    for cert in std::iter::once(leaf).chain(&intermediates) {
+        let name = &cert.certificate().tbs_cert.issuer;
+        if !issuer_names.contains(name) {
+            issuer_names.push(name.clone());
+        }
+    }
+    let key_anchors = records
+        .iter()
+        .filter_map(|r| r.trust_anchor_key())
+        .flat_map(|spki| {
+            issuer_names.iter().map(move |name| {
+                (
+                    KeyAnchor::new(name.clone(), spki.clone()),
+                    AnchorConstraints::default(),
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    if !anchors.is_empty() || !key_anchors.is_empty() {
+        match verify(
+            leaf,
+            intermediates.clone(),
+            ocsp_responses,
+            policy,
+            &Store::partial(anchors).with_key_anchors(key_anchors),
+            fetcher,
+            signature_cache,
+        ) {
+            Ok(chain) => return Ok(chain),
+            Err(e) => failure = Some(e),
+        }
+    }
+
+    let pkix_records = records
+        .iter()
+        .filter(|r| matches!(r.usage, TlsaUsage::PkixTa | TlsaUsage::PkixEe))
+        .cloned()
+        .collect::<Vec<_>>();
+    if !pkix_records.is_empty() {
+        let builder = ChainBuilder {
+            tlsa_records: &pkix_records,
+            ..ChainBuilder::new(
+                CertificatePool::new(intermediates),
+                ocsp_responses,
+                policy,
+                store,
+                fetcher,
+                signature_cache,
+            )
+        };
+        let mut budget = Budget::new(policy);
+        let mut chains = ChainSink::new(NonZeroUsize::new(1));
+        match builder.build_chain(leaf, &mut chains, &mut budget) {
+            // NOTE: `build_chain` only succeeds with at least one chain.
+            Ok(()) => return Ok(chains.chains.swap_remove(0)),
+            Err(e) => failure = Some(e),
+        }
+    }
+
+    Err(failure.unwrap_or_else(|| {
+        let error = ValidationError::Rejected(
+            FailureReason::DaneMismatch,
+            "no TLSA record matches the presented chain".to_string(),
+        );
+        VerificationFailure {
+            diagnostic: Diagnostic::new(leaf, 0, &error, vec![]),
+            error,
+        }
+    }))
+}
+
+/// Collects the valid chains found during path building, up to `limit`
+/// of them if there is one.
+struct ChainSink<'chain, B: CryptoOps> {
+    chains: Vec<VerifiedChain<'chain, B>>,
+    limit: Option<NonZeroUsize>,
+}
+
+impl<'chain, B: CryptoOps> ChainSink<'chain, B> {
+    fn new(limit: Option<NonZeroUsize>) -> Self {
+        Self {
+            chains: vec![],
+            limit,
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.limit
+            .map_or(false, |limit| self.chains.len() >= limit.get())
+    }
+}
+
+/// A link in the candidate path being built, from the current working
+/// certificate back down to the leaf.
+struct PathLink<'a, 'chain, B: CryptoOps> {
+    cert: &'a VerificationCertificate<'chain, B>,
+    child: Option<&'a PathLink<'a, 'chain, B>>,
+}
+
+impl<'a, 'chain, B: CryptoOps> PathLink<'a, 'chain, B> {
+    /// Returns the path ending at this link, in leaf-first order.
+    fn to_chain(&self) -> Chain<'chain, B> {
+        let mut chain = vec![self.cert.clone()];
+        let mut link = self.child;
+        while let Some(l) = link {
+            chain.push(l.cert.clone());
+            link = l.child;
+        }
+        chain.reverse();
+        chain
+    }
+
+    /// Returns whether `cert` is already in the path ending at this link.
+    fn contains(&self, cert: &VerificationCertificate<'chain, B>) -> bool {
+        self.cert == cert || self.child.map_or(false, |child| child.contains(cert))
+    }
+}
+
+// The certificates fetched from a URI, or the reason they couldn't be.
+type FetchResult<'chain, B> = Result<Vec<VerificationCertificate<'chain, B>>, String>;
+
+struct ChainBuilder<'a, 'chain, B: CryptoOps> {
+    intermediates: CertificatePool<'chain, B>,
+    ocsp_responses: &'a [&'a BasicOCSPResponse<'a>],
     policy: &'a Policy<'a, B>,
     store: &'a Store<'chain, B>,
+    fetcher: Option<&'a dyn IssuerFetcher<'chain, B>>,
+    signature_cache: Option<&'a SignatureCache>,
+    // The PKIX-TA and PKIX-EE TLSA records that a chain must match one of,
+    // if any.
+    tlsa_records: &'a [TlsaRecord<'a>],
+    // The results of each fetch so far, by URI, so that each URI is only
+    // fetched once. A failed fetch is recorded with the reason it failed.
+    fetched: RefCell<HashMap<String, FetchResult<'chain, B>>>,
 }
 
 // When applying a name constraint, we need to distinguish between a few different scenarios:
@@ -254,119 +916,264 @@ impl ApplyNameConstraintStatus {
 
 impl<'a, 'chain, B: CryptoOps> ChainBuilder<'a, 'chain, B> {
     fn new(
-        intermediates: Vec<VerificationCertificate<'chain, B>>,
+        intermediates: CertificatePool<'chain, B>,
+        ocsp_responses: &'a [&'a BasicOCSPResponse<'a>],
         policy: &'a Policy<'a, B>,
         store: &'a Store<'chain, B>,
+        fetcher: Option<&'a dyn IssuerFetcher<'chain, B>>,
+        signature_cache: Option<&'a SignatureCache>,
     ) -> Self {
         Self {
             intermediates,
+            ocsp_responses,
             policy,
             store,
+            fetcher,
+            signature_cache,
+            tlsa_records: &[],
+            fetched: RefCell::new(HashMap::new()),
         }
     }
 
-    fn potential_issuers(
-        &'a self,
-        cert: &'a VerificationCertificate<'chain, B>,
-    ) -> impl Iterator<Item = &'a VerificationCertificate<'chain, B>> + '_ {
-        // TODO: Optimizations:
-        // * Search by AKI and other identifiers?
-        self.store
-            .get_by_subject(&cert.certificate().tbs_cert.issuer)
-            .iter()
-            .chain(self.intermediates.iter().filter(|&candidate| {
-                candidate.certificate().subject() == cert.certificate().issuer()
-            }))
+    /// Returns the candidate issuers of `cert`, in the order that they
+    /// should be tried: those matching `cert`'s authority key identifier
+    /// first, and the trust store (including the trust anchors in `loaded`,
+    /// which the store loaded on demand) before the intermediates.
+    fn potential_issuers<'p>(
+        &'p self,
+        cert: &VerificationCertificate<'chain, B>,
+        aki: Option<&AuthorityKeyIdentifier<'chain>>,
+        loaded: &'p CertificatePool<'chain, B>,
+    ) -> Vec<&'p VerificationCertificate<'chain, B>> {
+        let (store_preferred, store_rest) =
+            self.store.pool().potential_issuers(cert.certificate(), aki);
+        let (loaded_preferred, loaded_rest) = loaded.potential_issuers(cert.certificate(), aki);
+        let (intermediates_preferred, intermediates_rest) = self
+            .intermediates
+            .potential_issuers(cert.certificate(), aki);
+
+        store_preferred
+            .into_iter()
+            .chain(loaded_preferred)
+            .chain(intermediates_preferred)
+            .chain(store_rest)
+            .chain(loaded_rest)
+            .chain(intermediates_rest)
+            .collect()
+    }
+
+    /// Fetches the certificates published at the AIA `caIssuers` URIs of
+    /// the certificate at `current_depth`, for use as additional candidate
+    /// issuers.
+    ///
+    /// Nothing is fetched without a fetcher, or if an issuer couldn't be
+    /// added to the path without exceeding the policy's maximum depth. A
+    /// failed fetch contributes no certificates, and is recorded in
+    /// `last_err` if there isn't already a more specific error. An aborted
+    /// fetch is a fatal error.
+    fn fetch_issuers(
+        &self,
+        current_depth: u8,
+        cert_extensions: &Extensions<'chain>,
+        last_err: &mut Option<ValidationError>,
+        budget: &mut Budget,
+    ) -> Result<Vec<VerificationCertificate<'chain, B>>, ValidationError> {
+        let mut fetched = vec![];
+        let (Some(fetcher), Some(aia)) = (
+            self.fetcher,
+            cert_extensions.get_extension(&AUTHORITY_INFORMATION_ACCESS_OID),
+        ) else {
+            return Ok(fetched);
+        };
+        if current_depth >= self.policy.max_chain_depth {
+            return Ok(fetched);
+        }
+
+        let descriptions = aia.value::<SequenceOfAccessDescriptions<'chain>>()?;
+        for description in descriptions.unwrap_read().clone() {
+            let GeneralName::UniformResourceIdentifier(uri) = description.access_location else {
+                continue;
+            };
+            if description.access_method != CA_ISSUERS_OID {
+                continue;
+            }
+
+            // NOTE: The cache isn't borrowed while the fetcher runs, since
+            // it may be arbitrary user code.
+            let cached = self.fetched.borrow().get(uri.0).cloned();
+            let result = match cached {
+                Some(result) => result,
+                None => {
+                    budget.issuer_fetch()?;
+                    let result = match fetcher.fetch(uri.0) {
+                        Ok(certs) => Ok(certs),
+                        Err(IssuerFetchError::Failed(reason)) => Err(reason),
+                        Err(IssuerFetchError::Aborted) => {
+                            return Err(ValidationError::FatalError(
+                                "Issuer certificate fetch was aborted",
+                            ))
+                        }
+                    };
+                    self.fetched
+                        .borrow_mut()
+                        .entry(uri.0.to_string())
+                        .or_insert(result)
+                        .clone()
+                }
+            };
+            match result {
+                Ok(certs) => fetched.extend(certs),
+                Err(reason) if last_err.is_none() => {
+                    *last_err = Some(ValidationError::Other(format!(
+                        "unable to fetch issuer certificates from {}: {reason}",
+                        uri.0
+                    )));
+                }
+                Err(_) => {}
+            }
+        }
+
+        Ok(fetched)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn build_chain_inner(
         &self,
         working_cert: &VerificationCertificate<'chain, B>,
         current_depth: u8,
         working_cert_extensions: &Extensions<'chain>,
         name_chain: NameChain<'_, 'chain>,
+        path: Option<&PathLink<'_, 'chain, B>>,
+        chains: &mut ChainSink<'chain, B>,
+        attempts: &mut Vec<Diagnostic<'chain, B>>,
         budget: &mut Budget,
-    ) -> Result<Chain<'chain, B>, ValidationError> {
+    ) -> Result<(), ValidationError> {
         if let Some(nc) = working_cert_extensions.get_extension(&NAME_CONSTRAINTS_OID) {
             name_chain.evaluate_constraints(&nc.value()?, budget)?;
         }
 
+        let path = PathLink {
+            cert: working_cert,
+            child: path,
+        };
+
         // Look in the store's root set to see if the working cert is listed.
-        // If it is, we've reached the end.
-        if self.store.contains(working_cert) {
-            return Ok(vec![working_cert.clone()]);
+        // If it is, and it can serve as a trust anchor, we've reached the end.
+        // If the anchor's own constraints reject the path, we keep looking
+        // for other issuers of the working cert instead, such as a root that
+        // cross-signed it.
+        let mut anchor_err = None;
+        if let Some(constraints) = self.store.constraints(working_cert) {
+            if self.policy.partial_chain
+                || self.store.is_partial()
+                || current_depth == 0
+                || cert_is_self_issued(working_cert.certificate())
+            {
+                match self.permits_anchor(constraints, current_depth, &name_chain, budget) {
+                    Ok(()) => {
+                        chains
+                            .chains
+                            .push(self.accept_chain(path.to_chain(), None)?);
+                        return Ok(());
+                    }
+                    Err(e @ ValidationError::FatalError(..)) => return Err(e),
+                    Err(e) => anchor_err = Some(e),
+                }
+            }
         }
 
         // Check that our current depth does not exceed our policy-configured
         // max depth. We do this after the root set check, since the depth
         // only measures the intermediate chain's length, not the root or leaf.
         if current_depth > self.policy.max_chain_depth {
-            return Err(ValidationError::Other(
-                "chain construction exceeds max depth".into(),
-            ));
+            return Err(anchor_err.unwrap_or_else(|| {
+                ValidationError::Rejected(
+                    FailureReason::DepthExceeded,
+                    "chain construction exceeds max depth".into(),
+                )
+            }));
         }
 
         // Otherwise, we collect a list of potential issuers for this cert,
-        // and continue with the first that verifies.
+        // and continue with each that verifies until we have enough chains.
+        let aki = match working_cert_extensions.get_extension(&AUTHORITY_KEY_IDENTIFIER_OID) {
+            Some(ext) => Some(ext.value::<AuthorityKeyIdentifier<'chain>>()?),
+            None => None,
+        };
         let mut last_err: Option<ValidationError> = None;
-        for issuing_cert_candidate in self.potential_issuers(working_cert) {
-            // A candidate issuer is said to verify if it both
-            // signs for the working certificate and conforms to the
-            // policy.
-            let issuer_extensions = issuing_cert_candidate.certificate().extensions()?;
-            match self.policy.valid_issuer(
-                issuing_cert_candidate,
-                working_cert.certificate(),
+        let mut found = self.try_key_anchors(
+            working_cert,
+            current_depth,
+            working_cert_extensions,
+            &name_chain,
+            &path,
+            chains,
+            &mut last_err,
+            budget,
+        )?;
+        if chains.is_full() {
+            return Ok(());
+        }
+
+        let loaded = match self
+            .store
+            .load_by_subject(&working_cert.certificate().tbs_cert.issuer)
+        {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                last_err = Some(ValidationError::Other(format!(
+                    "unable to load trust anchors: {e}"
+                )));
+                vec![]
+            }
+        };
+        let loaded = CertificatePool::new(loaded);
+        found |= self.try_issuers(
+            working_cert,
+            current_depth,
+            working_cert_extensions,
+            &name_chain,
+            &path,
+            self.potential_issuers(working_cert, aki.as_ref(), &loaded),
+            chains,
+            attempts,
+            &mut last_err,
+            budget,
+        )?;
+
+        // If none of the known candidates worked out, fall back to any
+        // issuers that the working cert says can be fetched.
+        if !found {
+            let fetched = CertificatePool::new(self.fetch_issuers(
                 current_depth,
-                &issuer_extensions,
-            ) {
-                Ok(_) => {
-                    match self.build_chain_inner(
-                        issuing_cert_candidate,
-                        // NOTE(ww): According to RFC 5280, we should only
-                        // increase the chain depth when the certificate is **not**
-                        // self-issued. In practice however, implementations widely
-                        // ignore this requirement, and unconditionally increment
-                        // the depth with every chain member. We choose to do the same;
-                        // see `pathlen::self-issued-certs-pathlen` from x509-limbo
-                        // for the testcase we intentionally fail.
-                        //
-                        // Implementation note for someone looking to change this in the future:
-                        // care should be taken to avoid infinite recursion with self-signed
-                        // certificates in the intermediate set; changing this behavior will
-                        // also require a "is not self-signed" check on intermediate candidates.
-                        //
-                        // See https://gist.github.com/woodruffw/776153088e0df3fc2f0675c5e835f7b8
-                        // for an example of this change.
-                        current_depth.checked_add(1).ok_or_else(|| {
-                            ValidationError::Other(
-                                "current depth calculation overflowed".to_string(),
-                            )
-                        })?,
-                        &issuer_extensions,
-                        NameChain::new(
-                            Some(&name_chain),
-                            &issuer_extensions,
-                            // Per RFC 5280 4.2.1.10: Name constraints are not applied
-                            // to subjects in self-issued certificates, *unless* the
-                            // certificate is the "final" (i.e., leaf) certificate in the path.
-                            // We accomplish this by only collecting the SANs when the issuing
-                            // candidate (which is a non-leaf by definition) isn't self-issued.
-                            cert_is_self_issued(issuing_cert_candidate.certificate()),
-                        )?,
-                        budget,
-                    ) {
-                        Ok(mut chain) => {
-                            chain.push(working_cert.clone());
-                            return Ok(chain);
-                        }
-                        // Immediately return on fatal error.
-                        Err(e @ ValidationError::FatalError(..)) => return Err(e),
-                        Err(e) => last_err = Some(e),
-                    };
-                }
-                Err(e) => last_err = Some(e),
-            };
+                working_cert_extensions,
+                &mut last_err,
+                budget,
+            )?);
+            let (preferred, rest) =
+                fetched.potential_issuers(working_cert.certificate(), aki.as_ref());
+            found = self.try_issuers(
+                working_cert,
+                current_depth,
+                working_cert_extensions,
+                &name_chain,
+                &path,
+                preferred.into_iter().chain(rest).collect(),
+                chains,
+                attempts,
+                &mut last_err,
+                budget,
+            )?;
+        }
+
+        if found {
+            return Ok(());
+        }
+
+        // A trust anchor whose constraints rejected the path, and that had
+        // no other issuers to try, is reported as such.
+        if let (Some(e), None) = (anchor_err, &last_err) {
+            return Err(e);
         }
 
         // We only reach this if we fail to hit our base case above, or if
@@ -385,11 +1192,252 @@ impl<'a, 'chain, B: CryptoOps> ChainBuilder<'a, 'chain, B> {
         )))
     }
 
+    /// Attempts to end `path` at each of the trust anchors without
+    /// certificates that may have issued `working_cert`, in order, until
+    /// enough chains have been found. Returns whether any anchor led to a
+    /// valid chain.
+    ///
+    /// The most recent failure is recorded in `last_err`. Fatal errors are
+    /// returned immediately.
+    #[allow(clippy::too_many_arguments)]
+    fn try_key_anchors(
+        &self,
+        working_cert: &VerificationCertificate<'chain, B>,
+        current_depth: u8,
+        working_cert_extensions: &Extensions<'chain>,
+        name_chain: &NameChain<'_, 'chain>,
+        path: &PathLink<'_, 'chain, B>,
+        chains: &mut ChainSink<'chain, B>,
+        last_err: &mut Option<ValidationError>,
+        budget: &mut Budget,
+    ) -> Result<bool, ValidationError> {
+        let mut found = false;
+        for (anchor, constraints) in self
+            .store
+            .key_anchors_by_name(&working_cert.certificate().tbs_cert.issuer)
+        {
+            match self.try_key_anchor(
+                working_cert,
+                current_depth,
+                working_cert_extensions,
+                name_chain,
+                path,
+                anchor,
+                constraints,
+                budget,
+            ) {
+                Ok(chain) => {
+                    chains.chains.push(chain);
+                    found = true;
+                    if chains.is_full() {
+                        break;
+                    }
+                }
+                Err(e @ ValidationError::FatalError(..)) => return Err(e),
+                Err(e) => *last_err = Some(e),
+            }
+        }
+
+        Ok(found)
+    }
+
+    /// Attempts to end `path` at `anchor`, a trust anchor without a
+    /// certificate that may have issued `working_cert`.
+    #[allow(clippy::too_many_arguments)]
+    fn try_key_anchor(
+        &self,
+        working_cert: &VerificationCertificate<'chain, B>,
+        current_depth: u8,
+        working_cert_extensions: &Extensions<'chain>,
+        name_chain: &NameChain<'_, 'chain>,
+        path: &PathLink<'_, 'chain, B>,
+        anchor: &KeyAnchor<'chain, B>,
+        constraints: &AnchorConstraints<'chain>,
+        budget: &mut Budget,
+    ) -> Result<VerifiedChain<'chain, B>, ValidationError> {
+        budget.candidate()?;
+
+        self.policy
+            .valid_key_anchor(anchor, working_cert.certificate(), budget)?;
+        revocation::permits(
+            self.policy,
+            self.ocsp_responses,
+            working_cert,
+            working_cert_extensions,
+            &revocation::Issuer::KeyAnchor(anchor),
+            budget,
+        )?;
+
+        // The anchor sits just above the working cert, although it isn't
+        // itself a member of the chain.
+        self.permits_anchor(
+            constraints,
+            current_depth.saturating_add(1),
+            name_chain,
+            budget,
+        )?;
+        self.accept_chain(path.to_chain(), Some(anchor))
+    }
+
+    /// Attempts to continue `path` through each of `candidates`, the
+    /// potential issuers of `working_cert`, in order, until enough chains
+    /// have been found. Returns whether any candidate led to a valid chain.
+    ///
+    /// Each failed candidate is recorded in `attempts`, and the most recent
+    /// failure in `last_err`. Fatal errors are returned immediately.
+    #[allow(clippy::too_many_arguments)]
+    fn try_issuers(
+        &self,
+        working_cert: &VerificationCertificate<'chain, B>,
+        current_depth: u8,
+        working_cert_extensions: &Extensions<'chain>,
+        name_chain: &NameChain<'_, 'chain>,
+        path: &PathLink<'_, 'chain, B>,
+        candidates: Vec<&VerificationCertificate<'chain, B>>,
+        chains: &mut ChainSink<'chain, B>,
+        attempts: &mut Vec<Diagnostic<'chain, B>>,
+        last_err: &mut Option<ValidationError>,
+        budget: &mut Budget,
+    ) -> Result<bool, ValidationError> {
+        let mut found = false;
+        // A certificate never appears twice in a path. In particular, a
+        // self-signed trust anchor that didn't terminate the path isn't its
+        // own issuer.
+        for issuing_cert_candidate in candidates.into_iter().filter(|c| !path.contains(c)) {
+            let mut candidate_attempts = vec![];
+            match self.try_issuer(
+                working_cert,
+                current_depth,
+                working_cert_extensions,
+                name_chain,
+                path,
+                issuing_cert_candidate,
+                chains,
+                &mut candidate_attempts,
+                budget,
+            ) {
+                Ok(()) if chains.is_full() => return Ok(true),
+                Ok(()) => found = true,
+                Err(e) => {
+                    attempts.push(Diagnostic::new(
+                        issuing_cert_candidate,
+                        current_depth.saturating_add(1),
+                        &e,
+                        candidate_attempts,
+                    ));
+                    // Immediately return on fatal error.
+                    if let ValidationError::FatalError(..) = e {
+                        return Err(e);
+                    }
+                    *last_err = Some(e);
+                }
+            };
+        }
+
+        Ok(found)
+    }
+
+    /// Attempts to continue `path` through `issuing_cert_candidate`, a
+    /// potential issuer of `working_cert`.
+    #[allow(clippy::too_many_arguments)]
+    fn try_issuer(
+        &self,
+        working_cert: &VerificationCertificate<'chain, B>,
+        current_depth: u8,
+        working_cert_extensions: &Extensions<'chain>,
+        name_chain: &NameChain<'_, 'chain>,
+        path: &PathLink<'_, 'chain, B>,
+        issuing_cert_candidate: &VerificationCertificate<'chain, B>,
+        chains: &mut ChainSink<'chain, B>,
+        attempts: &mut Vec<Diagnostic<'chain, B>>,
+        budget: &mut Budget,
+    ) -> Result<(), ValidationError> {
+        budget.candidate()?;
+
+        // A candidate issuer is said to verify if it both
+        // signs for the working certificate and conforms to the
+        // policy.
+        let issuer_extensions = issuing_cert_candidate.certificate().extensions()?;
+        self.policy.valid_issuer(
+            issuing_cert_candidate,
+            working_cert.certificate(),
+            current_depth,
+            &issuer_extensions,
+            self.signature_cache,
+            budget,
+        )?;
+        revocation::permits(
+            self.policy,
+            self.ocsp_responses,
+            working_cert,
+            working_cert_extensions,
+            &revocation::Issuer::Certificate(issuing_cert_candidate, &issuer_extensions),
+            budget,
+        )?;
+
+        self.build_chain_inner(
+            issuing_cert_candidate,
+            // NOTE(ww): According to RFC 5280, we should only
+            // increase the chain depth when the certificate is **not**
+            // self-issued. In practice however, implementations widely
+            // ignore this requirement, and unconditionally increment
+            // the depth with every chain member. We choose to do the same;
+            // see `pathlen::self-issued-certs-pathlen` from x509-limbo
+            // for the testcase we intentionally fail.
+            //
+            // Implementation note for someone looking to change this in the future:
+            // care should be taken to avoid infinite recursion with self-signed
+            // certificates in the intermediate set; changing this behavior will
+            // also require a "is not self-signed" check on intermediate candidates.
+            //
+            // See https://gist.github.com/woodruffw/776153088e0df3fc2f0675c5e835f7b8
+            // for an example of this change.
+            current_depth.checked_add(1).ok_or_else(|| {
+                ValidationError::Rejected(
+                    FailureReason::DepthExceeded,
+                    "current depth calculation overflowed".to_string(),
+                )
+            })?,
+            &issuer_extensions,
+            NameChain::new(
+                Some(name_chain),
+                issuing_cert_candidate.certificate(),
+                &issuer_extensions,
+                // Per RFC 5280 4.2.1.10: Name constraints are not applied
+                // to subjects in self-issued certificates, *unless* the
+                // certificate is the "final" (i.e., leaf) certificate in the path.
+                // We accomplish this by only collecting the SANs when the issuing
+                // candidate (which is a non-leaf by definition) isn't self-issued.
+                cert_is_self_issued(issuing_cert_candidate.certificate()),
+            )?,
+            Some(path),
+            chains,
+            attempts,
+            budget,
+        )
+    }
+
     fn build_chain(
         &self,
         leaf: &VerificationCertificate<'chain, B>,
+        chains: &mut ChainSink<'chain, B>,
+        budget: &mut Budget,
+    ) -> Result<(), VerificationFailure<'chain, B>> {
+        let mut attempts = vec![];
+        self.build_chain_from_leaf(leaf, chains, &mut attempts, budget)
+            .map_err(|error| VerificationFailure {
+                diagnostic: Diagnostic::new(leaf, 0, &error, attempts),
+                error,
+            })
+    }
+
+    fn build_chain_from_leaf(
+        &self,
+        leaf: &VerificationCertificate<'chain, B>,
+        chains: &mut ChainSink<'chain, B>,
+        attempts: &mut Vec<Diagnostic<'chain, B>>,
         budget: &mut Budget,
-    ) -> Result<Chain<'chain, B>, ValidationError> {
+    ) -> Result<(), ValidationError> {
         // Before anything else, check whether the given leaf cert
         // is well-formed according to our policy (and its underlying
         // certificate profile).
@@ -397,18 +1445,124 @@ impl<'a, 'chain, B: CryptoOps> ChainBuilder<'a, 'chain, B> {
         // The leaf must be an EE; a CA cert in the leaf position will be rejected.
         let leaf_extensions = leaf.certificate().extensions()?;
 
-        self.policy
-            .permits_ee(leaf.certificate(), &leaf_extensions)?;
+        self.policy.permits_ee(leaf, &leaf_extensions)?;
 
-        let mut chain = self.build_chain_inner(
+        self.build_chain_inner(
             leaf,
             0,
             &leaf_extensions,
-            NameChain::new(None, &leaf_extensions, false)?,
+            NameChain::new(None, leaf.certificate(), &leaf_extensions, false)?,
+            None,
+            chains,
+            attempts,
             budget,
-        )?;
-        // We build the chain in reverse order, fix it now.
-        chain.reverse();
-        Ok(chain)
+        )
+    }
+
+    /// Checks the RFC 5937 constraints of the trust anchor at `current_depth`
+    /// against the path below it.
+    fn permits_anchor(
+        &self,
+        constraints: &AnchorConstraints<'chain>,
+        current_depth: u8,
+        name_chain: &NameChain<'_, 'chain>,
+        budget: &mut Budget,
+    ) -> Result<(), ValidationError> {
+        if let Some(nc) = &constraints.name_constraints {
+            name_chain.evaluate_constraints(nc, budget)?;
+        }
+
+        // The path's intermediates are the certificates strictly between
+        // the anchor and the leaf.
+        if let Some(max_path_length) = constraints.max_path_length {
+            if current_depth.saturating_sub(1) > max_path_length {
+                return Err(ValidationError::Rejected(
+                    FailureReason::DepthExceeded,
+                    "trust anchor path length constraint violated".to_string(),
+                ));
+            }
+        }
+
+        if let Some(trust_domain) = constraints.spiffe_trust_domain {
+            let leaf = name_chain.leaf();
+            let mut ids = leaf.sans.clone().filter_map(|gn| match gn {
+                GeneralName::UniformResourceIdentifier(uri) => SpiffeId::new(uri.0),
+                _ => None,
+            });
+            if ids.clone().next().is_none() || !ids.all(|id| id.trust_domain() == trust_domain) {
+                return Err(ValidationError::Rejected(
+                    FailureReason::NameConstraintViolation,
+                    "trust anchor is not a trust bundle for the leaf's SPIFFE trust domain"
+                        .to_string(),
+                ));
+            }
+        }
+
+        if let Some(ekus) = &constraints.extended_key_usages {
+            if !ekus
+                .iter()
+                .any(|eku| eku == &self.policy.extended_key_usage || eku == &EKU_ANY_KEY_USAGE_OID)
+            {
+                return Err(ValidationError::Rejected(
+                    FailureReason::ExtendedKeyUsageMismatch,
+                    "trust anchor is not trusted for the required EKU".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs the checks that apply to a complete candidate chain, which is
+    /// in leaf-first order and ends with its trust anchor, or with a
+    /// certificate issued by `anchor` if it's a trust anchor without a
+    /// certificate.
+    fn accept_chain(
+        &self,
+        chain: Chain<'chain, B>,
+        anchor: Option<&KeyAnchor<'chain, B>>,
+    ) -> Result<VerifiedChain<'chain, B>, ValidationError> {
+        // A leaf that is itself a trust anchor has no issuer to check its
+        // SCTs against, and is trusted regardless.
+        let issuer_spki = match (&*chain, anchor) {
+            ([_, issuer, ..], _) => Some(&issuer.certificate().tbs_cert.spki),
+            (_, Some(anchor)) => Some(anchor.spki()),
+            _ => None,
+        };
+        if let (Some(ct), Some(issuer_spki)) = (&self.policy.certificate_transparency, issuer_spki)
+        {
+            ct::permits(self.policy, ct, &chain[0], issuer_spki)?;
+        }
+
+        if !self.tlsa_records.is_empty()
+            && !self
+                .tlsa_records
+                .iter()
+                .any(|record| record.permits_chain(&self.policy.ops, &chain))
+        {
+            return Err(ValidationError::Rejected(
+                FailureReason::DaneMismatch,
+                "chain doesn't match any PKIX-TA or PKIX-EE TLSA record".to_string(),
+            ));
+        }
+
+        self.policy.permits_pins(&chain, anchor)?;
+
+        let policies = match &self.policy.certificate_policies {
+            Some(constraints) => {
+                let path = match anchor {
+                    Some(_) => &chain[..],
+                    None => &chain[..chain.len() - 1],
+                };
+                Some(policy_tree::process(constraints, path)?)
+            }
+            None => None,
+        };
+
+        Ok(VerifiedChain {
+            chain,
+            anchor: anchor.cloned(),
+            policies,
+        })
     }
 }
@@ -3,6 +3,7 @@
 // for complete details.
 
 use cryptography_x509::certificate::Certificate;
+use cryptography_x509::common::{AlgorithmIdentifier, SubjectPublicKeyInfo, WithTlv};
 
 pub struct VerificationCertificate<'a, B: CryptoOps> {
     cert: Certificate<'a>,
@@ -60,9 +61,36 @@ pub trait CryptoOps {
     /// if the key is malformed.
     fn public_key(&self, cert: &Certificate<'_>) -> Result<Self::Key, Self::Err>;
 
+    /// Extracts the public key from the given `SubjectPublicKeyInfo`, such
+    /// as that of a trust anchor without a certificate, in a `Key` format
+    /// known by the cryptographic backend.
+    fn spki_public_key(
+        &self,
+        spki: &WithTlv<'_, SubjectPublicKeyInfo<'_>>,
+    ) -> Result<Self::Key, Self::Err>;
+
     /// Verifies the signature on `Certificate` using the given
     /// `Key`.
     fn verify_signed_by(&self, cert: &Certificate<'_>, key: &Self::Key) -> Result<(), Self::Err>;
+
+    /// Verifies `signature` over `data` using the given `Key` and
+    /// signature algorithm. This is used for signed objects other than
+    /// certificates, such as CRLs.
+    fn verify_signature(
+        &self,
+        key: &Self::Key,
+        signature_algorithm: &AlgorithmIdentifier<'_>,
+        signature: &[u8],
+        data: &[u8],
+    ) -> Result<(), Self::Err>;
+
+    /// Computes the digest of `data` using the given hash algorithm. This
+    /// is used to match OCSP `CertID`s and responder key hashes.
+    fn digest(
+        &self,
+        hash_algorithm: &AlgorithmIdentifier<'_>,
+        data: &[u8],
+    ) -> Result<Vec<u8>, Self::Err>;
 }
 
 #[cfg(test)]
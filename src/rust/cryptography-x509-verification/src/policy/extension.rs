@@ -2,19 +2,30 @@
 // 2.0, and the BSD License. See the LICENSE file in the root of this repository
 // for complete details.
 
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use asn1::ObjectIdentifier;
 use cryptography_x509::oid::{
     AUTHORITY_INFORMATION_ACCESS_OID, AUTHORITY_KEY_IDENTIFIER_OID, BASIC_CONSTRAINTS_OID,
-    EXTENDED_KEY_USAGE_OID, KEY_USAGE_OID, NAME_CONSTRAINTS_OID, SUBJECT_ALTERNATIVE_NAME_OID,
-    SUBJECT_KEY_IDENTIFIER_OID,
+    CERTIFICATE_POLICIES_OID, EXTENDED_KEY_USAGE_OID, INHIBIT_ANY_POLICY_OID, KEY_USAGE_OID,
+    NAME_CONSTRAINTS_OID, POLICY_CONSTRAINTS_OID, POLICY_MAPPINGS_OID,
+    SUBJECT_ALTERNATIVE_NAME_OID, SUBJECT_KEY_IDENTIFIER_OID,
 };
 use cryptography_x509::{
     certificate::Certificate,
     extensions::{Extension, Extensions},
 };
 
-use crate::{ops::CryptoOps, policy::Policy, ValidationError};
+use crate::{
+    ops::{CryptoOps, VerificationCertificate},
+    policy::Policy,
+    ValidationError,
+};
 
-pub(crate) struct ExtensionPolicy<B: CryptoOps> {
+/// Describes the extensions that a certificate of a given kind (CA or EE)
+/// must, may, or must not contain, and how each of them is validated.
+pub struct ExtensionPolicy<B: CryptoOps> {
     pub(crate) authority_information_access: ExtensionValidator<B>,
     pub(crate) authority_key_identifier: ExtensionValidator<B>,
     pub(crate) subject_key_identifier: ExtensionValidator<B>,
@@ -23,13 +34,80 @@ pub(crate) struct ExtensionPolicy<B: CryptoOps> {
     pub(crate) basic_constraints: ExtensionValidator<B>,
     pub(crate) name_constraints: ExtensionValidator<B>,
     pub(crate) extended_key_usage: ExtensionValidator<B>,
+    pub(crate) certificate_policies: ExtensionValidator<B>,
+    pub(crate) policy_mappings: ExtensionValidator<B>,
+    pub(crate) policy_constraints: ExtensionValidator<B>,
+    pub(crate) inhibit_any_policy: ExtensionValidator<B>,
+
+    /// Validators for extensions outside of the set above. Critical
+    /// extensions that appear neither above nor here are rejected.
+    pub(crate) custom: HashMap<ObjectIdentifier, ExtensionValidator<B>>,
 }
 
 impl<B: CryptoOps> ExtensionPolicy<B> {
+    /// Returns the validator for `oid`, creating a permissive one (the
+    /// extension MAY be present, with any criticality) if `oid` is not
+    /// otherwise known to this policy.
+    fn validator_mut(&mut self, oid: &ObjectIdentifier) -> &mut ExtensionValidator<B> {
+        match *oid {
+            AUTHORITY_INFORMATION_ACCESS_OID => &mut self.authority_information_access,
+            AUTHORITY_KEY_IDENTIFIER_OID => &mut self.authority_key_identifier,
+            SUBJECT_KEY_IDENTIFIER_OID => &mut self.subject_key_identifier,
+            KEY_USAGE_OID => &mut self.key_usage,
+            SUBJECT_ALTERNATIVE_NAME_OID => &mut self.subject_alternative_name,
+            BASIC_CONSTRAINTS_OID => &mut self.basic_constraints,
+            NAME_CONSTRAINTS_OID => &mut self.name_constraints,
+            EXTENDED_KEY_USAGE_OID => &mut self.extended_key_usage,
+            CERTIFICATE_POLICIES_OID => &mut self.certificate_policies,
+            POLICY_MAPPINGS_OID => &mut self.policy_mappings,
+            POLICY_CONSTRAINTS_OID => &mut self.policy_constraints,
+            INHIBIT_ANY_POLICY_OID => &mut self.inhibit_any_policy,
+            _ => self
+                .custom
+                .entry(oid.clone())
+                .or_insert_with(|| ExtensionValidator::maybe_present(Criticality::Agnostic, None)),
+        }
+    }
+
+    /// Permits the extension identified by `oid` (with any criticality) if it
+    /// is not otherwise known to this policy. This is how an unknown critical
+    /// extension is deliberately accepted.
+    pub fn permit(&mut self, oid: &ObjectIdentifier) {
+        self.validator_mut(oid);
+    }
+
+    /// Overrides whether the extension identified by `oid` must, may, or
+    /// must not be present. Any built-in validation of the extension's
+    /// contents is kept.
+    pub fn set_presence(&mut self, oid: &ObjectIdentifier, presence: Presence) {
+        self.validator_mut(oid).presence = presence;
+    }
+
+    /// Overrides the criticality required of the extension identified by
+    /// `oid`, if it is present.
+    pub fn set_criticality(&mut self, oid: &ObjectIdentifier, criticality: Criticality) {
+        self.validator_mut(oid).criticality = criticality;
+    }
+
+    /// Registers a validator over the extension identified by `oid`. The
+    /// validator is called after the presence and criticality checks (and
+    /// after any built-in validation of the extension's contents), with the
+    /// extension if it is present and `None` otherwise.
+    ///
+    /// Registering a validator for an extension that is otherwise unknown
+    /// to this policy permits that extension, even if it is critical.
+    pub fn set_validator(
+        &mut self,
+        oid: &ObjectIdentifier,
+        validator: CustomExtensionValidatorCallback<B>,
+    ) {
+        self.validator_mut(oid).custom_validator = Some(validator);
+    }
+
     pub(crate) fn permits(
         &self,
         policy: &Policy<'_, B>,
-        cert: &Certificate<'_>,
+        cert: &VerificationCertificate<'_, B>,
         extensions: &Extensions<'_>,
     ) -> Result<(), ValidationError> {
         let mut authority_information_access_seen = false;
@@ -40,6 +118,10 @@ impl<B: CryptoOps> ExtensionPolicy<B> {
         let mut basic_constraints_seen = false;
         let mut name_constraints_seen = false;
         let mut extended_key_usage_seen = false;
+        let mut certificate_policies_seen = false;
+        let mut policy_mappings_seen = false;
+        let mut policy_constraints_seen = false;
+        let mut inhibit_any_policy_seen = false;
 
         // Iterate over each extension and run its policy.
         for ext in extensions.iter() {
@@ -80,13 +162,33 @@ impl<B: CryptoOps> ExtensionPolicy<B> {
                     extended_key_usage_seen = true;
                     self.extended_key_usage.permits(policy, cert, Some(&ext))?;
                 }
-                _ if ext.critical => {
-                    return Err(ValidationError::Other(format!(
-                        "certificate contains unaccounted-for critical extensions: {}",
-                        ext.extn_id
-                    )));
+                CERTIFICATE_POLICIES_OID => {
+                    certificate_policies_seen = true;
+                    self.certificate_policies
+                        .permits(policy, cert, Some(&ext))?;
+                }
+                POLICY_MAPPINGS_OID => {
+                    policy_mappings_seen = true;
+                    self.policy_mappings.permits(policy, cert, Some(&ext))?;
+                }
+                POLICY_CONSTRAINTS_OID => {
+                    policy_constraints_seen = true;
+                    self.policy_constraints.permits(policy, cert, Some(&ext))?;
                 }
-                _ => {}
+                INHIBIT_ANY_POLICY_OID => {
+                    inhibit_any_policy_seen = true;
+                    self.inhibit_any_policy.permits(policy, cert, Some(&ext))?;
+                }
+                _ => match self.custom.get(&ext.extn_id) {
+                    Some(validator) => validator.permits(policy, cert, Some(&ext))?,
+                    None if ext.critical => {
+                        return Err(ValidationError::Other(format!(
+                            "certificate contains unaccounted-for critical extensions: {}",
+                            ext.extn_id
+                        )));
+                    }
+                    None => {}
+                },
             }
         }
 
@@ -117,13 +219,31 @@ impl<B: CryptoOps> ExtensionPolicy<B> {
         if !extended_key_usage_seen {
             self.extended_key_usage.permits(policy, cert, None)?;
         }
+        if !certificate_policies_seen {
+            self.certificate_policies.permits(policy, cert, None)?;
+        }
+        if !policy_mappings_seen {
+            self.policy_mappings.permits(policy, cert, None)?;
+        }
+        if !policy_constraints_seen {
+            self.policy_constraints.permits(policy, cert, None)?;
+        }
+        if !inhibit_any_policy_seen {
+            self.inhibit_any_policy.permits(policy, cert, None)?;
+        }
+        for (oid, validator) in &self.custom {
+            if extensions.get_extension(oid).is_none() {
+                validator.permits(policy, cert, None)?;
+            }
+        }
 
         Ok(())
     }
 }
 
 /// Represents different criticality states for an extension.
-pub(crate) enum Criticality {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Criticality {
     /// The extension MUST be marked as critical.
     Critical,
     /// The extension MAY be marked as critical.
@@ -144,124 +264,154 @@ impl Criticality {
     }
 }
 
+/// Represents different presence requirements for an extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Presence {
+    /// The extension MUST be present.
+    Required,
+    /// The extension MAY be present.
+    Optional,
+    /// The extension MUST NOT be present.
+    Prohibited,
+}
+
 type PresentExtensionValidatorCallback<B> =
     fn(&Policy<'_, B>, &Certificate<'_>, &Extension<'_>) -> Result<(), ValidationError>;
 
 type MaybeExtensionValidatorCallback<B> =
     fn(&Policy<'_, B>, &Certificate<'_>, Option<&Extension<'_>>) -> Result<(), ValidationError>;
 
-/// Represents different validation states for an extension.
-pub(crate) enum ExtensionValidator<B: CryptoOps> {
-    /// The extension MUST NOT be present.
-    NotPresent,
-    /// The extension MUST be present.
-    Present {
-        /// The extension's criticality.
-        criticality: Criticality,
-        /// An optional validator over the extension's inner contents, with
-        /// the surrounding `Policy` as context.
-        validator: Option<PresentExtensionValidatorCallback<B>>,
-    },
-    /// The extension MAY be present; the interior validator is
-    /// always called if supplied, including if the extension is not present.
-    MaybePresent {
-        criticality: Criticality,
-        validator: Option<MaybeExtensionValidatorCallback<B>>,
-    },
+/// A caller-supplied validator over an extension, called with the extension
+/// if it is present and `None` otherwise.
+pub type CustomExtensionValidatorCallback<B> = Arc<
+    dyn Fn(
+            &Policy<'_, B>,
+            &VerificationCertificate<'_, B>,
+            Option<&Extension<'_>>,
+        ) -> Result<(), ValidationError>
+        + Send
+        + Sync,
+>;
+
+/// A built-in validator over an extension's inner contents.
+enum BuiltinExtensionValidator<B: CryptoOps> {
+    /// Only called if the extension is present.
+    Present(PresentExtensionValidatorCallback<B>),
+    /// Always called, including if the extension is not present.
+    MaybePresent(MaybeExtensionValidatorCallback<B>),
+}
+
+/// Represents the validation state for an extension: whether it must be
+/// present, its criticality, and any validators over its contents.
+pub(crate) struct ExtensionValidator<B: CryptoOps> {
+    presence: Presence,
+    criticality: Criticality,
+    /// An optional validator over the extension's inner contents, with
+    /// the surrounding `Policy` as context.
+    validator: Option<BuiltinExtensionValidator<B>>,
+    custom_validator: Option<CustomExtensionValidatorCallback<B>>,
 }
 
 impl<B: CryptoOps> ExtensionValidator<B> {
     pub(crate) fn not_present() -> Self {
-        Self::NotPresent
+        Self {
+            presence: Presence::Prohibited,
+            criticality: Criticality::Agnostic,
+            validator: None,
+            custom_validator: None,
+        }
     }
 
     pub(crate) fn present(
         criticality: Criticality,
         validator: Option<PresentExtensionValidatorCallback<B>>,
     ) -> Self {
-        Self::Present {
+        Self {
+            presence: Presence::Required,
             criticality,
-            validator,
+            validator: validator.map(BuiltinExtensionValidator::Present),
+            custom_validator: None,
         }
     }
 
+    /// The interior validator is always called if supplied, including if
+    /// the extension is not present.
     pub(crate) fn maybe_present(
         criticality: Criticality,
         validator: Option<MaybeExtensionValidatorCallback<B>>,
     ) -> Self {
-        Self::MaybePresent {
+        Self {
+            presence: Presence::Optional,
             criticality,
-            validator,
+            validator: validator.map(BuiltinExtensionValidator::MaybePresent),
+            custom_validator: None,
         }
     }
 
     pub(crate) fn permits(
         &self,
         policy: &Policy<'_, B>,
-        cert: &Certificate<'_>,
+        cert: &VerificationCertificate<'_, B>,
         extension: Option<&Extension<'_>>,
     ) -> Result<(), ValidationError> {
-        match (self, extension) {
-            // Extension MUST NOT be present and isn't; OK.
-            (ExtensionValidator::NotPresent, None) => Ok(()),
+        match (self.presence, extension) {
             // Extension MUST NOT be present but is; NOT OK.
-            (ExtensionValidator::NotPresent, Some(_)) => Err(ValidationError::Other(
-                "Certificate contains prohibited extension".to_string(),
-            )),
+            (Presence::Prohibited, Some(_)) => {
+                return Err(ValidationError::Other(
+                    "Certificate contains prohibited extension".to_string(),
+                ))
+            }
             // Extension MUST be present but is not; NOT OK.
-            (ExtensionValidator::Present { .. }, None) => Err(ValidationError::Other(
-                "Certificate is missing required extension".to_string(),
-            )),
-            // Extension MUST be present and is; check it.
-            (
-                ExtensionValidator::Present {
-                    criticality,
-                    validator,
-                },
-                Some(extn),
-            ) => {
-                if !criticality.permits(extn.critical) {
-                    return Err(ValidationError::Other(
-                        "Certificate extension has incorrect criticality".to_string(),
-                    ));
-                }
-
-                // If a custom validator is supplied, apply it.
-                validator.map_or(Ok(()), |v| v(policy, cert, extn))
+            (Presence::Required, None) => {
+                return Err(ValidationError::Other(
+                    "Certificate is missing required extension".to_string(),
+                ))
             }
-            // Extension MAY be present.
-            (
-                ExtensionValidator::MaybePresent {
-                    criticality,
-                    validator,
-                },
-                extn,
-            ) => {
-                // If the extension is present, apply our criticality check.
-                if extn.map_or(false, |extn| !criticality.permits(extn.critical)) {
-                    return Err(ValidationError::Other(
-                        "Certificate extension has incorrect criticality".to_string(),
-                    ));
-                }
+            // If the extension is present, apply our criticality check.
+            (_, Some(extn)) if !self.criticality.permits(extn.critical) => {
+                return Err(ValidationError::Other(
+                    "Certificate extension has incorrect criticality".to_string(),
+                ))
+            }
+            _ => {}
+        }
 
-                // If a custom validator is supplied, apply it.
-                validator.map_or(Ok(()), |v| v(policy, cert, extn))
+        // If a built-in validator is supplied, apply it.
+        match (&self.validator, extension) {
+            (Some(BuiltinExtensionValidator::Present(v)), Some(extn)) => {
+                v(policy, cert.certificate(), extn)?
             }
+            (Some(BuiltinExtensionValidator::MaybePresent(v)), extn) => {
+                v(policy, cert.certificate(), extn)?
+            }
+            _ => {}
         }
+
+        // If a custom validator is supplied, apply it.
+        self.custom_validator
+            .as_ref()
+            .map_or(Ok(()), |v| v(policy, cert, extension))
     }
 }
 
 pub(crate) mod ee {
+    use asn1::ObjectIdentifier;
     use cryptography_x509::{
         certificate::Certificate,
         extensions::{
             BasicConstraints, ExtendedKeyUsage, Extension, KeyUsage, SubjectAlternativeName,
         },
+        name::GeneralName,
+        oid::{
+            EKU_ANY_KEY_USAGE_OID, EKU_CODE_SIGNING_OID, EKU_SERVER_AUTH_OID, EKU_TIME_STAMPING_OID,
+        },
     };
 
     use crate::{
         ops::CryptoOps,
         policy::{Policy, ValidationError},
+        types::SpiffeId,
+        FailureReason,
     };
 
     pub(crate) fn basic_constraints<B: CryptoOps>(
@@ -336,7 +486,10 @@ pub(crate) mod ee {
             if ekus.any(|eku| eku == policy.extended_key_usage) {
                 Ok(())
             } else {
-                Err(ValidationError::Other("required EKU not found".to_string()))
+                Err(ValidationError::Rejected(
+                    FailureReason::ExtendedKeyUsageMismatch,
+                    "required EKU not found".to_string(),
+                ))
             }
         } else {
             Ok(())
@@ -360,6 +513,212 @@ pub(crate) mod ee {
 
         Ok(())
     }
+
+    // Checks that `ekus` contains the policy's EKU, and none of `forbidden`.
+    fn permits_ekus<B: CryptoOps>(
+        policy: &Policy<'_, B>,
+        ekus: &ExtendedKeyUsage<'_>,
+        forbidden: &[ObjectIdentifier],
+    ) -> Result<(), ValidationError> {
+        if let Some(eku) = ekus.clone().find(|eku| forbidden.contains(eku)) {
+            return Err(ValidationError::Other(format!(
+                "EE extendedKeyUsage must not contain {eku}"
+            )));
+        }
+
+        if !ekus.clone().any(|eku| eku == policy.extended_key_usage) {
+            return Err(ValidationError::Rejected(
+                FailureReason::ExtendedKeyUsageMismatch,
+                "required EKU not found".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Checks that an EE keyUsage asserts neither of the CA-only bits.
+    fn permits_ee_key_usage(key_usage: &KeyUsage<'_>) -> Result<(), ValidationError> {
+        if key_usage.key_cert_sign() || key_usage.crl_sign() {
+            return Err(ValidationError::Other(
+                "EE keyUsage must not assert keyCertSign or cRLSign".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn spiffe_subject_alternative_name<B: CryptoOps>(
+        policy: &Policy<'_, B>,
+        _cert: &Certificate<'_>,
+        extn: &Extension<'_>,
+    ) -> Result<(), ValidationError> {
+        let san: SubjectAlternativeName<'_> = extn.value()?;
+
+        // X509-SVID 2: An X.509 SVID MUST contain exactly one URI SAN, and
+        // by extension, exactly one SPIFFE ID.
+        let mut uris = san.clone().filter_map(|gn| match gn {
+            GeneralName::UniformResourceIdentifier(uri) => Some(uri.0),
+            _ => None,
+        });
+        let (Some(uri), None) = (uris.next(), uris.next()) else {
+            return Err(ValidationError::Other(
+                "SVID must have exactly one URI subjectAltName".to_string(),
+            ));
+        };
+
+        // X509-SVID 3: Leaf certificate SPIFFE IDs MUST have a non-root
+        // path component.
+        if !SpiffeId::new(uri).map_or(false, |id| !id.is_trust_domain()) {
+            return Err(ValidationError::Other(format!(
+                "SVID URI subjectAltName is not a workload SPIFFE ID: {uri:?}"
+            )));
+        }
+
+        if let Some(sub) = policy.subject.as_ref() {
+            if !sub.matches(&san) {
+                return Err(ValidationError::Other(
+                    "leaf certificate has no matching subjectAltName".into(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn spiffe_key_usage<B: CryptoOps>(
+        _policy: &Policy<'_, B>,
+        _cert: &Certificate<'_>,
+        extn: &Extension<'_>,
+    ) -> Result<(), ValidationError> {
+        let key_usage: KeyUsage<'_> = extn.value()?;
+
+        // X509-SVID 4.3: Leaf SVIDs MUST set digitalSignature, and MUST NOT
+        // set keyCertSign or cRLSign.
+        if !key_usage.digital_signature() {
+            return Err(ValidationError::Other(
+                "SVID keyUsage must assert digitalSignature".to_string(),
+            ));
+        }
+
+        permits_ee_key_usage(&key_usage)
+    }
+
+    pub(crate) fn code_signing_extended_key_usage<B: CryptoOps>(
+        policy: &Policy<'_, B>,
+        _cert: &Certificate<'_>,
+        extn: &Extension<'_>,
+    ) -> Result<(), ValidationError> {
+        let ekus: ExtendedKeyUsage<'_> = extn.value()?;
+
+        // CS BR 7.1.2.3 (f): id-kp-codeSigning MUST be present, and
+        // anyExtendedKeyUsage and id-kp-serverAuth MUST NOT be present.
+        permits_ekus(policy, &ekus, &[EKU_ANY_KEY_USAGE_OID, EKU_SERVER_AUTH_OID])
+    }
+
+    pub(crate) fn code_signing_key_usage<B: CryptoOps>(
+        _policy: &Policy<'_, B>,
+        _cert: &Certificate<'_>,
+        extn: &Extension<'_>,
+    ) -> Result<(), ValidationError> {
+        let key_usage: KeyUsage<'_> = extn.value()?;
+
+        // CS BR 7.1.2.3 (e): digitalSignature MUST be asserted, and
+        // keyCertSign and cRLSign MUST NOT be.
+        if !key_usage.digital_signature() {
+            return Err(ValidationError::Other(
+                "code signing keyUsage must assert digitalSignature".to_string(),
+            ));
+        }
+
+        permits_ee_key_usage(&key_usage)
+    }
+
+    pub(crate) fn email_protection_extended_key_usage<B: CryptoOps>(
+        policy: &Policy<'_, B>,
+        _cert: &Certificate<'_>,
+        extn: &Extension<'_>,
+    ) -> Result<(), ValidationError> {
+        let ekus: ExtendedKeyUsage<'_> = extn.value()?;
+
+        // S/MIME BR 7.1.2.3 (f): id-kp-emailProtection MUST be present, and
+        // id-kp-serverAuth, id-kp-codeSigning, id-kp-timeStamping and
+        // anyExtendedKeyUsage MUST NOT be present.
+        permits_ekus(
+            policy,
+            &ekus,
+            &[
+                EKU_SERVER_AUTH_OID,
+                EKU_CODE_SIGNING_OID,
+                EKU_TIME_STAMPING_OID,
+                EKU_ANY_KEY_USAGE_OID,
+            ],
+        )
+    }
+
+    pub(crate) fn email_protection_key_usage<B: CryptoOps>(
+        _policy: &Policy<'_, B>,
+        _cert: &Certificate<'_>,
+        extn: &Extension<'_>,
+    ) -> Result<(), ValidationError> {
+        let key_usage: KeyUsage<'_> = extn.value()?;
+
+        // S/MIME BR 7.1.2.3 (e): the key must be usable for signing,
+        // key management, or both.
+        if !(key_usage.digital_signature()
+            || key_usage.content_comitment()
+            || key_usage.key_encipherment()
+            || key_usage.key_agreement())
+        {
+            return Err(ValidationError::Other(
+                "S/MIME keyUsage must assert digitalSignature, contentCommitment, \
+                 keyEncipherment or keyAgreement"
+                    .to_string(),
+            ));
+        }
+
+        permits_ee_key_usage(&key_usage)
+    }
+
+    pub(crate) fn time_stamping_extended_key_usage<B: CryptoOps>(
+        policy: &Policy<'_, B>,
+        _cert: &Certificate<'_>,
+        extn: &Extension<'_>,
+    ) -> Result<(), ValidationError> {
+        let ekus: ExtendedKeyUsage<'_> = extn.value()?;
+
+        // RFC 3161 2.3: the TSA's certificate MUST contain only one instance
+        // of the extended key usage field, with KeyPurposeID
+        // id-kp-timeStamping. Its criticality is checked by the policy.
+        if ekus.clone().any(|eku| eku != policy.extended_key_usage) {
+            return Err(ValidationError::Other(
+                "time stamping extendedKeyUsage must contain only id-kp-timeStamping".to_string(),
+            ));
+        }
+
+        permits_ekus(policy, &ekus, &[])
+    }
+
+    pub(crate) fn time_stamping_key_usage<B: CryptoOps>(
+        _policy: &Policy<'_, B>,
+        _cert: &Certificate<'_>,
+        extn: Option<&Extension<'_>>,
+    ) -> Result<(), ValidationError> {
+        if let Some(extn) = extn {
+            let key_usage: KeyUsage<'_> = extn.value()?;
+
+            // RFC 3628 7.2.1: the TSU's key is used only to sign timestamps.
+            if !(key_usage.digital_signature() || key_usage.content_comitment()) {
+                return Err(ValidationError::Other(
+                    "time stamping keyUsage must assert digitalSignature or contentCommitment"
+                        .to_string(),
+                ));
+            }
+
+            permits_ee_key_usage(&key_usage)?;
+        }
+
+        Ok(())
+    }
 }
 
 pub(crate) mod ca {
@@ -367,14 +726,15 @@ pub(crate) mod ca {
         certificate::Certificate,
         extensions::{
             AuthorityKeyIdentifier, BasicConstraints, ExtendedKeyUsage, Extension, KeyUsage,
-            NameConstraints,
+            NameConstraints, PolicyMapping,
         },
-        oid::EKU_ANY_KEY_USAGE_OID,
+        oid::{ANY_POLICY_OID, EKU_ANY_KEY_USAGE_OID},
     };
 
     use crate::{
         ops::CryptoOps,
         policy::{Policy, ValidationError},
+        FailureReason,
     };
 
     pub(crate) fn authority_key_identifier<B: CryptoOps>(
@@ -493,6 +853,41 @@ pub(crate) mod ca {
         Ok(())
     }
 
+    pub(crate) fn policy_mappings<B: CryptoOps>(
+        _policy: &Policy<'_, B>,
+        _cert: &Certificate<'_>,
+        extn: Option<&Extension<'_>>,
+    ) -> Result<(), ValidationError> {
+        if let Some(extn) = extn {
+            let mut mappings: asn1::SequenceOf<'_, PolicyMapping> = extn.value()?;
+
+            // 5280 4.2.1.5: Policies MUST NOT be mapped either to or from
+            // the special value anyPolicy.
+            if mappings.any(|m| {
+                m.issuer_domain_policy == ANY_POLICY_OID
+                    || m.subject_domain_policy == ANY_POLICY_OID
+            }) {
+                return Err(ValidationError::Other(
+                    "policyMappings must not map to or from anyPolicy".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn inhibit_any_policy<B: CryptoOps>(
+        _policy: &Policy<'_, B>,
+        _cert: &Certificate<'_>,
+        extn: Option<&Extension<'_>>,
+    ) -> Result<(), ValidationError> {
+        if let Some(extn) = extn {
+            let _: u64 = extn.value()?;
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn extended_key_usage<B: CryptoOps>(
         policy: &Policy<'_, B>,
         _cert: &Certificate<'_>,
@@ -506,7 +901,10 @@ pub(crate) mod ca {
             if ekus.any(|eku| eku == policy.extended_key_usage || eku == EKU_ANY_KEY_USAGE_OID) {
                 Ok(())
             } else {
-                Err(ValidationError::Other("required EKU not found".to_string()))
+                Err(ValidationError::Rejected(
+                    FailureReason::ExtendedKeyUsageMismatch,
+                    "required EKU not found".to_string(),
+                ))
             }
         } else {
             Ok(())
@@ -515,9 +913,13 @@ pub(crate) mod ca {
 }
 
 pub(crate) mod common {
+    use std::collections::HashSet;
+
     use cryptography_x509::{
         certificate::Certificate,
-        extensions::{Extension, SequenceOfAccessDescriptions},
+        extensions::{
+            Extension, PolicyConstraints, PolicyInformation, SequenceOfAccessDescriptions,
+        },
     };
 
     use crate::{
@@ -538,19 +940,71 @@ pub(crate) mod common {
 
         Ok(())
     }
+
+    pub(crate) fn certificate_policies<B: CryptoOps>(
+        _policy: &Policy<'_, B>,
+        _cert: &Certificate<'_>,
+        extn: Option<&Extension<'_>>,
+    ) -> Result<(), ValidationError> {
+        if let Some(extn) = extn {
+            let policies: asn1::SequenceOf<'_, PolicyInformation<'_>> = extn.value()?;
+
+            // 5280 4.2.1.4: A certificate policy OID MUST NOT appear more
+            // than once in a certificate policies extension.
+            let mut seen = HashSet::new();
+            for info in policies {
+                if !seen.insert(info.policy_identifier) {
+                    return Err(ValidationError::Other(
+                        "certificatePolicies contains a duplicate policy".to_string(),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn policy_constraints<B: CryptoOps>(
+        _policy: &Policy<'_, B>,
+        _cert: &Certificate<'_>,
+        extn: Option<&Extension<'_>>,
+    ) -> Result<(), ValidationError> {
+        if let Some(extn) = extn {
+            let constraints: PolicyConstraints = extn.value()?;
+
+            // 5280 4.2.1.11: Conforming CAs MUST NOT issue certificates
+            // where policy constraints is an empty sequence.
+            if constraints.require_explicit_policy.is_none()
+                && constraints.inhibit_policy_mapping.is_none()
+            {
+                return Err(ValidationError::Other(
+                    "policyConstraints must not be an empty sequence".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use asn1::{ObjectIdentifier, SimpleAsn1Writable};
     use cryptography_x509::certificate::Certificate;
-    use cryptography_x509::extensions::{BasicConstraints, Extension};
-    use cryptography_x509::oid::BASIC_CONSTRAINTS_OID;
+    use cryptography_x509::common::Asn1ReadableOrWritable;
+    use cryptography_x509::extensions::{BasicConstraints, Extension, Extensions};
+    use cryptography_x509::oid::{
+        AUTHORITY_KEY_IDENTIFIER_OID, BASIC_CONSTRAINTS_OID, EKU_CODE_SIGNING_OID,
+        EKU_TIME_STAMPING_OID, EXTENDED_KEY_USAGE_OID, SUBJECT_ALTERNATIVE_NAME_OID,
+        SUBJECT_KEY_IDENTIFIER_OID,
+    };
 
-    use super::{Criticality, ExtensionValidator};
+    use super::{Criticality, ExtensionValidator, Presence};
     use crate::certificate::tests::PublicKeyErrorOps;
     use crate::ops::tests::{cert, v1_cert_pem};
-    use crate::ops::CryptoOps;
+    use crate::ops::{CryptoOps, VerificationCertificate};
     use crate::policy::{Policy, Subject, ValidationError};
     use crate::types::DNSName;
 
@@ -599,7 +1053,7 @@ mod tests {
     fn test_extension_validator_present() {
         // The certificate doesn't get used for this validator, so the certificate we use isn't important.
         let cert_pem = v1_cert_pem();
-        let cert = cert(&cert_pem);
+        let cert = VerificationCertificate::new(cert(&cert_pem), ());
         let ops = PublicKeyErrorOps {};
         let policy = Policy::server(
             ops,
@@ -639,7 +1093,7 @@ mod tests {
     fn test_extension_validator_maybe() {
         // The certificate doesn't get used for this validator, so the certificate we use isn't important.
         let cert_pem = v1_cert_pem();
-        let cert = cert(&cert_pem);
+        let cert = VerificationCertificate::new(cert(&cert_pem), ());
         let ops = PublicKeyErrorOps {};
         let policy = Policy::server(
             ops,
@@ -673,7 +1127,7 @@ mod tests {
     fn test_extension_validator_not_present() {
         // The certificate doesn't get used for this validator, so the certificate we use isn't important.
         let cert_pem = v1_cert_pem();
-        let cert = cert(&cert_pem);
+        let cert = VerificationCertificate::new(cert(&cert_pem), ());
         let ops = PublicKeyErrorOps {};
         let policy = Policy::server(
             ops,
@@ -704,7 +1158,7 @@ mod tests {
     fn test_extension_validator_present_incorrect_criticality() {
         // The certificate doesn't get used for this validator, so the certificate we use isn't important.
         let cert_pem = v1_cert_pem();
-        let cert = cert(&cert_pem);
+        let cert = VerificationCertificate::new(cert(&cert_pem), ());
         let ops = PublicKeyErrorOps {};
         let policy = Policy::server(
             ops,
@@ -733,7 +1187,7 @@ mod tests {
     fn test_extension_validator_maybe_present_incorrect_criticality() {
         // The certificate doesn't get used for this validator, so the certificate we use isn't important.
         let cert_pem = v1_cert_pem();
-        let cert = cert(&cert_pem);
+        let cert = VerificationCertificate::new(cert(&cert_pem), ());
         let ops = PublicKeyErrorOps {};
         let policy = Policy::server(
             ops,
@@ -759,4 +1213,133 @@ mod tests {
             .permits(&policy, &cert, Some(&raw_ext))
             .is_err());
     }
+
+    #[test]
+    fn test_extension_policy_overrides() {
+        let cert_pem = v1_cert_pem();
+        let cert = VerificationCertificate::new(cert(&cert_pem), ());
+        let no_extensions = Extensions::from_raw_extensions(None).unwrap();
+        let mut policy = Policy::client(PublicKeyErrorOps {}, epoch(), None);
+
+        // By default, EEs must carry an AKI and a SAN.
+        assert!(policy
+            .ee_extension_policy
+            .permits(&policy, &cert, &no_extensions)
+            .is_err());
+
+        policy
+            .ee_extension_policy
+            .set_presence(&AUTHORITY_KEY_IDENTIFIER_OID, Presence::Optional);
+        policy
+            .ee_extension_policy
+            .set_presence(&SUBJECT_ALTERNATIVE_NAME_OID, Presence::Optional);
+        assert!(policy
+            .ee_extension_policy
+            .permits(&policy, &cert, &no_extensions)
+            .is_ok());
+
+        policy
+            .ee_extension_policy
+            .set_presence(&SUBJECT_KEY_IDENTIFIER_OID, Presence::Required);
+        assert!(policy
+            .ee_extension_policy
+            .permits(&policy, &cert, &no_extensions)
+            .is_err());
+    }
+
+    #[test]
+    fn test_extension_policy_custom_extension() {
+        let cert_pem = v1_cert_pem();
+        let cert = VerificationCertificate::new(cert(&cert_pem), ());
+        let mut policy = Policy::client(PublicKeyErrorOps {}, epoch(), None);
+        policy
+            .ee_extension_policy
+            .set_presence(&AUTHORITY_KEY_IDENTIFIER_OID, Presence::Optional);
+        policy
+            .ee_extension_policy
+            .set_presence(&SUBJECT_ALTERNATIVE_NAME_OID, Presence::Optional);
+
+        let oid = asn1::oid!(1, 3, 6, 1, 4, 1, 55555, 1);
+        let der_ext = create_encoded_extension(oid.clone(), true, &());
+        let raw_ext: Extension<'_> = asn1::parse_single(&der_ext).unwrap();
+        let der_exts = asn1::write_single(&asn1::SequenceOfWriter::new(vec![raw_ext])).unwrap();
+        let raw_exts = Asn1ReadableOrWritable::new_read(asn1::parse_single(&der_exts).unwrap());
+        let extensions = Extensions::from_raw_extensions(Some(&raw_exts)).unwrap();
+
+        // An unknown critical extension is rejected by default...
+        assert!(policy
+            .ee_extension_policy
+            .permits(&policy, &cert, &extensions)
+            .is_err());
+
+        // ...but is accepted once a validator for it is registered.
+        policy.ee_extension_policy.set_validator(
+            &oid,
+            Arc::new(|_, _, ext| {
+                assert!(ext.is_some());
+                Ok(())
+            }),
+        );
+        assert!(policy
+            .ee_extension_policy
+            .permits(&policy, &cert, &extensions)
+            .is_ok());
+
+        // Criticality overrides apply to custom extensions too.
+        policy
+            .ee_extension_policy
+            .set_criticality(&oid, Criticality::NonCritical);
+        assert!(policy
+            .ee_extension_policy
+            .permits(&policy, &cert, &extensions)
+            .is_err());
+
+        // Custom validators are called even when their extension is absent.
+        let no_extensions = Extensions::from_raw_extensions(None).unwrap();
+        policy.ee_extension_policy.set_validator(
+            &oid,
+            Arc::new(|_, _, ext| match ext {
+                Some(_) => Ok(()),
+                None => Err(ValidationError::Other("missing".to_string())),
+            }),
+        );
+        assert!(policy
+            .ee_extension_policy
+            .permits(&policy, &cert, &no_extensions)
+            .is_err());
+    }
+
+    #[test]
+    fn test_time_stamping_extended_key_usage() {
+        let cert_pem = v1_cert_pem();
+        let cert = VerificationCertificate::new(cert(&cert_pem), ());
+        let policy = Policy::time_stamping(PublicKeyErrorOps {}, epoch(), None);
+        let validator = &policy.ee_extension_policy.extended_key_usage;
+
+        for (ekus, critical, ok) in [
+            (vec![EKU_TIME_STAMPING_OID], true, true),
+            // RFC 3161 2.3: the EKU must be critical...
+            (vec![EKU_TIME_STAMPING_OID], false, false),
+            // ...and must contain only id-kp-timeStamping.
+            (
+                vec![EKU_TIME_STAMPING_OID, EKU_CODE_SIGNING_OID],
+                true,
+                false,
+            ),
+            (vec![EKU_CODE_SIGNING_OID], true, false),
+        ] {
+            let der_ext = create_encoded_extension(
+                EXTENDED_KEY_USAGE_OID,
+                critical,
+                &asn1::SequenceOfWriter::new(ekus),
+            );
+            let raw_ext = asn1::parse_single(&der_ext).unwrap();
+            assert_eq!(
+                validator.permits(&policy, &cert, Some(&raw_ext)).is_ok(),
+                ok
+            );
+        }
+
+        assert!(validator.permits(&policy, &cert, None).is_err());
+    }
 }
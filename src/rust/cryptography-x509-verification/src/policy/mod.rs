@@ -4,30 +4,41 @@
 
 mod extension;
 
-use std::collections::HashSet;
+pub use crate::policy::extension::{
+    Criticality, CustomExtensionValidatorCallback, ExtensionPolicy, Presence,
+};
+
+use std::collections::{HashMap, HashSet};
 use std::ops::Range;
 use std::sync::Arc;
+use std::time::Duration;
 
 use asn1::ObjectIdentifier;
 use cryptography_key_parsing::rsa::Pkcs1RsaPublicKey;
 use cryptography_x509::certificate::Certificate;
 use cryptography_x509::common::{
-    AlgorithmIdentifier, AlgorithmParameters, EcParameters, RsaPssParameters, Time,
-    PSS_SHA256_HASH_ALG, PSS_SHA256_MASK_GEN_ALG, PSS_SHA384_HASH_ALG, PSS_SHA384_MASK_GEN_ALG,
-    PSS_SHA512_HASH_ALG, PSS_SHA512_MASK_GEN_ALG,
+    AlgorithmIdentifier, AlgorithmParameters, EcParameters, RsaPssParameters, SubjectPublicKeyInfo,
+    Time, PSS_SHA256_HASH_ALG, PSS_SHA256_MASK_GEN_ALG, PSS_SHA384_HASH_ALG,
+    PSS_SHA384_MASK_GEN_ALG, PSS_SHA512_HASH_ALG, PSS_SHA512_MASK_GEN_ALG,
 };
 use cryptography_x509::extensions::{BasicConstraints, Extensions, SubjectAlternativeName};
 use cryptography_x509::name::GeneralName;
 use cryptography_x509::oid::{
     BASIC_CONSTRAINTS_OID, EC_SECP256R1, EC_SECP384R1, EC_SECP521R1, EKU_CLIENT_AUTH_OID,
-    EKU_SERVER_AUTH_OID,
+    EKU_CODE_SIGNING_OID, EKU_EMAIL_PROTECTION_OID, EKU_SERVER_AUTH_OID, EKU_TIME_STAMPING_OID,
+    RSASSA_PSS_OID,
 };
 use once_cell::sync::Lazy;
 
+use crate::cache::SignatureCache;
+use crate::ct::CTPolicy;
 use crate::ops::CryptoOps;
-use crate::policy::extension::{ca, common, ee, Criticality, ExtensionPolicy, ExtensionValidator};
-use crate::types::{DNSName, DNSPattern, IPAddress};
-use crate::{ValidationError, VerificationCertificate};
+use crate::policy::extension::{ca, common, ee, ExtensionValidator};
+use crate::policy_tree::PolicyTreeConstraints;
+use crate::revocation::RevocationPolicy;
+use crate::trust_store::KeyAnchor;
+use crate::types::{DNSName, DNSPattern, IPAddress, SmtpUTF8Mailbox, SpiffeId};
+use crate::{Budget, FailureReason, ValidationError, VerificationCertificate};
 
 // RSA key constraints, as defined in CA/B 6.1.5.
 static WEBPKI_MINIMUM_RSA_MODULUS: usize = 2048;
@@ -169,11 +180,33 @@ pub static WEBPKI_PERMITTED_SIGNATURE_ALGORITHMS: Lazy<Arc<HashSet<AlgorithmIden
 /// necessary.
 const DEFAULT_MAX_CHAIN_DEPTH: u8 = 8;
 
+/// The default maximum number of signature verifications that path building
+/// may perform. This is the same limit as Go's `crypto/x509`, and is enough
+/// for realistic cross-signed hierarchies while bounding the work that a
+/// mesh of same-subject intermediates can force.
+const DEFAULT_MAX_SIGNATURE_CHECKS: usize = 100;
+
+/// The default maximum number of candidate issuers that path building may
+/// visit. Most candidates cost a signature check, so this only matters for
+/// candidates that are rejected by the policy before their signature is
+/// checked.
+const DEFAULT_MAX_CANDIDATES: usize = 1024;
+
+/// The default maximum number of AIA `caIssuers` URIs that path building may
+/// fetch. Each fetch is potentially a network round trip, so this is kept
+/// small; it's enough for a handful of missing intermediates.
+const DEFAULT_MAX_ISSUER_FETCHES: usize = 8;
+
 /// Represents a logical certificate "subject," i.e. a principal matching
 /// one of the names listed in a certificate's `subjectAltNames` extension.
 pub enum Subject<'a> {
     DNS(DNSName<'a>),
     IP(IPAddress),
+    Email(SmtpUTF8Mailbox<'a>),
+    /// A URI. The ID of a SPIFFE trust domain (e.g. `spiffe://example.org`)
+    /// matches the SPIFFE ID of any workload in the trust domain; other
+    /// URIs must match exactly.
+    Uri(&'a str),
 }
 
 impl Subject<'_> {
@@ -185,6 +218,23 @@ impl Subject<'_> {
             (GeneralName::IPAddress(addr), Self::IP(name)) => {
                 IPAddress::from_bytes(addr).map_or(false, |addr| addr == *name)
             }
+            // RFC 8398 5: an email subject matches both rfc822Name and
+            // SmtpUTF8Mailbox names, with the domain compared in its
+            // ASCII form.
+            (GeneralName::RFC822Name(_) | GeneralName::OtherName(_), Self::Email(name)) => {
+                crate::email_address(general_name)
+                    .and_then(Result::ok)
+                    .and_then(SmtpUTF8Mailbox::new)
+                    .map_or(false, |address| address == *name)
+            }
+            (GeneralName::UniformResourceIdentifier(uri), Self::Uri(name)) => {
+                match (SpiffeId::new(name), SpiffeId::new(uri.0)) {
+                    (Some(expected), Some(id)) if expected.is_trust_domain() => {
+                        expected.trust_domain() == id.trust_domain()
+                    }
+                    _ => uri.0 == *name,
+                }
+            }
             _ => false,
         }
     }
@@ -196,6 +246,21 @@ impl Subject<'_> {
     }
 }
 
+/// How the validity periods of the certificates in a chain are checked
+/// against the validation time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidationModel {
+    /// The RFC 5280 model, also known as the shell model: every certificate
+    /// in the chain must be valid at the validation time.
+    Shell,
+    /// The chain model: the EE must be valid at the validation time, and
+    /// each issuer must have been valid when it issued its child, i.e. at
+    /// the child's `notBefore`. This is typically used to validate
+    /// signatures on documents, with the validation time being the time of
+    /// signing.
+    Chain,
+}
+
 /// A `Policy` describes user-configurable aspects of X.509 path validation.
 pub struct Policy<'a, B: CryptoOps> {
     pub ops: B,
@@ -211,10 +276,18 @@ pub struct Policy<'a, B: CryptoOps> {
     /// validated by this policy must match.
     pub subject: Option<Subject<'a>>,
 
-    /// The validation time. All certificates validated by this policy must
-    /// be valid at this time.
+    /// The validation time. Which certificates must be valid at this time
+    /// depends on the `validation_model`.
     pub validation_time: asn1::DateTime,
 
+    /// How certificate validity periods are checked against the validation
+    /// time.
+    pub validation_model: ValidationModel,
+
+    /// The clock skew tolerated when checking a certificate's `notBefore`
+    /// and `notAfter`.
+    pub clock_skew: Duration,
+
     /// An extended key usage that must appear in EEs validated by this policy.
     pub extended_key_usage: ObjectIdentifier,
 
@@ -223,15 +296,68 @@ pub struct Policy<'a, B: CryptoOps> {
     pub minimum_rsa_modulus: usize,
 
     /// The set of permitted public key algorithms, identified by their
-    /// algorithm identifiers.
+    /// algorithm identifiers. An `id-RSASSA-PSS` identifier without
+    /// parameters permits RSASSA-PSS keys with any parameters, since those
+    /// only restrict the signatures that the key may make.
     pub permitted_public_key_algorithms: Arc<HashSet<AlgorithmIdentifier<'a>>>,
 
     /// The set of permitted signature algorithms, identified by their
     /// algorithm identifiers.
     pub permitted_signature_algorithms: Arc<HashSet<AlgorithmIdentifier<'a>>>,
 
-    ca_extension_policy: ExtensionPolicy<B>,
-    ee_extension_policy: ExtensionPolicy<B>,
+    /// The revocation data to check non-anchor certificates against, if
+    /// any. When `None`, no revocation checking is performed.
+    pub revocation: Option<RevocationPolicy<'a>>,
+
+    /// The clock skew tolerated when checking the `thisUpdate` and
+    /// `nextUpdate` of stapled OCSP responses against the validation time.
+    pub ocsp_clock_skew: Duration,
+
+    /// The inputs to RFC 5280 certificate policy processing, if enabled.
+    /// When `None`, certificate policies are not processed.
+    pub certificate_policies: Option<PolicyTreeConstraints>,
+
+    /// The SCTs required of EE certificates, if any. When `None`,
+    /// Certificate Transparency is not enforced.
+    pub certificate_transparency: Option<CTPolicy<B>>,
+
+    /// Whether a certificate that asserts the `status_request` TLS feature
+    /// (RFC 7633 "must-staple") requires a valid stapled OCSP response.
+    pub must_staple: bool,
+
+    /// The SHA-256 digests of pinned DER-encoded `SubjectPublicKeyInfo`s, if
+    /// any. When set, a chain is only accepted if at least one of its
+    /// certificates has a pinned SPKI.
+    pub spki_pins: Option<Arc<HashSet<Vec<u8>>>>,
+
+    /// Whether any certificate in the trust store may terminate a path, like
+    /// OpenSSL's `X509_V_FLAG_PARTIAL_CHAIN`. This is the default. When
+    /// `false`, only self-issued store certificates (and a leaf that is
+    /// itself in the store) do; path building continues past any others to
+    /// their issuers.
+    pub partial_chain: bool,
+
+    /// The maximum number of signature verifications that path building
+    /// may perform.
+    pub max_signature_checks: usize,
+
+    /// The maximum number of candidate issuers that path building may visit.
+    pub max_candidates: usize,
+
+    /// The maximum number of AIA `caIssuers` URIs that path building may
+    /// fetch issuer certificates from.
+    pub max_issuer_fetches: usize,
+
+    /// The maximum wall-clock time that path building may take, if any.
+    pub max_path_building_time: Option<Duration>,
+
+    /// The extensions permitted or required in CA certificates, and how
+    /// they are validated.
+    pub ca_extension_policy: ExtensionPolicy<B>,
+
+    /// The extensions permitted or required in EE certificates, and how
+    /// they are validated.
+    pub ee_extension_policy: ExtensionPolicy<B>,
 }
 
 impl<'a, B: CryptoOps> Policy<'a, B> {
@@ -247,10 +373,23 @@ impl<'a, B: CryptoOps> Policy<'a, B> {
             max_chain_depth: max_chain_depth.unwrap_or(DEFAULT_MAX_CHAIN_DEPTH),
             subject,
             validation_time: time,
+            validation_model: ValidationModel::Shell,
+            clock_skew: Duration::ZERO,
             extended_key_usage,
             minimum_rsa_modulus: WEBPKI_MINIMUM_RSA_MODULUS,
             permitted_public_key_algorithms: Arc::clone(&*WEBPKI_PERMITTED_SPKI_ALGORITHMS),
             permitted_signature_algorithms: Arc::clone(&*WEBPKI_PERMITTED_SIGNATURE_ALGORITHMS),
+            revocation: None,
+            ocsp_clock_skew: Duration::ZERO,
+            certificate_policies: None,
+            certificate_transparency: None,
+            must_staple: false,
+            spki_pins: None,
+            partial_chain: true,
+            max_signature_checks: DEFAULT_MAX_SIGNATURE_CHECKS,
+            max_candidates: DEFAULT_MAX_CANDIDATES,
+            max_issuer_fetches: DEFAULT_MAX_ISSUER_FETCHES,
+            max_path_building_time: None,
             ca_extension_policy: ExtensionPolicy {
                 // 5280 4.2.2.1: Authority Information Access
                 authority_information_access: ExtensionValidator::maybe_present(
@@ -294,6 +433,29 @@ impl<'a, B: CryptoOps> Policy<'a, B> {
                     Criticality::NonCritical,
                     Some(ca::extended_key_usage),
                 ),
+                // 5280 4.2.1.4: Certificate Policies
+                certificate_policies: ExtensionValidator::maybe_present(
+                    Criticality::Agnostic,
+                    Some(common::certificate_policies),
+                ),
+                // 5280 4.2.1.5: Policy Mappings
+                policy_mappings: ExtensionValidator::maybe_present(
+                    Criticality::Agnostic,
+                    Some(ca::policy_mappings),
+                ),
+                // 5280 4.2.1.11: Policy Constraints
+                // NOTE: MUST be critical in 5280, but we choose to be permissive here.
+                policy_constraints: ExtensionValidator::maybe_present(
+                    Criticality::Agnostic,
+                    Some(common::policy_constraints),
+                ),
+                // 5280 4.2.1.14: Inhibit anyPolicy
+                // NOTE: MUST be critical in 5280, but we choose to be permissive here.
+                inhibit_any_policy: ExtensionValidator::maybe_present(
+                    Criticality::Agnostic,
+                    Some(ca::inhibit_any_policy),
+                ),
+                custom: HashMap::new(),
             },
             ee_extension_policy: ExtensionPolicy {
                 // 5280 4.2.2.1: Authority Information Access
@@ -336,6 +498,21 @@ impl<'a, B: CryptoOps> Policy<'a, B> {
                     Criticality::NonCritical,
                     Some(ee::extended_key_usage),
                 ),
+                // 5280 4.2.1.4: Certificate Policies
+                certificate_policies: ExtensionValidator::maybe_present(
+                    Criticality::Agnostic,
+                    Some(common::certificate_policies),
+                ),
+                // Policy mappings and anyPolicy inhibition are only meaningful
+                // in CA certificates, and are ignored in EEs.
+                policy_mappings: ExtensionValidator::maybe_present(Criticality::Agnostic, None),
+                // 5280 6.1.5 (b): an EE's requireExplicitPolicy is processed.
+                policy_constraints: ExtensionValidator::maybe_present(
+                    Criticality::Agnostic,
+                    Some(common::policy_constraints),
+                ),
+                inhibit_any_policy: ExtensionValidator::maybe_present(Criticality::Agnostic, None),
+                custom: HashMap::new(),
             },
         }
     }
@@ -364,16 +541,168 @@ impl<'a, B: CryptoOps> Policy<'a, B> {
         time: asn1::DateTime,
         max_chain_depth: Option<u8>,
     ) -> Self {
-        Self::new(
+        let mut policy = Self::new(
             ops,
             Some(subject),
             time,
             max_chain_depth,
             EKU_SERVER_AUTH_OID.clone(),
+        );
+
+        // RFC 7633: A TLS client MUST reject a certificate that asserts
+        // `status_request` if the server doesn't staple a valid OCSP response.
+        policy.must_staple = true;
+
+        policy
+    }
+
+    /// Create a new policy with defaults for the code signing certificate
+    /// profile defined in the CA/B Forum's Code Signing Baseline
+    /// Requirements.
+    pub fn code_signing(ops: B, time: asn1::DateTime, max_chain_depth: Option<u8>) -> Self {
+        let mut policy = Self::new(
+            ops,
+            None,
+            time,
+            max_chain_depth,
+            EKU_CODE_SIGNING_OID.clone(),
+        );
+
+        // CS BR 7.1.2.3: Code signing and Timestamp Certificate
+        // The subject is an organization or individual, so no SAN is needed,
+        // but the EKU and keyUsage are.
+        policy.ee_extension_policy.subject_alternative_name =
+            ExtensionValidator::maybe_present(Criticality::Agnostic, None);
+        policy.ee_extension_policy.key_usage =
+            ExtensionValidator::present(Criticality::Agnostic, Some(ee::code_signing_key_usage));
+        policy.ee_extension_policy.extended_key_usage = ExtensionValidator::present(
+            Criticality::Agnostic,
+            Some(ee::code_signing_extended_key_usage),
+        );
+
+        policy
+    }
+
+    /// Create a new policy with defaults for the S/MIME certificate profile
+    /// defined in the CA/B Forum's S/MIME Baseline Requirements. EE
+    /// certificates must have an email address matching `subject`.
+    pub fn email_protection(
+        ops: B,
+        subject: SmtpUTF8Mailbox<'a>,
+        time: asn1::DateTime,
+        max_chain_depth: Option<u8>,
+    ) -> Self {
+        let mut policy = Self::new(
+            ops,
+            Some(Subject::Email(subject)),
+            time,
+            max_chain_depth,
+            EKU_EMAIL_PROTECTION_OID.clone(),
+        );
+
+        // S/MIME BR 7.1.2.3: Subscriber certificates
+        policy.ee_extension_policy.key_usage = ExtensionValidator::present(
+            Criticality::Agnostic,
+            Some(ee::email_protection_key_usage),
+        );
+        policy.ee_extension_policy.extended_key_usage = ExtensionValidator::present(
+            Criticality::Agnostic,
+            Some(ee::email_protection_extended_key_usage),
+        );
+
+        policy
+    }
+
+    /// Create a new policy with defaults for the time stamping authority
+    /// certificate profile defined in RFC 3161.
+    pub fn time_stamping(ops: B, time: asn1::DateTime, max_chain_depth: Option<u8>) -> Self {
+        let mut policy = Self::new(
+            ops,
+            None,
+            time,
+            max_chain_depth,
+            EKU_TIME_STAMPING_OID.clone(),
+        );
+
+        // RFC 3161 2.3: the TSA's certificate MUST contain a critical EKU
+        // with only id-kp-timeStamping. A TSA is not identified by a SAN.
+        policy.ee_extension_policy.subject_alternative_name =
+            ExtensionValidator::maybe_present(Criticality::Agnostic, None);
+        policy.ee_extension_policy.key_usage = ExtensionValidator::maybe_present(
+            Criticality::Agnostic,
+            Some(ee::time_stamping_key_usage),
+        );
+        policy.ee_extension_policy.extended_key_usage = ExtensionValidator::present(
+            Criticality::Critical,
+            Some(ee::time_stamping_extended_key_usage),
+        );
+
+        policy
+    }
+
+    /// Create a new policy with defaults for the X509-SVID profile defined
+    /// by SPIFFE, for authenticating servers. EE certificates must have
+    /// exactly one URI SAN, which is the SPIFFE ID of a workload, and it must
+    /// match `subject` if one is given.
+    pub fn spiffe_server(
+        ops: B,
+        subject: Option<Subject<'a>>,
+        time: asn1::DateTime,
+        max_chain_depth: Option<u8>,
+    ) -> Self {
+        Self::spiffe(
+            ops,
+            subject,
+            time,
+            max_chain_depth,
+            EKU_SERVER_AUTH_OID.clone(),
         )
     }
 
-    fn permits_basic(&self, cert: &Certificate<'_>) -> Result<(), ValidationError> {
+    /// Like [`Policy::spiffe_server`], but for authenticating clients.
+    pub fn spiffe_client(
+        ops: B,
+        subject: Option<Subject<'a>>,
+        time: asn1::DateTime,
+        max_chain_depth: Option<u8>,
+    ) -> Self {
+        Self::spiffe(
+            ops,
+            subject,
+            time,
+            max_chain_depth,
+            EKU_CLIENT_AUTH_OID.clone(),
+        )
+    }
+
+    fn spiffe(
+        ops: B,
+        subject: Option<Subject<'a>>,
+        time: asn1::DateTime,
+        max_chain_depth: Option<u8>,
+        extended_key_usage: ObjectIdentifier,
+    ) -> Self {
+        let mut policy = Self::new(ops, subject, time, max_chain_depth, extended_key_usage);
+
+        // X509-SVID 2: An X.509 SVID MUST contain exactly one URI SAN.
+        // Its other SANs and its subject are unconstrained.
+        policy.ee_extension_policy.subject_alternative_name = ExtensionValidator::present(
+            Criticality::Agnostic,
+            Some(ee::spiffe_subject_alternative_name),
+        );
+        // X509-SVID 4.3: The key usage extension MUST be set on all SVIDs,
+        // and MUST be marked critical.
+        policy.ee_extension_policy.key_usage =
+            ExtensionValidator::present(Criticality::Critical, Some(ee::spiffe_key_usage));
+
+        policy
+    }
+
+    fn permits_basic(
+        &self,
+        cert: &Certificate<'_>,
+        validation_time: &asn1::DateTime,
+    ) -> Result<(), ValidationError> {
         // CA/B 7.1.1:
         // Certificates MUST be of type X.509 v3.
         if cert.tbs_cert.version != 2 {
@@ -422,12 +751,15 @@ impl<'a, B: CryptoOps> Policy<'a, B> {
         // 5280 4.1.2.5: Validity
         // Validity dates before 2050 MUST be encoded as UTCTime;
         // dates in or after 2050 MUST be encoded as GeneralizedTime.
-        let not_before = cert.tbs_cert.validity.not_before.as_datetime();
-        let not_after = cert.tbs_cert.validity.not_after.as_datetime();
+        let not_before = unix_seconds(cert.tbs_cert.validity.not_before.as_datetime());
+        let not_after = unix_seconds(cert.tbs_cert.validity.not_after.as_datetime());
         permits_validity_date(&cert.tbs_cert.validity.not_before)?;
         permits_validity_date(&cert.tbs_cert.validity.not_after)?;
-        if &self.validation_time < not_before || &self.validation_time > not_after {
-            return Err(ValidationError::Other(
+        let now = unix_seconds(validation_time);
+        let skew = i64::try_from(self.clock_skew.as_secs()).unwrap_or(i64::MAX);
+        if not_before.saturating_sub(skew) > now || not_after.saturating_add(skew) < now {
+            return Err(ValidationError::Rejected(
+                FailureReason::Expired,
                 "cert is not valid at validation time".to_string(),
             ));
         }
@@ -435,14 +767,16 @@ impl<'a, B: CryptoOps> Policy<'a, B> {
         Ok(())
     }
 
-    /// Checks whether the given CA certificate is compatible with this policy.
+    /// Checks whether the given CA certificate is compatible with this policy,
+    /// when issuing a certificate at `validation_time`.
     pub(crate) fn permits_ca(
         &self,
-        cert: &Certificate<'_>,
+        cert: &VerificationCertificate<'_, B>,
         current_depth: u8,
         extensions: &Extensions<'_>,
+        validation_time: &asn1::DateTime,
     ) -> Result<(), ValidationError> {
-        self.permits_basic(cert)?;
+        self.permits_basic(cert.certificate(), validation_time)?;
 
         // 5280 4.1.2.6: Subject
         // CA certificates MUST have a subject populated with a non-empty distinguished name.
@@ -464,7 +798,8 @@ impl<'a, B: CryptoOps> Policy<'a, B> {
                 .path_length
                 .map_or(false, |len| u64::from(current_depth) > len)
             {
-                return Err(ValidationError::Other(
+                return Err(ValidationError::Rejected(
+                    FailureReason::DepthExceeded,
                     "path length constraint violated".to_string(),
                 ))?;
             }
@@ -478,10 +813,10 @@ impl<'a, B: CryptoOps> Policy<'a, B> {
     /// Checks whether the given EE certificate is compatible with this policy.
     pub(crate) fn permits_ee(
         &self,
-        cert: &Certificate<'_>,
+        cert: &VerificationCertificate<'_, B>,
         extensions: &Extensions<'_>,
     ) -> Result<(), ValidationError> {
-        self.permits_basic(cert)?;
+        self.permits_basic(cert.certificate(), &self.validation_time)?;
 
         self.ee_extension_policy.permits(self, cert, extensions)?;
 
@@ -507,17 +842,90 @@ impl<'a, B: CryptoOps> Policy<'a, B> {
         child: &Certificate<'_>,
         current_depth: u8,
         issuer_extensions: &Extensions<'_>,
+        signature_cache: Option<&SignatureCache>,
+        budget: &mut Budget,
     ) -> Result<(), ValidationError> {
-        // The issuer needs to be a valid CA at the current depth.
-        self.permits_ca(issuer.certificate(), current_depth, issuer_extensions)?;
+        // The issuer needs to be a valid CA at the current depth. Under the
+        // chain model, it needs to have been valid when it issued the child.
+        let validation_time = match self.validation_model {
+            ValidationModel::Shell => &self.validation_time,
+            ValidationModel::Chain => child.tbs_cert.validity.not_before.as_datetime(),
+        };
+        self.permits_ca(issuer, current_depth, issuer_extensions, validation_time)?;
+        self.permits_issuer_key(&issuer.certificate().tbs_cert.spki, child)?;
+
+        // NOTE: The child is the leaf at depth 0.
+        let signature_cache =
+            signature_cache.filter(|cache| current_depth > 0 || !cache.skips_leaves());
+        let cache_key = signature_cache
+            .and_then(|_| SignatureCache::key(&self.ops, issuer.certificate(), child));
+        if let (Some(cache), Some(key)) = (signature_cache, &cache_key) {
+            if cache.contains(key) {
+                return Ok(());
+            }
+        }
+
+        let pk = issuer.public_key(&self.ops).map_err(|_| {
+            ValidationError::Rejected(
+                FailureReason::BadSignature,
+                "issuer has malformed public key".to_string(),
+            )
+        })?;
+        self.permits_signature(child, pk, budget)?;
+        if let (Some(cache), Some(key)) = (signature_cache, cache_key) {
+            cache.insert(key);
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether `anchor`, a trust anchor without a certificate, is a
+    /// valid issuer for `child`.
+    ///
+    /// As a precondition, the caller must have already checked that
+    /// `anchor.name() == child.issuer()`.
+    pub(crate) fn valid_key_anchor(
+        &self,
+        anchor: &KeyAnchor<'_, B>,
+        child: &Certificate<'_>,
+        budget: &mut Budget,
+    ) -> Result<(), ValidationError> {
+        self.permits_issuer_key(anchor.spki(), child)?;
+
+        let pk = anchor.public_key(&self.ops).map_err(|_| {
+            ValidationError::Rejected(
+                FailureReason::BadSignature,
+                "trust anchor has malformed public key".to_string(),
+            )
+        })?;
+        self.permits_signature(child, pk, budget)
+    }
+
+    /// Returns true if `algorithm` is one of the permitted public key
+    /// algorithms.
+    pub(crate) fn permits_public_key_algorithm(&self, algorithm: &AlgorithmIdentifier<'_>) -> bool {
+        if algorithm.oid() == &RSASSA_PSS_OID {
+            return self
+                .permitted_public_key_algorithms
+                .contains(&AlgorithmIdentifier {
+                    oid: asn1::DefinedByMarker::marker(),
+                    params: AlgorithmParameters::RsaPss(None),
+                });
+        }
+        self.permitted_public_key_algorithms.contains(algorithm)
+    }
 
+    /// Checks that `issuer_spki` is permitted as the key of `child`'s
+    /// issuer, and that `child`'s signature algorithm is permitted.
+    fn permits_issuer_key(
+        &self,
+        issuer_spki: &SubjectPublicKeyInfo<'_>,
+        child: &Certificate<'_>,
+    ) -> Result<(), ValidationError> {
         // CA/B 7.1.3.1 SubjectPublicKeyInfo
         // NOTE: We check the issuer's SPKI here, since the issuer is
         // definitionally a CA and thus subject to CABF key requirements.
-        if !self
-            .permitted_public_key_algorithms
-            .contains(&issuer.certificate().tbs_cert.spki.algorithm)
-        {
+        if !self.permits_public_key_algorithm(&issuer_spki.algorithm) {
             return Err(ValidationError::Other(format!(
                 "Forbidden public key algorithm: {:?}",
                 &child.tbs_cert.spki.algorithm
@@ -543,7 +951,6 @@ impl<'a, B: CryptoOps> Policy<'a, B> {
         // CA/B 6.1.5: Key sizes
         // NOTE: We don't currently enforce that RSA moduli are divisible by 8,
         // since other implementations don't bother.
-        let issuer_spki = &issuer.certificate().tbs_cert.spki;
         if matches!(
             issuer_spki.algorithm.params,
             AlgorithmParameters::Rsa(_) | AlgorithmParameters::RsaPss(_)
@@ -556,17 +963,58 @@ impl<'a, B: CryptoOps> Policy<'a, B> {
             }
         }
 
-        let pk = issuer
-            .public_key(&self.ops)
-            .map_err(|_| ValidationError::Other("issuer has malformed public key".to_string()))?;
+        Ok(())
+    }
+
+    /// Checks that `child` is signed by `pk`.
+    fn permits_signature(
+        &self,
+        child: &Certificate<'_>,
+        pk: &B::Key,
+        budget: &mut Budget,
+    ) -> Result<(), ValidationError> {
+        budget.signature_check()?;
         if self.ops.verify_signed_by(child, pk).is_err() {
-            return Err(ValidationError::Other(
+            return Err(ValidationError::Rejected(
+                FailureReason::BadSignature,
                 "signature does not match".to_string(),
             ));
         }
 
         Ok(())
     }
+
+    /// Checks whether `chain`, a complete candidate chain ending at `anchor`
+    /// (if it's a trust anchor without a certificate), satisfies this
+    /// policy's SPKI pins, if any.
+    pub(crate) fn permits_pins(
+        &self,
+        chain: &[VerificationCertificate<'_, B>],
+        anchor: Option<&KeyAnchor<'_, B>>,
+    ) -> Result<(), ValidationError> {
+        let pins = match &self.spki_pins {
+            Some(pins) => pins,
+            None => return Ok(()),
+        };
+
+        let mut spkis = chain
+            .iter()
+            .map(|cert| &cert.certificate().tbs_cert.spki)
+            .chain(anchor.map(|anchor| anchor.spki()));
+        let pinned = spkis.any(|spki| {
+            self.ops
+                .digest(&PSS_SHA256_HASH_ALG, spki.tlv().full_data())
+                .map_or(false, |digest| pins.contains(&digest))
+        });
+        if !pinned {
+            return Err(ValidationError::Rejected(
+                FailureReason::PinMismatch,
+                "no certificate in the chain has a pinned public key".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 fn permits_validity_date(validity_date: &Time) -> Result<(), ValidationError> {
@@ -585,6 +1033,23 @@ fn permits_validity_date(validity_date: &Time) -> Result<(), ValidationError> {
     Ok(())
 }
 
+/// Returns the number of seconds between the Unix epoch and `dt`.
+pub(crate) fn unix_seconds(dt: &asn1::DateTime) -> i64 {
+    // Howard Hinnant's `days_from_civil`.
+    let (month, day) = (i64::from(dt.month()), i64::from(dt.day()));
+    let year = i64::from(dt.year()) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+
+    days * 86400
+        + i64::from(dt.hour()) * 3600
+        + i64::from(dt.minute()) * 60
+        + i64::from(dt.second())
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::Deref;
@@ -597,16 +1062,16 @@ mod tests {
     };
 
     use super::{
-        permits_validity_date, ECDSA_SHA256, ECDSA_SHA384, ECDSA_SHA512, RSASSA_PKCS1V15_SHA256,
-        RSASSA_PKCS1V15_SHA384, RSASSA_PKCS1V15_SHA512, RSASSA_PSS_SHA256, RSASSA_PSS_SHA384,
-        RSASSA_PSS_SHA512, WEBPKI_PERMITTED_SIGNATURE_ALGORITHMS,
+        permits_validity_date, unix_seconds, ECDSA_SHA256, ECDSA_SHA384, ECDSA_SHA512,
+        RSASSA_PKCS1V15_SHA256, RSASSA_PKCS1V15_SHA384, RSASSA_PKCS1V15_SHA512, RSASSA_PSS_SHA256,
+        RSASSA_PSS_SHA384, RSASSA_PSS_SHA512, WEBPKI_PERMITTED_SIGNATURE_ALGORITHMS,
     };
     use crate::{
         policy::{
             Subject, SPKI_RSA, SPKI_SECP256R1, SPKI_SECP384R1, SPKI_SECP521R1,
             WEBPKI_PERMITTED_SPKI_ALGORITHMS,
         },
-        types::{DNSName, IPAddress},
+        types::{DNSName, IPAddress, SmtpUTF8Mailbox},
     };
 
     #[test]
@@ -759,6 +1224,53 @@ mod tests {
 
             assert!(!domain_sub.matches(&any_cryptography_io));
         }
+
+        // Single SAN, email address.
+        {
+            let email_sub = Subject::Email(SmtpUTF8Mailbox::new("user@Cryptography.io").unwrap());
+            let email_gn = GeneralName::RFC822Name(UnvalidatedIA5String("user@cryptography.io"));
+            let san_der = asn1::write_single(&SequenceOfWriter::new([email_gn])).unwrap();
+            let user = asn1::parse_single::<SubjectAlternativeName<'_>>(&san_der).unwrap();
+
+            assert!(email_sub.matches(&user));
+            assert!(!domain_sub.matches(&user));
+
+            // Local parts are compared exactly.
+            let other_sub = Subject::Email(SmtpUTF8Mailbox::new("User@cryptography.io").unwrap());
+            assert!(!other_sub.matches(&user));
+        }
+
+        // Single SAN, SPIFFE ID.
+        {
+            let uri_gn = GeneralName::UniformResourceIdentifier(UnvalidatedIA5String(
+                "spiffe://example.org/web",
+            ));
+            let san_der = asn1::write_single(&SequenceOfWriter::new([uri_gn])).unwrap();
+            let web = asn1::parse_single::<SubjectAlternativeName<'_>>(&san_der).unwrap();
+
+            assert!(Subject::Uri("spiffe://example.org/web").matches(&web));
+            assert!(!Subject::Uri("spiffe://example.org/db").matches(&web));
+            assert!(!domain_sub.matches(&web));
+
+            // A trust domain's ID matches any workload in the trust domain.
+            assert!(Subject::Uri("spiffe://example.org").matches(&web));
+            assert!(!Subject::Uri("spiffe://example.com").matches(&web));
+        }
+    }
+
+    #[test]
+    fn test_unix_seconds() {
+        for (dt, expected) in [
+            (asn1::DateTime::new(1970, 1, 1, 0, 0, 0).unwrap(), 0),
+            (asn1::DateTime::new(2000, 3, 1, 0, 0, 0).unwrap(), 951868800),
+            (
+                asn1::DateTime::new(2024, 6, 1, 12, 30, 15).unwrap(),
+                1717245015,
+            ),
+            (asn1::DateTime::new(1969, 12, 31, 23, 59, 59).unwrap(), -1),
+        ] {
+            assert_eq!(unix_seconds(&dt), expected);
+        }
     }
 
     #[test]
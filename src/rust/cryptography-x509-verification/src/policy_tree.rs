@@ -0,0 +1,490 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Certificate policy processing, per RFC 5280 6.1.
+//!
+//! Rather than materializing the `valid_policy_tree` (which can grow
+//! exponentially with policy mappings), each depth of the tree is kept as a
+//! set of nodes with at most one node per policy, per RFC 9618. The only
+//! property of a node's ancestry that the algorithm's outputs depend on is
+//! which authority-domain policies (the `valid_policy` of its ancestors
+//! that are children of `anyPolicy`) it descends from, so that set is
+//! tracked on each node instead of parent links.
+
+use std::collections::{HashMap, HashSet};
+
+use asn1::ObjectIdentifier;
+use cryptography_x509::extensions::{PolicyConstraints, PolicyInformation, PolicyMapping};
+use cryptography_x509::oid::{
+    ANY_POLICY_OID, CERTIFICATE_POLICIES_OID, INHIBIT_ANY_POLICY_OID, POLICY_CONSTRAINTS_OID,
+    POLICY_MAPPINGS_OID,
+};
+
+use crate::certificate::cert_is_self_issued;
+use crate::ops::{CryptoOps, VerificationCertificate};
+use crate::{FailureReason, ValidationError};
+
+/// The user-supplied inputs to certificate policy processing (RFC 5280 6.1.1).
+#[derive(Clone, Debug, Default)]
+pub struct PolicyTreeConstraints {
+    /// The `user-initial-policy-set`. `None` is equivalent to a set
+    /// containing only `anyPolicy`.
+    pub initial_policy_set: Option<HashSet<ObjectIdentifier>>,
+
+    /// The `initial-explicit-policy` indicator: when set, the path must be
+    /// valid for at least one policy in the initial policy set.
+    pub explicit_policy: bool,
+
+    /// The `initial-policy-mapping-inhibit` indicator: when set, policy
+    /// mappings are not permitted in the path.
+    pub policy_mapping_inhibit: bool,
+
+    /// The `initial-any-policy-inhibit` indicator: when set, `anyPolicy` is
+    /// not treated as matching other policies.
+    pub any_policy_inhibit: bool,
+}
+
+struct PolicyNode {
+    valid_policy: ObjectIdentifier,
+    expected_policy_set: Vec<ObjectIdentifier>,
+    authority_policies: Vec<ObjectIdentifier>,
+}
+
+/// A single depth of the `valid_policy_tree`. An empty level means the
+/// tree is NULL.
+#[derive(Default)]
+struct PolicyLevel {
+    nodes: Vec<PolicyNode>,
+    index: HashMap<ObjectIdentifier, usize>,
+}
+
+impl PolicyLevel {
+    fn any_policy() -> Self {
+        let mut level = PolicyLevel::default();
+        level.add(&ANY_POLICY_OID, &[ANY_POLICY_OID], &[ANY_POLICY_OID]);
+        level
+    }
+
+    fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn get(&self, policy: &ObjectIdentifier) -> Option<&PolicyNode> {
+        self.index.get(policy).map(|&i| &self.nodes[i])
+    }
+
+    /// Adds a node for `policy` descending from `authority_policies`, or
+    /// extends the existing node's ancestry if there already is one.
+    fn add(
+        &mut self,
+        policy: &ObjectIdentifier,
+        expected_policy_set: &[ObjectIdentifier],
+        authority_policies: &[ObjectIdentifier],
+    ) {
+        let i = match self.index.get(policy) {
+            Some(&i) => i,
+            None => {
+                self.nodes.push(PolicyNode {
+                    valid_policy: policy.clone(),
+                    expected_policy_set: expected_policy_set.to_vec(),
+                    authority_policies: vec![],
+                });
+                self.index.insert(policy.clone(), self.nodes.len() - 1);
+                self.nodes.len() - 1
+            }
+        };
+
+        let node = &mut self.nodes[i];
+        for p in authority_policies {
+            if !node.authority_policies.contains(p) {
+                node.authority_policies.push(p.clone());
+            }
+        }
+    }
+
+    fn retain(&mut self, f: impl FnMut(&mut PolicyNode) -> bool) {
+        self.nodes.retain_mut(f);
+        self.index = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.valid_policy.clone(), i))
+            .collect();
+    }
+}
+
+/// Runs certificate policy processing over `path`, the certificates of a
+/// chain below its trust anchor, in leaf-first order.
+///
+/// On success, returns the authority-constrained policy set: the policies,
+/// in the trust anchor's domain, that the chain is valid for. This may
+/// contain `anyPolicy`.
+pub(crate) fn process<B: CryptoOps>(
+    constraints: &PolicyTreeConstraints,
+    path: &[VerificationCertificate<'_, B>],
+) -> Result<Vec<ObjectIdentifier>, ValidationError> {
+    // The trust anchor is an input to path validation, and is not itself
+    // processed.
+    let n = path.len();
+    let initial = |inhibit: bool| if inhibit { 0 } else { n as u64 + 1 };
+
+    // RFC 5280 6.1.2: Initialization.
+    let mut level = PolicyLevel::any_policy();
+    let mut explicit_policy = initial(constraints.explicit_policy);
+    let mut inhibit_any_policy = initial(constraints.any_policy_inhibit);
+    let mut policy_mapping = initial(constraints.policy_mapping_inhibit);
+
+    for (i, cert) in path.iter().rev().enumerate() {
+        let cert = cert.certificate();
+        let is_leaf = i + 1 == n;
+        let self_issued = cert_is_self_issued(cert);
+        let extensions = cert.extensions()?;
+
+        // 6.1.3 (d) and (e): extend the tree with this certificate's
+        // policies, or prune it entirely if it has none.
+        level = match extensions.get_extension(&CERTIFICATE_POLICIES_OID) {
+            Some(ext) if !level.is_empty() => next_level(
+                &level,
+                ext.value::<asn1::SequenceOf<'_, PolicyInformation<'_>>>()?,
+                inhibit_any_policy > 0 || (!is_leaf && self_issued),
+            ),
+            _ => PolicyLevel::default(),
+        };
+
+        // 6.1.3 (f)
+        if explicit_policy == 0 && level.is_empty() {
+            return Err(ValidationError::Rejected(
+                FailureReason::CertificatePolicyViolation,
+                "certificate policy processing failed: no valid policy".to_string(),
+            ));
+        }
+
+        let policy_constraints = match extensions.get_extension(&POLICY_CONSTRAINTS_OID) {
+            Some(ext) => Some(ext.value::<PolicyConstraints>()?),
+            None => None,
+        };
+
+        if is_leaf {
+            // 6.1.5 (a) and (b)
+            explicit_policy = explicit_policy.saturating_sub(1);
+            if policy_constraints.map_or(false, |pc| pc.require_explicit_policy == Some(0)) {
+                explicit_policy = 0;
+            }
+            break;
+        }
+
+        // 6.1.4 (a) and (b)
+        if let Some(ext) = extensions.get_extension(&POLICY_MAPPINGS_OID) {
+            apply_policy_mappings(
+                &mut level,
+                ext.value::<asn1::SequenceOf<'_, PolicyMapping>>()?,
+                policy_mapping > 0,
+            )?;
+        }
+
+        // 6.1.4 (h)
+        if !self_issued {
+            explicit_policy = explicit_policy.saturating_sub(1);
+            policy_mapping = policy_mapping.saturating_sub(1);
+            inhibit_any_policy = inhibit_any_policy.saturating_sub(1);
+        }
+
+        // 6.1.4 (i)
+        if let Some(pc) = policy_constraints {
+            if let Some(require_explicit_policy) = pc.require_explicit_policy {
+                explicit_policy = explicit_policy.min(require_explicit_policy);
+            }
+            if let Some(inhibit_policy_mapping) = pc.inhibit_policy_mapping {
+                policy_mapping = policy_mapping.min(inhibit_policy_mapping);
+            }
+        }
+
+        // 6.1.4 (j)
+        if let Some(ext) = extensions.get_extension(&INHIBIT_ANY_POLICY_OID) {
+            inhibit_any_policy = inhibit_any_policy.min(ext.value::<u64>()?);
+        }
+    }
+
+    // 6.1.5 (g): intersect the tree with the user-initial-policy-set.
+    if let Some(initial_policy_set) = constraints
+        .initial_policy_set
+        .as_ref()
+        .filter(|s| !s.contains(&ANY_POLICY_OID))
+    {
+        intersect(&mut level, initial_policy_set);
+    }
+
+    if explicit_policy == 0 && level.is_empty() {
+        return Err(ValidationError::Rejected(
+            FailureReason::CertificatePolicyViolation,
+            "certificate policy processing failed: no acceptable policy".to_string(),
+        ));
+    }
+
+    let mut policies = level
+        .nodes
+        .iter()
+        .flat_map(|node| node.authority_policies.iter())
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    // Return the policies in a stable order.
+    policies.sort_by_cached_key(|p| asn1::write_single(p).unwrap_or_default());
+    Ok(policies)
+}
+
+/// Computes depth `i` of the tree from depth `i - 1` (RFC 5280 6.1.3 (d)).
+fn next_level<'a>(
+    prev: &PolicyLevel,
+    policies: asn1::SequenceOf<'a, PolicyInformation<'a>>,
+    any_policy_permitted: bool,
+) -> PolicyLevel {
+    let mut next = PolicyLevel::default();
+
+    let mut expecting: HashMap<&ObjectIdentifier, Vec<&PolicyNode>> = HashMap::new();
+    for node in &prev.nodes {
+        for p in &node.expected_policy_set {
+            expecting.entry(p).or_default().push(node);
+        }
+    }
+
+    let mut asserts_any_policy = false;
+    for info in policies {
+        let policy = info.policy_identifier;
+        if policy == ANY_POLICY_OID {
+            asserts_any_policy = true;
+            continue;
+        }
+
+        match expecting.get(&policy) {
+            // (d)(1)(i): children of every node that expects this policy.
+            Some(parents) => {
+                for parent in parents {
+                    next.add(
+                        &policy,
+                        std::slice::from_ref(&policy),
+                        &parent.authority_policies,
+                    );
+                }
+            }
+            // (d)(1)(ii): otherwise, a child of the anyPolicy node.
+            None => {
+                if prev.get(&ANY_POLICY_OID).is_some() {
+                    next.add(
+                        &policy,
+                        std::slice::from_ref(&policy),
+                        std::slice::from_ref(&policy),
+                    );
+                }
+            }
+        }
+    }
+
+    // (d)(2): if the certificate asserts anyPolicy, every expected policy
+    // that didn't appear in the certificate is carried forward.
+    if asserts_any_policy && any_policy_permitted {
+        for parent in &prev.nodes {
+            for p in &parent.expected_policy_set {
+                next.add(p, std::slice::from_ref(p), &parent.authority_policies);
+            }
+        }
+    }
+
+    next
+}
+
+/// Applies a CA certificate's policy mappings to its depth of the tree
+/// (RFC 5280 6.1.4 (a) and (b)).
+fn apply_policy_mappings(
+    level: &mut PolicyLevel,
+    mappings: asn1::SequenceOf<'_, PolicyMapping>,
+    mapping_permitted: bool,
+) -> Result<(), ValidationError> {
+    let mut mapped: Vec<(ObjectIdentifier, Vec<ObjectIdentifier>)> = vec![];
+    for mapping in mappings {
+        if mapping.issuer_domain_policy == ANY_POLICY_OID
+            || mapping.subject_domain_policy == ANY_POLICY_OID
+        {
+            return Err(ValidationError::Rejected(
+                FailureReason::CertificatePolicyViolation,
+                "policy mappings must not map to or from anyPolicy".to_string(),
+            ));
+        }
+
+        match mapped
+            .iter_mut()
+            .find(|(issuer_policy, _)| *issuer_policy == mapping.issuer_domain_policy)
+        {
+            Some((_, subject_policies)) => {
+                if !subject_policies.contains(&mapping.subject_domain_policy) {
+                    subject_policies.push(mapping.subject_domain_policy);
+                }
+            }
+            None => mapped.push((
+                mapping.issuer_domain_policy,
+                vec![mapping.subject_domain_policy],
+            )),
+        }
+    }
+
+    if !mapping_permitted {
+        level.retain(|node| {
+            !mapped
+                .iter()
+                .any(|(issuer_policy, _)| *issuer_policy == node.valid_policy)
+        });
+        return Ok(());
+    }
+
+    let has_any_policy = level.get(&ANY_POLICY_OID).is_some();
+    for (issuer_policy, subject_policies) in mapped {
+        match level.index.get(&issuer_policy) {
+            Some(&i) => level.nodes[i].expected_policy_set = subject_policies,
+            // A sibling of the anyPolicy node, i.e. a child of the previous
+            // depth's anyPolicy node.
+            None if has_any_policy => level.add(
+                &issuer_policy,
+                &subject_policies,
+                std::slice::from_ref(&issuer_policy),
+            ),
+            None => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Restricts the final depth of the tree to the user-initial-policy-set
+/// (RFC 5280 6.1.5 (g)(iii)).
+fn intersect(level: &mut PolicyLevel, initial_policy_set: &HashSet<ObjectIdentifier>) {
+    let any_policy = level.get(&ANY_POLICY_OID).is_some();
+
+    level.retain(|node| {
+        if node.valid_policy == ANY_POLICY_OID {
+            return false;
+        }
+        node.authority_policies
+            .retain(|p| initial_policy_set.contains(p));
+        !node.authority_policies.is_empty()
+    });
+
+    if any_policy {
+        let present = level
+            .nodes
+            .iter()
+            .flat_map(|node| node.authority_policies.iter())
+            .cloned()
+            .collect::<HashSet<_>>();
+        for p in initial_policy_set {
+            if !present.contains(p) {
+                level.add(p, std::slice::from_ref(p), std::slice::from_ref(p));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use asn1::ObjectIdentifier;
+    use cryptography_x509::extensions::PolicyMapping;
+    use cryptography_x509::oid::ANY_POLICY_OID;
+
+    use super::{apply_policy_mappings, intersect, PolicyLevel};
+
+    const POLICY_1: ObjectIdentifier = asn1::oid!(1, 3, 6, 1, 4, 1, 11129, 2, 5, 1);
+    const POLICY_2: ObjectIdentifier = asn1::oid!(1, 3, 6, 1, 4, 1, 11129, 2, 5, 2);
+    const POLICY_3: ObjectIdentifier = asn1::oid!(1, 3, 6, 1, 4, 1, 11129, 2, 5, 3);
+
+    fn mappings_der(mappings: &[(ObjectIdentifier, ObjectIdentifier)]) -> Vec<u8> {
+        asn1::write_single(&asn1::SequenceOfWriter::new(
+            mappings
+                .iter()
+                .map(|(issuer, subject)| PolicyMapping {
+                    issuer_domain_policy: issuer.clone(),
+                    subject_domain_policy: subject.clone(),
+                })
+                .collect::<Vec<_>>(),
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_policy_level_add_merges_ancestry() {
+        let mut level = PolicyLevel::default();
+        level.add(&POLICY_1, &[POLICY_1], &[POLICY_1]);
+        level.add(&POLICY_1, &[POLICY_1], &[POLICY_2]);
+        level.add(&POLICY_1, &[POLICY_1], &[POLICY_2]);
+
+        assert_eq!(level.nodes.len(), 1);
+        assert_eq!(
+            level.get(&POLICY_1).unwrap().authority_policies,
+            vec![POLICY_1, POLICY_2]
+        );
+
+        level.add(&POLICY_2, &[POLICY_2], &[POLICY_2]);
+        level.retain(|node| node.valid_policy != POLICY_1);
+        assert!(level.get(&POLICY_1).is_none());
+        assert_eq!(level.get(&POLICY_2).unwrap().valid_policy, POLICY_2);
+    }
+
+    #[test]
+    fn test_intersect() {
+        let initial_policy_set = HashSet::from([POLICY_1, POLICY_3]);
+
+        let mut level = PolicyLevel::default();
+        level.add(&POLICY_1, &[POLICY_1], &[POLICY_1, POLICY_2]);
+        level.add(&POLICY_2, &[POLICY_2], &[POLICY_2]);
+        intersect(&mut level, &initial_policy_set);
+        assert_eq!(level.nodes.len(), 1);
+        assert_eq!(
+            level.get(&POLICY_1).unwrap().authority_policies,
+            vec![POLICY_1]
+        );
+
+        // anyPolicy stands in for every policy in the initial set that isn't
+        // otherwise present.
+        let mut level = PolicyLevel::any_policy();
+        level.add(&POLICY_1, &[POLICY_1], &[POLICY_1]);
+        intersect(&mut level, &initial_policy_set);
+        assert!(level.get(&ANY_POLICY_OID).is_none());
+        assert!(level.get(&POLICY_1).is_some());
+        assert!(level.get(&POLICY_3).is_some());
+        assert_eq!(level.nodes.len(), 2);
+    }
+
+    #[test]
+    fn test_apply_policy_mappings() {
+        let der = mappings_der(&[(POLICY_1, POLICY_2), (POLICY_1, POLICY_3)]);
+
+        let mut level = PolicyLevel::default();
+        level.add(&POLICY_1, &[POLICY_1], &[POLICY_1]);
+        apply_policy_mappings(&mut level, asn1::parse_single(&der).unwrap(), true).unwrap();
+        assert_eq!(
+            level.get(&POLICY_1).unwrap().expected_policy_set,
+            vec![POLICY_2, POLICY_3]
+        );
+
+        let mut level = PolicyLevel::default();
+        level.add(&POLICY_1, &[POLICY_1], &[POLICY_1]);
+        apply_policy_mappings(&mut level, asn1::parse_single(&der).unwrap(), false).unwrap();
+        assert!(level.is_empty());
+
+        // Mapped policies become siblings of anyPolicy.
+        let mut level = PolicyLevel::any_policy();
+        apply_policy_mappings(&mut level, asn1::parse_single(&der).unwrap(), true).unwrap();
+        assert_eq!(
+            level.get(&POLICY_1).unwrap().expected_policy_set,
+            vec![POLICY_2, POLICY_3]
+        );
+
+        let der = mappings_der(&[(ANY_POLICY_OID, POLICY_1)]);
+        let mut level = PolicyLevel::any_policy();
+        assert!(
+            apply_policy_mappings(&mut level, asn1::parse_single(&der).unwrap(), true).is_err()
+        );
+    }
+}
@@ -0,0 +1,657 @@
+// This file is dual licensed under the terms of the Apache License, Version
+// 2.0, and the BSD License. See the LICENSE file in the root of this repository
+// for complete details.
+
+//! Revocation checking during path validation.
+
+use cryptography_x509::certificate::Certificate;
+use cryptography_x509::common::{
+    AlgorithmIdentifier, AlgorithmParameters, SubjectPublicKeyInfo, WithTlv,
+};
+use cryptography_x509::crl::{CertificateRevocationList, IssuingDistributionPoint};
+use cryptography_x509::extensions::{
+    BasicConstraints, DistributionPoint, DistributionPointName, Extensions, KeyUsage,
+};
+use cryptography_x509::name::Name;
+use cryptography_x509::ocsp_req::CertID;
+use cryptography_x509::ocsp_resp::{BasicOCSPResponse, CertStatus, ResponderId};
+use cryptography_x509::oid::{
+    BASIC_CONSTRAINTS_OID, CRL_DISTRIBUTION_POINTS_OID, DELTA_CRL_INDICATOR_OID,
+    EKU_OCSP_SIGNING_OID, EXTENDED_KEY_USAGE_OID, ISSUING_DISTRIBUTION_POINT_OID, KEY_USAGE_OID,
+    TLS_FEATURE_OID,
+};
+
+use crate::ops::{CryptoOps, VerificationCertificate};
+use crate::policy::{unix_seconds, Policy};
+use crate::trust_store::KeyAnchor;
+use crate::{Budget, FailureReason, ValidationError};
+
+/// Describes how revocation checking behaves when no authoritative
+/// revocation information is available for a certificate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RevocationMode {
+    /// Certificates without applicable revocation information are
+    /// accepted.
+    SoftFail,
+    /// Certificates without applicable revocation information are
+    /// rejected.
+    HardFail,
+}
+
+/// A `RevocationPolicy` describes the revocation data consulted during
+/// path validation.
+pub struct RevocationPolicy<'a> {
+    /// The CRLs to check certificates against. Each CRL is only used for
+    /// certificates whose issuer signed it.
+    pub crls: Vec<CertificateRevocationList<'a>>,
+
+    /// The behavior when neither an applicable CRL nor a stapled OCSP
+    /// response is found for a certificate.
+    pub mode: RevocationMode,
+}
+
+/// The issuer of a certificate whose revocation status is being checked.
+pub(crate) enum Issuer<'a, 'chain, B: CryptoOps> {
+    /// An issuing certificate, with its extensions.
+    Certificate(
+        &'a VerificationCertificate<'chain, B>,
+        &'a Extensions<'chain>,
+    ),
+    /// A trust anchor without a certificate.
+    KeyAnchor(&'a KeyAnchor<'chain, B>),
+}
+
+impl<'chain, B: CryptoOps> Issuer<'_, 'chain, B> {
+    fn name(&self) -> &Name<'chain> {
+        match self {
+            Issuer::Certificate(cert, _) => &cert.certificate().tbs_cert.subject,
+            Issuer::KeyAnchor(anchor) => anchor.name(),
+        }
+    }
+
+    fn spki(&self) -> &WithTlv<'chain, SubjectPublicKeyInfo<'chain>> {
+        match self {
+            Issuer::Certificate(cert, _) => &cert.certificate().tbs_cert.spki,
+            Issuer::KeyAnchor(anchor) => anchor.spki(),
+        }
+    }
+
+    fn public_key(&self, ops: &B) -> Result<&B::Key, B::Err> {
+        match self {
+            Issuer::Certificate(cert, _) => cert.public_key(ops),
+            Issuer::KeyAnchor(anchor) => anchor.public_key(ops),
+        }
+    }
+
+    /// Returns the issuer's extensions, which a trust anchor without a
+    /// certificate doesn't have.
+    fn extensions(&self) -> Option<&Extensions<'chain>> {
+        match self {
+            Issuer::Certificate(_, extensions) => Some(extensions),
+            Issuer::KeyAnchor(_) => None,
+        }
+    }
+}
+
+impl<'a> RevocationPolicy<'a> {
+    pub fn new(crls: Vec<CertificateRevocationList<'a>>, mode: RevocationMode) -> Self {
+        Self { crls, mode }
+    }
+
+    /// Checks `child`, as issued by `issuer`, against the configured CRLs.
+    /// Each CRL signature that is verified is charged to `budget`.
+    ///
+    /// Returns whether any applicable CRL was found.
+    fn check_crls<B: CryptoOps>(
+        &self,
+        policy: &Policy<'_, B>,
+        child: &VerificationCertificate<'_, B>,
+        child_extensions: &Extensions<'_>,
+        issuer: &Issuer<'_, '_, B>,
+        budget: &mut Budget,
+    ) -> Result<bool, ValidationError> {
+        let mut covered = false;
+        for crl in &self.crls {
+            if !crl_is_applicable(
+                policy,
+                crl,
+                child.certificate(),
+                child_extensions,
+                issuer,
+                budget,
+            )? {
+                continue;
+            }
+            covered = true;
+
+            if crl_lists_serial(crl, child.certificate()) {
+                return Err(ValidationError::Revoked(
+                    "certificate is listed as revoked in its issuer's CRL".to_string(),
+                ));
+            }
+        }
+
+        Ok(covered)
+    }
+}
+
+/// Checks the revocation status of `child`, as issued by `issuer`, against
+/// any stapled OCSP responses and the policy's revocation data. The
+/// signatures verified along the way are charged to `budget`, like those of
+/// the chain itself.
+///
+/// As a precondition, `issuer` must already have been checked as a valid
+/// issuer for `child` under `policy`. A trust anchor without a certificate
+/// is matched to CRLs and OCSP responses by its name and key, as an issuing
+/// certificate would be.
+pub(crate) fn permits<B: CryptoOps>(
+    policy: &Policy<'_, B>,
+    ocsp_responses: &[&BasicOCSPResponse<'_>],
+    child: &VerificationCertificate<'_, B>,
+    child_extensions: &Extensions<'_>,
+    issuer: &Issuer<'_, '_, B>,
+    budget: &mut Budget,
+) -> Result<(), ValidationError> {
+    let mut covered = check_ocsp_responses(policy, ocsp_responses, child, issuer, budget)?;
+
+    if policy.must_staple && !covered && asserts_status_request(child_extensions)? {
+        return Err(ValidationError::Rejected(
+            FailureReason::MustStapleViolation,
+            "certificate requires a stapled OCSP response, but none was supplied".to_string(),
+        ));
+    }
+
+    if let Some(revocation) = &policy.revocation {
+        covered |= revocation.check_crls(policy, child, child_extensions, issuer, budget)?;
+
+        if !covered && revocation.mode == RevocationMode::HardFail {
+            return Err(ValidationError::Rejected(
+                FailureReason::RevocationCheckFailed,
+                "no applicable revocation information found for certificate".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// The TLS feature (RFC 7633) for the TLS `status_request` extension, i.e.
+/// OCSP stapling.
+const TLS_FEATURE_STATUS_REQUEST: u64 = 5;
+
+/// Returns true if the certificate with `extensions` asserts the
+/// `status_request` TLS feature, i.e. is "must-staple".
+fn asserts_status_request(extensions: &Extensions<'_>) -> Result<bool, ValidationError> {
+    let Some(ext) = extensions.get_extension(&TLS_FEATURE_OID) else {
+        return Ok(false);
+    };
+    let mut features = ext.value::<asn1::SequenceOf<'_, u64>>()?;
+    Ok(features.any(|feature| feature == TLS_FEATURE_STATUS_REQUEST))
+}
+
+/// Checks `child`, as issued by `issuer`, against every stapled OCSP
+/// response that refers to it.
+///
+/// Returns whether any response for `child` was found. Responses for
+/// `child` that are not valid (e.g. because they're stale or not signed by
+/// an authorized responder) are an error, as are responses that report
+/// `child` as revoked or unknown.
+fn check_ocsp_responses<B: CryptoOps>(
+    policy: &Policy<'_, B>,
+    ocsp_responses: &[&BasicOCSPResponse<'_>],
+    child: &VerificationCertificate<'_, B>,
+    issuer: &Issuer<'_, '_, B>,
+    budget: &mut Budget,
+) -> Result<bool, ValidationError> {
+    let mut covered = false;
+    for response in ocsp_responses {
+        for single in response.tbs_response_data.responses.unwrap_read().clone() {
+            if !cert_id_matches(policy, &single.cert_id, child.certificate(), issuer) {
+                continue;
+            }
+
+            if !ocsp_response_is_valid(policy, response, issuer, budget)? {
+                return Err(ValidationError::Rejected(
+                    FailureReason::RevocationCheckFailed,
+                    "stapled OCSP response is not signed by an authorized responder".to_string(),
+                ));
+            }
+
+            // RFC 6960 3.2 (4) and (5): the response must be current,
+            // modulo the permitted clock skew.
+            let now = unix_seconds(&policy.validation_time);
+            let skew = i64::try_from(policy.ocsp_clock_skew.as_secs()).unwrap_or(i64::MAX);
+            if unix_seconds(single.this_update.as_datetime()).saturating_sub(skew) > now
+                || single.next_update.as_ref().map_or(false, |nu| {
+                    unix_seconds(nu.as_datetime()).saturating_add(skew) < now
+                })
+            {
+                return Err(ValidationError::Rejected(
+                    FailureReason::RevocationCheckFailed,
+                    "stapled OCSP response is not current".to_string(),
+                ));
+            }
+
+            match single.cert_status {
+                CertStatus::Good(()) => covered = true,
+                CertStatus::Revoked(_) => {
+                    return Err(ValidationError::Revoked(
+                        "certificate is revoked according to its stapled OCSP response".to_string(),
+                    ))
+                }
+                CertStatus::Unknown(()) => {
+                    return Err(ValidationError::Rejected(
+                        FailureReason::RevocationCheckFailed,
+                        "stapled OCSP response reports an unknown certificate status".to_string(),
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(covered)
+}
+
+/// Returns true if `cert_id` identifies `child`, as issued by `issuer`.
+fn cert_id_matches<B: CryptoOps>(
+    policy: &Policy<'_, B>,
+    cert_id: &CertID<'_>,
+    child: &Certificate<'_>,
+    issuer: &Issuer<'_, '_, B>,
+) -> bool {
+    if cert_id.serial_number.as_bytes() != child.tbs_cert.serial.as_bytes() {
+        return false;
+    }
+
+    let issuer_name_der = match asn1::write_single(&child.tbs_cert.issuer) {
+        Ok(der) => der,
+        Err(_) => return false,
+    };
+    let issuer_key = issuer.spki().subject_public_key.as_bytes();
+
+    policy
+        .ops
+        .digest(&cert_id.hash_algorithm, &issuer_name_der)
+        .map_or(false, |h| h == cert_id.issuer_name_hash)
+        && policy
+            .ops
+            .digest(&cert_id.hash_algorithm, issuer_key)
+            .map_or(false, |h| h == cert_id.issuer_key_hash)
+}
+
+/// Returns true if `response` is signed by `issuer`, or by a responder that
+/// `issuer` has delegated OCSP signing to (RFC 6960 4.2.2.2). Each signature
+/// verified is charged to `budget`.
+fn ocsp_response_is_valid<B: CryptoOps>(
+    policy: &Policy<'_, B>,
+    response: &BasicOCSPResponse<'_>,
+    issuer: &Issuer<'_, '_, B>,
+    budget: &mut Budget,
+) -> Result<bool, ValidationError> {
+    if !policy
+        .permitted_signature_algorithms
+        .contains(&response.signature_algorithm)
+    {
+        return Ok(false);
+    }
+    let tbs_der = match asn1::write_single(&response.tbs_response_data) {
+        Ok(der) => der,
+        Err(_) => return Ok(false),
+    };
+    let signed_by = |key: &B::Key, budget: &mut Budget| {
+        budget.signature_check()?;
+        Ok(policy
+            .ops
+            .verify_signature(
+                key,
+                &response.signature_algorithm,
+                response.signature.as_bytes(),
+                &tbs_der,
+            )
+            .is_ok())
+    };
+
+    let responder_id = &response.tbs_response_data.responder_id;
+    if responder_id_matches(policy, responder_id, issuer.name(), issuer.spki()) {
+        return match issuer.public_key(&policy.ops) {
+            Ok(key) => signed_by(key, budget),
+            Err(_) => Ok(false),
+        };
+    }
+
+    let certs = match &response.certs {
+        Some(certs) => certs.unwrap_read().clone(),
+        None => return Ok(false),
+    };
+    for responder in certs {
+        if responder_id_matches(
+            policy,
+            responder_id,
+            &responder.tbs_cert.subject,
+            &responder.tbs_cert.spki,
+        ) && is_delegated_responder(policy, &responder, issuer, budget)?
+        {
+            return match policy.ops.public_key(&responder) {
+                Ok(key) => signed_by(&key, budget),
+                Err(_) => Ok(false),
+            };
+        }
+    }
+
+    Ok(false)
+}
+
+/// Returns true if `responder_id` identifies the responder with `name` and
+/// `spki`.
+fn responder_id_matches<B: CryptoOps>(
+    policy: &Policy<'_, B>,
+    responder_id: &ResponderId<'_>,
+    name: &Name<'_>,
+    spki: &SubjectPublicKeyInfo<'_>,
+) -> bool {
+    match responder_id {
+        ResponderId::ByName(responder_name) => responder_name == name,
+        // RFC 6960 4.2.1: the key hash is always SHA-1.
+        ResponderId::ByKey(key_hash) => policy
+            .ops
+            .digest(
+                &AlgorithmIdentifier {
+                    oid: asn1::DefinedByMarker::marker(),
+                    params: AlgorithmParameters::Sha1(Some(())),
+                },
+                spki.subject_public_key.as_bytes(),
+            )
+            .map_or(false, |h| h == *key_hash),
+    }
+}
+
+/// Returns true if `responder` is a valid delegated OCSP responder for
+/// certificates issued by `issuer`. Checking its signature is charged to
+/// `budget`.
+fn is_delegated_responder<B: CryptoOps>(
+    policy: &Policy<'_, B>,
+    responder: &Certificate<'_>,
+    issuer: &Issuer<'_, '_, B>,
+    budget: &mut Budget,
+) -> Result<bool, ValidationError> {
+    if &responder.tbs_cert.issuer != issuer.name()
+        || !policy
+            .permitted_signature_algorithms
+            .contains(&responder.signature_alg)
+        || !policy.permits_public_key_algorithm(&responder.tbs_cert.spki.algorithm)
+    {
+        return Ok(false);
+    }
+
+    let validity = &responder.tbs_cert.validity;
+    if &policy.validation_time < validity.not_before.as_datetime()
+        || &policy.validation_time > validity.not_after.as_datetime()
+    {
+        return Ok(false);
+    }
+
+    let has_ocsp_signing = match responder.extensions() {
+        Ok(extensions) => match extensions.get_extension(&EXTENDED_KEY_USAGE_OID) {
+            Some(eku) => eku
+                .value::<asn1::SequenceOf<'_, asn1::ObjectIdentifier>>()
+                .map_or(false, |mut ekus| {
+                    ekus.any(|eku| eku == EKU_OCSP_SIGNING_OID)
+                }),
+            None => false,
+        },
+        Err(_) => false,
+    };
+    if !has_ocsp_signing {
+        return Ok(false);
+    }
+
+    let Ok(key) = issuer.public_key(&policy.ops) else {
+        return Ok(false);
+    };
+    budget.signature_check()?;
+    Ok(policy.ops.verify_signed_by(responder, key).is_ok())
+}
+
+/// Returns true if `crl` is an authoritative, complete CRL for `child`,
+/// i.e. it was issued and signed by `issuer`, it is current at the
+/// policy's validation time, and its scope covers `child`. Checking its
+/// signature is charged to `budget`.
+fn crl_is_applicable<B: CryptoOps>(
+    policy: &Policy<'_, B>,
+    crl: &CertificateRevocationList<'_>,
+    child: &Certificate<'_>,
+    child_extensions: &Extensions<'_>,
+    issuer: &Issuer<'_, '_, B>,
+    budget: &mut Budget,
+) -> Result<bool, ValidationError> {
+    let tbs = &crl.tbs_cert_list;
+
+    // RFC 5280 6.3.3 (b): the CRL issuer must match the certificate issuer.
+    // Indirect CRLs are not supported.
+    if tbs.issuer.unwrap_read() != child.issuer() {
+        return Ok(false);
+    }
+
+    // RFC 5280 6.3.3 (a): the CRL must be current.
+    if &policy.validation_time < tbs.this_update.as_datetime()
+        || tbs
+            .next_update
+            .as_ref()
+            .map_or(false, |nu| &policy.validation_time > nu.as_datetime())
+    {
+        return Ok(false);
+    }
+
+    let crl_extensions = Extensions::from_raw_extensions(tbs.raw_crl_extensions.as_ref())?;
+
+    // Delta CRLs are not complete on their own, and we don't know how to
+    // process any other critical CRL extensions.
+    for ext in crl_extensions.iter() {
+        if ext.extn_id == DELTA_CRL_INDICATOR_OID {
+            return Ok(false);
+        }
+        if ext.critical && ext.extn_id != ISSUING_DISTRIBUTION_POINT_OID {
+            return Ok(false);
+        }
+    }
+
+    // RFC 5280 6.3.3 (b)(2): check the CRL's scope against the certificate.
+    if let Some(idp) = crl_extensions.get_extension(&ISSUING_DISTRIBUTION_POINT_OID) {
+        let idp: IssuingDistributionPoint<'_> = idp.value()?;
+        if !idp_covers(&idp, child_extensions)? {
+            return Ok(false);
+        }
+    }
+
+    // RFC 5280 6.3.3 (f): the issuer must be permitted to sign CRLs. A trust
+    // anchor without a certificate has no key usage to restrict it.
+    if let Some(ku) = issuer
+        .extensions()
+        .and_then(|extensions| extensions.get_extension(&KEY_USAGE_OID))
+    {
+        let ku: KeyUsage<'_> = ku.value()?;
+        if !ku.crl_sign() {
+            return Ok(false);
+        }
+    }
+
+    // RFC 5280 6.3.3 (g): the CRL's signature must verify with the issuer's key.
+    if !policy
+        .permitted_signature_algorithms
+        .contains(&crl.signature_algorithm)
+    {
+        return Ok(false);
+    }
+    let pk = issuer
+        .public_key(&policy.ops)
+        .map_err(|_| ValidationError::Other("issuer has malformed public key".to_string()))?;
+    let tbs_der = asn1::write_single(tbs)
+        .map_err(|_| ValidationError::Other("unable to encode CRL".to_string()))?;
+    budget.signature_check()?;
+    Ok(policy
+        .ops
+        .verify_signature(
+            pk,
+            &crl.signature_algorithm,
+            crl.signature_value.as_bytes(),
+            &tbs_der,
+        )
+        .is_ok())
+}
+
+/// Returns true if the given `IssuingDistributionPoint` scopes a CRL
+/// such that it covers a certificate with the given extensions.
+fn idp_covers(
+    idp: &IssuingDistributionPoint<'_>,
+    child_extensions: &Extensions<'_>,
+) -> Result<bool, ValidationError> {
+    // CRLs that only cover some reasons or that are indirect cannot be
+    // used on their own to establish a certificate's status.
+    if idp.only_some_reasons.is_some() || idp.indirect_crl || idp.only_contains_attribute_certs {
+        return Ok(false);
+    }
+
+    let child_is_ca = match child_extensions.get_extension(&BASIC_CONSTRAINTS_OID) {
+        Some(bc) => bc.value::<BasicConstraints>()?.ca,
+        None => false,
+    };
+    if (idp.only_contains_user_certs && child_is_ca) || (idp.only_contains_ca_certs && !child_is_ca)
+    {
+        return Ok(false);
+    }
+
+    // If the CRL is partitioned by distribution point, the certificate must
+    // list a matching distribution point of its own.
+    let idp_name = match &idp.distribution_point {
+        Some(name) => name,
+        None => return Ok(true),
+    };
+    let child_dps = match child_extensions.get_extension(&CRL_DISTRIBUTION_POINTS_OID) {
+        Some(ext) => ext.value::<asn1::SequenceOf<'_, DistributionPoint<'_>>>()?,
+        None => return Ok(false),
+    };
+    for dp in child_dps {
+        if let Some(dp_name) = &dp.distribution_point {
+            if distribution_point_names_match(idp_name, dp_name)? {
+                return Ok(true);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+fn distribution_point_names_match(
+    a: &DistributionPointName<'_>,
+    b: &DistributionPointName<'_>,
+) -> Result<bool, ValidationError> {
+    match (a, b) {
+        // RFC 5280 6.3.3 (b)(2)(i): at least one name must match.
+        (DistributionPointName::FullName(a), DistributionPointName::FullName(b)) => {
+            for a_name in a.unwrap_read().clone() {
+                let a_der = asn1::write_single(&a_name)
+                    .map_err(|_| ValidationError::Other("unable to encode name".to_string()))?;
+                for b_name in b.unwrap_read().clone() {
+                    let b_der = asn1::write_single(&b_name)
+                        .map_err(|_| ValidationError::Other("unable to encode name".to_string()))?;
+                    if a_der == b_der {
+                        return Ok(true);
+                    }
+                }
+            }
+            Ok(false)
+        }
+        (
+            DistributionPointName::NameRelativeToCRLIssuer(a),
+            DistributionPointName::NameRelativeToCRLIssuer(b),
+        ) => Ok(a.unwrap_read() == b.unwrap_read()),
+        _ => Ok(false),
+    }
+}
+
+fn crl_lists_serial(crl: &CertificateRevocationList<'_>, cert: &Certificate<'_>) -> bool {
+    let serial = cert.tbs_cert.serial.as_bytes();
+    crl.tbs_cert_list
+        .revoked_certificates
+        .as_ref()
+        .map_or(false, |revoked| {
+            revoked
+                .unwrap_read()
+                .clone()
+                .any(|rc| rc.user_certificate.as_bytes() == serial)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use cryptography_x509::common::Asn1ReadableOrWritable;
+    use cryptography_x509::crl::IssuingDistributionPoint;
+    use cryptography_x509::extensions::{DistributionPointName, Extensions};
+    use cryptography_x509::name::{GeneralName, UnvalidatedIA5String};
+
+    use super::{distribution_point_names_match, idp_covers};
+
+    fn full_name_der(uri: &str) -> Vec<u8> {
+        let name = DistributionPointName::FullName(Asn1ReadableOrWritable::new_write(
+            asn1::SequenceOfWriter::new(vec![GeneralName::UniformResourceIdentifier(
+                UnvalidatedIA5String(uri),
+            )]),
+        ));
+        asn1::write_single(&name).unwrap()
+    }
+
+    fn idp_der(
+        uri: Option<&str>,
+        only_contains_user_certs: bool,
+        only_contains_ca_certs: bool,
+    ) -> Vec<u8> {
+        let name_der = uri.map(full_name_der);
+        let idp = IssuingDistributionPoint {
+            distribution_point: name_der
+                .as_ref()
+                .map(|der| asn1::parse_single(der).unwrap()),
+            only_contains_user_certs,
+            only_contains_ca_certs,
+            only_some_reasons: None,
+            indirect_crl: false,
+            only_contains_attribute_certs: false,
+        };
+        asn1::write_single(&idp).unwrap()
+    }
+
+    #[test]
+    fn test_distribution_point_names_match() {
+        let a_der = full_name_der("http://example.com/a.crl");
+        let b_der = full_name_der("http://example.com/b.crl");
+        let a = asn1::parse_single::<DistributionPointName<'_>>(&a_der).unwrap();
+        let a_again = asn1::parse_single::<DistributionPointName<'_>>(&a_der).unwrap();
+        let b = asn1::parse_single::<DistributionPointName<'_>>(&b_der).unwrap();
+
+        assert!(distribution_point_names_match(&a, &a_again).unwrap());
+        assert!(!distribution_point_names_match(&a, &b).unwrap());
+    }
+
+    #[test]
+    fn test_idp_covers() {
+        let no_extensions = Extensions::from_raw_extensions(None).unwrap();
+
+        // An unscoped IDP covers everything.
+        let der = idp_der(None, false, false);
+        let idp = asn1::parse_single::<IssuingDistributionPoint<'_>>(&der).unwrap();
+        assert!(idp_covers(&idp, &no_extensions).unwrap());
+
+        // A certificate without basicConstraints is not a CA.
+        let der = idp_der(None, true, false);
+        let idp = asn1::parse_single::<IssuingDistributionPoint<'_>>(&der).unwrap();
+        assert!(idp_covers(&idp, &no_extensions).unwrap());
+        let der = idp_der(None, false, true);
+        let idp = asn1::parse_single::<IssuingDistributionPoint<'_>>(&der).unwrap();
+        assert!(!idp_covers(&idp, &no_extensions).unwrap());
+
+        // A distribution point-scoped IDP requires a matching distribution
+        // point in the certificate.
+        let der = idp_der(Some("http://example.com/a.crl"), false, false);
+        let idp = asn1::parse_single::<IssuingDistributionPoint<'_>>(&der).unwrap();
+        assert!(!idp_covers(&idp, &no_extensions).unwrap());
+    }
+}
@@ -4,50 +4,465 @@
 
 use std::collections::HashMap;
 
-use cryptography_x509::name::Name;
+use asn1::ObjectIdentifier;
+use cryptography_x509::certificate::Certificate;
+use cryptography_x509::common::{SubjectPublicKeyInfo, WithTlv};
+use cryptography_x509::extensions::{AuthorityKeyIdentifier, NameConstraints};
+use cryptography_x509::name::{GeneralName, Name};
+use cryptography_x509::oid::SUBJECT_KEY_IDENTIFIER_OID;
 
+use crate::types::SpiffeTrustDomain;
 use crate::CryptoOps;
 use crate::VerificationCertificate;
 
+/// A set of certificates, indexed by the identifiers that path building
+/// uses to find a certificate's issuer: its subject, its subject key
+/// identifier, and its issuer and serial number.
+pub(crate) struct CertificatePool<'a, B: CryptoOps> {
+    certs: Vec<VerificationCertificate<'a, B>>,
+    by_subject: HashMap<Name<'a>, Vec<usize>>,
+    by_ski: HashMap<&'a [u8], Vec<usize>>,
+    by_issuer_serial: HashMap<(Name<'a>, &'a [u8]), Vec<usize>>,
+}
+
+impl<'a, B: CryptoOps> CertificatePool<'a, B> {
+    pub(crate) fn new(certs: impl IntoIterator<Item = VerificationCertificate<'a, B>>) -> Self {
+        let mut pool = CertificatePool {
+            certs: vec![],
+            by_subject: HashMap::new(),
+            by_ski: HashMap::new(),
+            by_issuer_serial: HashMap::new(),
+        };
+
+        for cert in certs {
+            let i = pool.certs.len();
+            let tbs_cert = &cert.certificate().tbs_cert;
+
+            pool.by_subject
+                .entry(tbs_cert.subject.clone())
+                .or_default()
+                .push(i);
+            pool.by_issuer_serial
+                .entry((tbs_cert.issuer.clone(), tbs_cert.serial.as_bytes()))
+                .or_default()
+                .push(i);
+            // NOTE: A certificate with a malformed extension set is still
+            // pooled, but can't be found by its SKI. Path building will
+            // reject it anyways.
+            if let Some(ski) = subject_key_identifier(cert.certificate()) {
+                pool.by_ski.entry(ski).or_default().push(i);
+            }
+
+            pool.certs.push(cert);
+        }
+
+        pool
+    }
+
+    fn get<'s>(
+        &'s self,
+        indices: Option<&'s Vec<usize>>,
+    ) -> impl Iterator<Item = &'s VerificationCertificate<'a, B>> {
+        indices.into_iter().flatten().map(move |&i| &self.certs[i])
+    }
+
+    pub(crate) fn get_by_subject(
+        &self,
+        subject: &Name<'a>,
+    ) -> impl Iterator<Item = &VerificationCertificate<'a, B>> {
+        self.get(self.by_subject.get(subject))
+    }
+
+    /// Returns the index of `cert` in this pool, in the order that the
+    /// pool's certificates were supplied.
+    fn position(&self, cert: &VerificationCertificate<'a, B>) -> Option<usize> {
+        self.by_subject
+            .get(&cert.certificate().tbs_cert.subject)?
+            .iter()
+            .copied()
+            .find(|&i| &self.certs[i] == cert)
+    }
+
+    pub(crate) fn get_by_ski(
+        &self,
+        ski: &[u8],
+    ) -> impl Iterator<Item = &VerificationCertificate<'a, B>> {
+        self.get(self.by_ski.get(ski))
+    }
+
+    pub(crate) fn get_by_issuer_serial(
+        &self,
+        issuer: &Name<'a>,
+        serial: &'a [u8],
+    ) -> impl Iterator<Item = &VerificationCertificate<'a, B>> {
+        self.get(self.by_issuer_serial.get(&(issuer.clone(), serial)))
+    }
+
+    /// Returns the certificates in this pool that may have issued `cert`,
+    /// i.e. whose subject is `cert`'s issuer.
+    ///
+    /// The candidates are partitioned in two: those that also match
+    /// `cert`'s authority key identifier (if any), which are the most likely
+    /// issuers, and the rest.
+    pub(crate) fn potential_issuers(
+        &self,
+        cert: &Certificate<'a>,
+        aki: Option<&AuthorityKeyIdentifier<'a>>,
+    ) -> (
+        Vec<&VerificationCertificate<'a, B>>,
+        Vec<&VerificationCertificate<'a, B>>,
+    ) {
+        let issuer = &cert.tbs_cert.issuer;
+        let Some(aki) = aki else {
+            return (vec![], self.get_by_subject(issuer).collect());
+        };
+
+        let mut matches: Vec<&VerificationCertificate<'a, B>> = vec![];
+        if let Some(key_identifier) = aki.key_identifier {
+            matches.extend(self.get_by_ski(key_identifier));
+        }
+        if let (Some(issuer_names), Some(serial)) =
+            (&aki.authority_cert_issuer, aki.authority_cert_serial_number)
+        {
+            for gn in issuer_names.unwrap_read().clone() {
+                if let GeneralName::DirectoryName(name) = gn {
+                    matches.extend(self.get_by_issuer_serial(&name, serial.as_bytes()));
+                }
+            }
+        }
+
+        let mut preferred: Vec<&VerificationCertificate<'a, B>> = vec![];
+        for candidate in matches {
+            if &candidate.certificate().tbs_cert.subject == issuer
+                && !preferred.iter().any(|p| std::ptr::eq(*p, candidate))
+            {
+                preferred.push(candidate);
+            }
+        }
+
+        let rest = self
+            .get_by_subject(issuer)
+            .filter(|candidate| !preferred.iter().any(|p| std::ptr::eq(*p, *candidate)))
+            .collect();
+
+        (preferred, rest)
+    }
+}
+
+fn subject_key_identifier<'a>(cert: &Certificate<'a>) -> Option<&'a [u8]> {
+    cert.extensions()
+        .ok()?
+        .get_extension(&SUBJECT_KEY_IDENTIFIER_OID)?
+        .value::<&'a [u8]>()
+        .ok()
+}
+
+/// Constraints on the paths that a trust anchor may be used to validate,
+/// as described in RFC 5937. These apply in addition to any constraints in
+/// the anchor's own certificate.
+#[derive(Default)]
+pub struct AnchorConstraints<'a> {
+    /// Name constraints on the certificates below the anchor.
+    pub name_constraints: Option<NameConstraints<'a>>,
+
+    /// The maximum number of intermediate CA certificates between the
+    /// anchor and the leaf.
+    pub max_path_length: Option<u8>,
+
+    /// The extended key usages that the anchor is trusted for, or `None`
+    /// if it is trusted for any. This is checked against the policy's
+    /// extended key usage.
+    pub extended_key_usages: Option<Vec<ObjectIdentifier>>,
+
+    /// The SPIFFE trust domain that the anchor is a trust bundle for, if
+    /// any. The leaf's SPIFFE IDs must all be in this trust domain.
+    pub spiffe_trust_domain: Option<SpiffeTrustDomain<'a>>,
+}
+
+/// A trust anchor that is a name and a public key, rather than a
+/// certificate, such as an RFC 5914 `TrustAnchorInfo` without a
+/// certificate. It terminates any path whose last certificate names it as
+/// the issuer and is signed by its key.
+pub struct KeyAnchor<'a, B: CryptoOps> {
+    name: Name<'a>,
+    spki: WithTlv<'a, SubjectPublicKeyInfo<'a>>,
+    public_key: once_cell::sync::OnceCell<B::Key>,
+}
+
+impl<'a, B: CryptoOps> KeyAnchor<'a, B> {
+    pub fn new(name: Name<'a>, spki: WithTlv<'a, SubjectPublicKeyInfo<'a>>) -> Self {
+        KeyAnchor {
+            name,
+            spki,
+            public_key: once_cell::sync::OnceCell::new(),
+        }
+    }
+
+    pub fn name(&self) -> &Name<'a> {
+        &self.name
+    }
+
+    pub fn spki(&self) -> &WithTlv<'a, SubjectPublicKeyInfo<'a>> {
+        &self.spki
+    }
+
+    pub fn public_key(&self, ops: &B) -> Result<&B::Key, B::Err> {
+        self.public_key
+            .get_or_try_init(|| ops.spki_public_key(&self.spki))
+    }
+}
+
+impl<B: CryptoOps> PartialEq for KeyAnchor<'_, B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.spki == other.spki
+    }
+}
+impl<B: CryptoOps> Eq for KeyAnchor<'_, B> {}
+impl<B: CryptoOps> Clone for KeyAnchor<'_, B> {
+    fn clone(&self) -> Self {
+        KeyAnchor::new(self.name.clone(), self.spki.clone())
+    }
+}
+
+/// A source of trust anchors that are loaded on demand rather than
+/// supplied up front, such as an OpenSSL-style hashed certificate directory.
+pub trait AnchorSource<B: CryptoOps> {
+    /// Returns the trust anchors whose subject is `subject`, loading them if
+    /// they haven't been already, or a description of why they couldn't be
+    /// loaded.
+    fn get_by_subject<'s>(
+        &'s self,
+        subject: &Name<'_>,
+    ) -> Result<Vec<VerificationCertificate<'s, B>>, String>;
+
+    /// Returns whether `cert` is one of the trust anchors that have already
+    /// been loaded, without loading any more.
+    fn is_loaded(&self, cert: &Certificate<'_>) -> bool;
+}
+
 /// A `Store` represents the core state needed for X.509 path validation.
 pub struct Store<'a, B: CryptoOps> {
-    by_subject: HashMap<Name<'a>, Vec<VerificationCertificate<'a, B>>>,
+    pool: CertificatePool<'a, B>,
+    // The constraints on each of the pool's certificates, in pool order.
+    constraints: Vec<AnchorConstraints<'a>>,
+    // Trust anchors loaded on demand, which are unconstrained.
+    source: Option<&'a (dyn AnchorSource<B> + Sync)>,
+    source_constraints: AnchorConstraints<'a>,
+    // Trust anchors without certificates, each with its own constraints.
+    key_anchors: Vec<(KeyAnchor<'a, B>, AnchorConstraints<'a>)>,
+    // Whether every trust anchor may terminate a path, even if it isn't
+    // self-issued.
+    partial: bool,
 }
 
 impl<'a, B: CryptoOps> Store<'a, B> {
     /// Create a new `Store` from the given iterable certificate source.
     pub fn new(trusted: impl IntoIterator<Item = VerificationCertificate<'a, B>>) -> Self {
-        let mut by_subject: HashMap<Name<'a>, Vec<VerificationCertificate<'a, B>>> = HashMap::new();
-        for cert in trusted {
-            by_subject
-                .entry(cert.certificate().tbs_cert.subject.clone())
-                .or_default()
-                .push(cert.clone());
+        Self::with_constraints(
+            trusted
+                .into_iter()
+                .map(|cert| (cert, AnchorConstraints::default())),
+        )
+    }
+
+    /// Create a new `Store` from the given trust anchors, each with its own
+    /// constraints.
+    pub fn with_constraints(
+        trusted: impl IntoIterator<Item = (VerificationCertificate<'a, B>, AnchorConstraints<'a>)>,
+    ) -> Self {
+        let (certs, constraints): (Vec<_>, Vec<_>) = trusted.into_iter().unzip();
+        Store {
+            pool: CertificatePool::new(certs),
+            constraints,
+            source: None,
+            source_constraints: AnchorConstraints::default(),
+            key_anchors: vec![],
+            partial: false,
+        }
+    }
+
+    /// Adds the given trust anchors without certificates to this `Store`,
+    /// each with its own constraints.
+    pub fn with_key_anchors(
+        mut self,
+        anchors: impl IntoIterator<Item = (KeyAnchor<'a, B>, AnchorConstraints<'a>)>,
+    ) -> Self {
+        self.key_anchors.extend(anchors);
+        self
+    }
+
+    /// Create a new `Store` whose trust anchors may each terminate a path,
+    /// even if they aren't self-issued and the policy doesn't allow partial
+    /// chains. This is useful for one-off trust anchors, such as those
+    /// designated by DANE-TA records.
+    pub fn partial(trusted: impl IntoIterator<Item = VerificationCertificate<'a, B>>) -> Self {
+        Store {
+            partial: true,
+            ..Self::new(trusted)
+        }
+    }
+
+    /// Create a new `Store` whose trust anchors are loaded on demand from
+    /// `source`.
+    pub fn from_source(source: &'a (dyn AnchorSource<B> + Sync)) -> Self {
+        Store {
+            source: Some(source),
+            ..Self::new([])
         }
-        Store { by_subject }
     }
 
     /// Returns whether this store contains the given certificate.
     pub fn contains(&self, cert: &VerificationCertificate<'a, B>) -> bool {
-        self.get_by_subject(&cert.certificate().tbs_cert.subject)
-            .contains(cert)
+        self.constraints(cert).is_some()
     }
 
-    pub fn get_by_subject(&self, subject: &Name<'a>) -> &[VerificationCertificate<'a, B>] {
-        self.by_subject
-            .get(subject)
-            .map(|v| v.as_slice())
-            .unwrap_or_default()
+    /// Returns the constraints on the given certificate as a trust anchor,
+    /// or `None` if this store doesn't contain it.
+    ///
+    /// This never loads anything from the store's source: one of its trust
+    /// anchors is only recognized once it has been loaded, e.g. by looking
+    /// up the issuers of the certificate below it.
+    pub fn constraints(
+        &self,
+        cert: &VerificationCertificate<'a, B>,
+    ) -> Option<&AnchorConstraints<'a>> {
+        if let Some(i) = self.pool.position(cert) {
+            return Some(&self.constraints[i]);
+        }
+        self.source
+            .map_or(false, |source| source.is_loaded(cert.certificate()))
+            .then_some(&self.source_constraints)
+    }
+
+    /// Returns the trust anchors whose subject is `subject`, including any
+    /// that are loaded on demand.
+    pub fn get_by_subject(
+        &self,
+        subject: &Name<'a>,
+    ) -> Result<Vec<VerificationCertificate<'a, B>>, String> {
+        Ok(self
+            .pool
+            .get_by_subject(subject)
+            .cloned()
+            .chain(self.load_by_subject(subject)?)
+            .collect())
+    }
+
+    /// Returns only the trust anchors whose subject is `subject` that are
+    /// loaded on demand.
+    pub(crate) fn load_by_subject(
+        &self,
+        subject: &Name<'a>,
+    ) -> Result<Vec<VerificationCertificate<'a, B>>, String> {
+        self.source
+            .map_or_else(|| Ok(vec![]), |source| source.get_by_subject(subject))
+    }
+
+    /// Returns the trust anchors without certificates whose name is `name`,
+    /// along with their constraints.
+    pub(crate) fn key_anchors_by_name<'s>(
+        &'s self,
+        name: &'s Name<'a>,
+    ) -> impl Iterator<Item = &'s (KeyAnchor<'a, B>, AnchorConstraints<'a>)> {
+        self.key_anchors
+            .iter()
+            .filter(move |(anchor, _)| anchor.name() == name)
+    }
+
+    pub(crate) fn is_partial(&self) -> bool {
+        self.partial
+    }
+
+    pub(crate) fn pool(&self) -> &CertificatePool<'a, B> {
+        &self.pool
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Store;
+    use std::sync::Mutex;
+
+    use cryptography_x509::certificate::Certificate;
+    use cryptography_x509::extensions::AuthorityKeyIdentifier;
+    use cryptography_x509::name::Name;
+    use cryptography_x509::oid::AUTHORITY_KEY_IDENTIFIER_OID;
+
+    use super::{AnchorConstraints, AnchorSource, CertificatePool, KeyAnchor, Store};
     use crate::certificate::tests::PublicKeyErrorOps;
     use crate::ops::tests::{cert, v1_cert_pem};
     use crate::VerificationCertificate;
 
+    fn ca1_pem() -> pem::Pem {
+        // A self-signed CA named `ca`, with an SKI.
+        pem::parse(
+            "-----BEGIN CERTIFICATE-----
+MIIBKTCB0KADAgECAgEBMAoGCCqGSM49BAMCMA0xCzAJBgNVBAMMAmNhMB4XDTI0
+MDEwMTAwMDAwMFoXDTM0MDEwMTAwMDAwMFowDTELMAkGA1UEAwwCY2EwWTATBgcq
+hkjOPQIBBggqhkjOPQMBBwNCAAQW5pF1PwHOxTMtBYrj5bspWphhkQAjkdnwtoLY
+a352uFP5dKDkTXuQmwQ4DXZuHNLjH+dp5XyNvM94/ajQOsBtoyEwHzAdBgNVHQ4E
+FgQUMPIHui4ylGMz6fB4YWi7TbxmiG8wCgYIKoZIzj0EAwIDSAAwRQIhAJ6Ik/ye
+PP8gwLdnpPTPs2LIhXZLjcQDAuoXHCnPyZ4YAiBFvH2jHWiGjE269Icwb7rAGxmr
+mxecwkVAVMwN/8VmfQ==
+-----END CERTIFICATE-----",
+        )
+        .unwrap()
+    }
+
+    fn ca2_pem() -> pem::Pem {
+        // A re-keyed `ca`, with a different key and SKI.
+        pem::parse(
+            "-----BEGIN CERTIFICATE-----
+MIIBKTCB0KADAgECAgECMAoGCCqGSM49BAMCMA0xCzAJBgNVBAMMAmNhMB4XDTI0
+MDEwMTAwMDAwMFoXDTM0MDEwMTAwMDAwMFowDTELMAkGA1UEAwwCY2EwWTATBgcq
+hkjOPQIBBggqhkjOPQMBBwNCAARKSwW7IV6rJNWq94KXvf6RbINDHsQHSrEnzyjm
+ebiUsYsYQutRqN5WF/lppvEz99H9ogJJcH/sTJEd0op9LlpsoyEwHzAdBgNVHQ4E
+FgQUGtx0CC1u4lzIYrq8Lq1AgX0H0n0wCgYIKoZIzj0EAwIDSAAwRQIgPff89RQ5
+G03RiOVjbfl1kTWi0HwgqHqIlO3wsIdZsGYCIQDlvoENuZBLEbMIzJxulLLRDSDv
+Ydo+z9W5OkfP0EeJeQ==
+-----END CERTIFICATE-----",
+        )
+        .unwrap()
+    }
+
+    fn leaf_ski_pem() -> pem::Pem {
+        // Issued by `ca2`, with an AKI key identifier.
+        pem::parse(
+            "-----BEGIN CERTIFICATE-----
+MIIBLTCB1KADAgECAgEDMAoGCCqGSM49BAMCMA0xCzAJBgNVBAMMAmNhMB4XDTI0
+MDEwMTAwMDAwMFoXDTM0MDEwMTAwMDAwMFowDzENMAsGA1UEAwwEbGVhZjBZMBMG
+ByqGSM49AgEGCCqGSM49AwEHA0IABCTMAvAoJSryMsMlLRE4R4Aj43EYemj7Ife4
+bAno/EWw0c/MSM60v+Xbj4zjP6T+n05A9aNVST3zdxmz3dkIK12jIzAhMB8GA1Ud
+IwQYMBaAFBrcdAgtbuJcyGK6vC6tQIF9B9J9MAoGCCqGSM49BAMCA0gAMEUCIBUm
+UjOdmzCuGv61+/O1S6JuvaUtLtuyHLjIJRU+3QZoAiEA++nQrc4qTItCFyyN+0xL
+Rk7HZzJ3GoQhu/6FtJnon5w=
+-----END CERTIFICATE-----",
+        )
+        .unwrap()
+    }
+
+    fn leaf_serial_pem() -> pem::Pem {
+        // Issued by `ca2`, with an AKI issuer and serial number.
+        pem::parse(
+            "-----BEGIN CERTIFICATE-----
+MIIBLjCB1KADAgECAgEEMAoGCCqGSM49BAMCMA0xCzAJBgNVBAMMAmNhMB4XDTI0
+MDEwMTAwMDAwMFoXDTM0MDEwMTAwMDAwMFowDzENMAsGA1UEAwwEbGVhZjBZMBMG
+ByqGSM49AgEGCCqGSM49AwEHA0IABCTMAvAoJSryMsMlLRE4R4Aj43EYemj7Ife4
+bAno/EWw0c/MSM60v+Xbj4zjP6T+n05A9aNVST3zdxmz3dkIK12jIzAhMB8GA1Ud
+IwQYMBahEaQPMA0xCzAJBgNVBAMMAmNhggECMAoGCCqGSM49BAMCA0kAMEYCIQDw
+Aygam2ywAXXIDZta3z/O4bAmv60+9JORMI7PfnR0eAIhALwGDsdGLp3vKU6N4SXL
+jxCASg/CQ8uSvC9ro7emUgi0
+-----END CERTIFICATE-----",
+        )
+        .unwrap()
+    }
+
+    fn aki<'a>(cert: &Certificate<'a>) -> Option<AuthorityKeyIdentifier<'a>> {
+        cert.extensions()
+            .unwrap()
+            .get_extension(&AUTHORITY_KEY_IDENTIFIER_OID)
+            .map(|ext| ext.value().unwrap())
+    }
+
     #[test]
     fn test_store() {
         let cert_pem = v1_cert_pem();
@@ -55,5 +470,182 @@ mod tests {
         let store = Store::<'_, PublicKeyErrorOps>::new([cert.clone()]);
 
         assert!(store.contains(&cert));
+        assert!(store
+            .constraints(&cert)
+            .map_or(false, |c| c.max_path_length.is_none()));
+    }
+
+    #[test]
+    fn test_store_constraints() {
+        let ca1_pem = ca1_pem();
+        let ca2_pem = ca2_pem();
+        let ca1 = VerificationCertificate::new(cert(&ca1_pem), ());
+        let ca2 = VerificationCertificate::new(cert(&ca2_pem), ());
+        let store = Store::<'_, PublicKeyErrorOps>::with_constraints([(
+            ca2.clone(),
+            AnchorConstraints {
+                max_path_length: Some(1),
+                ..Default::default()
+            },
+        )]);
+
+        // Certificates with the same subject are still distinguished.
+        assert!(!store.contains(&ca1));
+        assert!(store.constraints(&ca1).is_none());
+        assert_eq!(
+            store.constraints(&ca2).and_then(|c| c.max_path_length),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn test_store_key_anchors() {
+        let ca1_pem = ca1_pem();
+        let ca2_pem = ca2_pem();
+        let v1_pem = v1_cert_pem();
+        let ca1 = cert(&ca1_pem);
+        let ca2 = cert(&ca2_pem);
+        let v1 = cert(&v1_pem);
+        let anchor1 = KeyAnchor::<PublicKeyErrorOps>::new(
+            ca1.tbs_cert.subject.clone(),
+            ca1.tbs_cert.spki.clone(),
+        );
+        let anchor2 = KeyAnchor::new(ca2.tbs_cert.subject.clone(), ca2.tbs_cert.spki.clone());
+        assert!(anchor1 != anchor2);
+        assert!(anchor1.clone() == anchor1);
+
+        let store = Store::new([]).with_key_anchors([
+            (anchor1.clone(), AnchorConstraints::default()),
+            (
+                anchor2.clone(),
+                AnchorConstraints {
+                    max_path_length: Some(1),
+                    ..Default::default()
+                },
+            ),
+        ]);
+
+        // Anchors with the same name are all candidates, in order.
+        let found = store
+            .key_anchors_by_name(&ca1.tbs_cert.subject)
+            .collect::<Vec<_>>();
+        assert_eq!(found.len(), 2);
+        assert!(found[0].0 == anchor1);
+        assert!(found[0].1.max_path_length.is_none());
+        assert!(found[1].0 == anchor2);
+        assert_eq!(found[1].1.max_path_length, Some(1));
+
+        assert!(store
+            .key_anchors_by_name(&v1.tbs_cert.subject)
+            .next()
+            .is_none());
+
+        // Key anchors aren't certificates in the store.
+        assert!(store
+            .get_by_subject(&ca1.tbs_cert.subject)
+            .unwrap()
+            .is_empty());
+        assert!(anchor1.public_key(&PublicKeyErrorOps {}).is_err());
+    }
+
+    struct VecSource<'a> {
+        certs: Vec<VerificationCertificate<'a, PublicKeyErrorOps>>,
+        loaded: Mutex<Vec<VerificationCertificate<'a, PublicKeyErrorOps>>>,
+    }
+
+    impl AnchorSource<PublicKeyErrorOps> for VecSource<'_> {
+        fn get_by_subject<'s>(
+            &'s self,
+            subject: &Name<'_>,
+        ) -> Result<Vec<VerificationCertificate<'s, PublicKeyErrorOps>>, String> {
+            let certs = self
+                .certs
+                .iter()
+                .filter(|c| &c.certificate().tbs_cert.subject == subject)
+                .cloned()
+                .collect::<Vec<_>>();
+            self.loaded.lock().unwrap().extend(certs.iter().cloned());
+            Ok(certs)
+        }
+
+        fn is_loaded(&self, cert: &Certificate<'_>) -> bool {
+            self.loaded
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|c| c.certificate() == cert)
+        }
+    }
+
+    #[test]
+    fn test_store_source() {
+        let ca1_pem = ca1_pem();
+        let ca2_pem = ca2_pem();
+        let v1_pem = v1_cert_pem();
+        let ca1 = VerificationCertificate::new(cert(&ca1_pem), ());
+        let ca2 = VerificationCertificate::new(cert(&ca2_pem), ());
+        let v1 = VerificationCertificate::new(cert(&v1_pem), ());
+        let source = VecSource {
+            certs: vec![ca2.clone()],
+            loaded: Mutex::new(vec![]),
+        };
+        let store = Store::from_source(&source);
+
+        // Nothing is recognized until it has been loaded.
+        assert!(!store.contains(&ca2));
+        assert!(store.get_by_subject(&ca1.certificate().tbs_cert.subject) == Ok(vec![ca2.clone()]));
+        assert!(store.contains(&ca2));
+        assert!(!store.contains(&ca1));
+        assert!(!store.contains(&v1));
+        assert!(store
+            .constraints(&ca2)
+            .map_or(false, |c| c.max_path_length.is_none()));
+        assert!(store
+            .get_by_subject(&v1.certificate().tbs_cert.subject)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_pool_potential_issuers() {
+        let ca1_pem = ca1_pem();
+        let ca2_pem = ca2_pem();
+        let ca1 = VerificationCertificate::new(cert(&ca1_pem), ());
+        let ca2 = VerificationCertificate::new(cert(&ca2_pem), ());
+        let pool = CertificatePool::<'_, PublicKeyErrorOps>::new([ca1.clone(), ca2.clone()]);
+
+        assert_eq!(
+            pool.get_by_subject(&ca1.certificate().tbs_cert.subject)
+                .count(),
+            2
+        );
+
+        // Without an AKI, every certificate with the right subject is a
+        // candidate, in the order they were added.
+        let (preferred, rest) = pool.potential_issuers(ca1.certificate(), None);
+        assert!(preferred.is_empty());
+        assert!(rest == [&ca1, &ca2]);
+
+        for leaf_pem in [leaf_ski_pem(), leaf_serial_pem()] {
+            let leaf = cert(&leaf_pem);
+            let aki = aki(&leaf);
+            assert!(aki.is_some());
+
+            let (preferred, rest) = pool.potential_issuers(&leaf, aki.as_ref());
+            assert!(preferred == [&ca2]);
+            assert!(rest == [&ca1]);
+        }
+
+        // A pool without the issuer has no candidates.
+        let v1_pem = v1_cert_pem();
+        let pool = CertificatePool::<'_, PublicKeyErrorOps>::new([VerificationCertificate::new(
+            cert(&v1_pem),
+            (),
+        )]);
+        let leaf_pem = leaf_ski_pem();
+        let leaf = cert(&leaf_pem);
+        let (preferred, rest) = pool.potential_issuers(&leaf, aki(&leaf).as_ref());
+        assert!(preferred.is_empty());
+        assert!(rest.is_empty());
     }
 }
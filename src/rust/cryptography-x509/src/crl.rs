@@ -7,7 +7,7 @@ use crate::{common, extensions, name};
 pub type ReasonFlags<'a> =
     Option<common::Asn1ReadableOrWritable<asn1::BitString<'a>, asn1::OwnedBitString>>;
 
-#[derive(asn1::Asn1Read, asn1::Asn1Write, PartialEq, Eq, Hash)]
+#[derive(asn1::Asn1Read, asn1::Asn1Write, PartialEq, Eq, Hash, Clone)]
 pub struct CertificateRevocationList<'a> {
     pub tbs_cert_list: TBSCertList<'a>,
     pub signature_algorithm: common::AlgorithmIdentifier<'a>,
@@ -21,7 +21,7 @@ pub type RevokedCertificates<'a> = Option<
     >,
 >;
 
-#[derive(asn1::Asn1Read, asn1::Asn1Write, PartialEq, Eq, Hash)]
+#[derive(asn1::Asn1Read, asn1::Asn1Write, PartialEq, Eq, Hash, Clone)]
 pub struct TBSCertList<'a> {
     pub version: Option<u8>,
     pub signature: common::AlgorithmIdentifier<'a>,
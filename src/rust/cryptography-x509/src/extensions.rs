@@ -88,6 +88,12 @@ pub struct PolicyConstraints {
     pub inhibit_policy_mapping: Option<u64>,
 }
 
+#[derive(asn1::Asn1Read, asn1::Asn1Write)]
+pub struct PolicyMapping {
+    pub issuer_domain_policy: asn1::ObjectIdentifier,
+    pub subject_domain_policy: asn1::ObjectIdentifier,
+}
+
 #[derive(asn1::Asn1Read, asn1::Asn1Write)]
 pub struct AccessDescription<'a> {
     pub access_method: asn1::ObjectIdentifier,
@@ -389,7 +389,7 @@ fn load_pem_pkcs7_certificates<'p>(
 }
 
 #[pyo3::prelude::pyfunction]
-fn load_der_pkcs7_certificates<'p>(
+pub(crate) fn load_der_pkcs7_certificates<'p>(
     py: pyo3::Python<'p>,
     data: &[u8],
 ) -> CryptographyResult<pyo3::Bound<'p, pyo3::types::PyList>> {
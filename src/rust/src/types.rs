@@ -262,6 +262,24 @@ pub static TLS_FEATURE_TYPE_TO_ENUM: LazyPyImport = LazyPyImport::new(
     &["_TLS_FEATURE_TYPE_TO_ENUM"],
 );
 
+pub static VERIFICATION_FAILURE_REASON: LazyPyImport =
+    LazyPyImport::new("cryptography.x509.verification", &["FailureReason"]);
+pub static VERIFICATION_CRITICALITY: LazyPyImport =
+    LazyPyImport::new("cryptography.x509.verification", &["Criticality"]);
+pub static VERIFICATION_EXTENSION_PRESENCE: LazyPyImport =
+    LazyPyImport::new("cryptography.x509.verification", &["ExtensionPresence"]);
+pub static VERIFICATION_VALIDATION_MODEL: LazyPyImport =
+    LazyPyImport::new("cryptography.x509.verification", &["ValidationModel"]);
+
+pub static VERIFICATION_TLSA_USAGE: LazyPyImport =
+    LazyPyImport::new("cryptography.x509.verification", &["TLSAUsage"]);
+
+pub static VERIFICATION_TLSA_SELECTOR: LazyPyImport =
+    LazyPyImport::new("cryptography.x509.verification", &["TLSASelector"]);
+
+pub static VERIFICATION_TLSA_MATCHING_TYPE: LazyPyImport =
+    LazyPyImport::new("cryptography.x509.verification", &["TLSAMatchingType"]);
+
 pub static OCSP_RESPONSE_STATUS: LazyPyImport =
     LazyPyImport::new("cryptography.x509.ocsp", &["OCSPResponseStatus"]);
 pub static OCSP_CERT_STATUS: LazyPyImport =
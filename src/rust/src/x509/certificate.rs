@@ -387,7 +387,7 @@ fn load_pem_x509_certificate(
 }
 
 #[pyo3::prelude::pyfunction]
-fn load_pem_x509_certificates(
+pub(crate) fn load_pem_x509_certificates(
     py: pyo3::Python<'_>,
     data: &[u8],
 ) -> CryptographyResult<Vec<Certificate>> {
@@ -74,7 +74,7 @@ fn load_pem_x509_crl(
 }
 
 self_cell::self_cell!(
-    struct OwnedCertificateRevocationList {
+    pub(crate) struct OwnedCertificateRevocationList {
         owner: pyo3::Py<pyo3::types::PyBytes>,
         #[covariant]
         dependent: RawCertificateRevocationList,
@@ -82,8 +82,8 @@ self_cell::self_cell!(
 );
 
 #[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.x509")]
-struct CertificateRevocationList {
-    owned: Arc<OwnedCertificateRevocationList>,
+pub(crate) struct CertificateRevocationList {
+    pub(crate) owned: Arc<OwnedCertificateRevocationList>,
 
     revoked_certs: pyo3::sync::GILOnceCell<Vec<OwnedRevokedCertificate>>,
     cached_extensions: pyo3::sync::GILOnceCell<pyo3::PyObject>,
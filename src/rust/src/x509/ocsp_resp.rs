@@ -73,7 +73,7 @@ self_cell::self_cell!(
 );
 
 #[pyo3::prelude::pyclass(frozen, module = "cryptography.hazmat.bindings._rust.ocsp")]
-struct OCSPResponse {
+pub(crate) struct OCSPResponse {
     raw: Arc<OwnedOCSPResponse>,
 
     cached_extensions: pyo3::sync::GILOnceCell<pyo3::PyObject>,
@@ -81,7 +81,9 @@ struct OCSPResponse {
 }
 
 impl OCSPResponse {
-    fn requires_successful_response(&self) -> pyo3::PyResult<&ocsp_resp::BasicOCSPResponse<'_>> {
+    pub(crate) fn requires_successful_response(
+        &self,
+    ) -> pyo3::PyResult<&ocsp_resp::BasicOCSPResponse<'_>> {
         match self.raw.borrow_dependent().response_bytes.as_ref() {
             Some(b) => Ok(b.response.get()),
             None => Err(pyo3::exceptions::PyValueError::new_err(
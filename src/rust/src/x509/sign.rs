@@ -355,6 +355,144 @@ pub(crate) fn verify_signature_with_signature_algorithm<'p>(
     Ok(())
 }
 
+/// Verifies `signature` over `data` with `issuer_public_key`, using OpenSSL
+/// directly rather than the Python key objects. This does not need the GIL.
+pub(crate) fn verify_signature_with_public_key(
+    issuer_public_key: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+    signature_algorithm: &common::AlgorithmIdentifier<'_>,
+    signature: &[u8],
+    data: &[u8],
+) -> CryptographyResult<()> {
+    let key_type = identify_pkey_type(issuer_public_key)?;
+    let sig_key_type = identify_key_type_for_algorithm_params(&signature_algorithm.params)?;
+    if key_type != sig_key_type {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyValueError::new_err(
+                "Signature algorithm does not match issuer key type",
+            ),
+        ));
+    }
+
+    let valid = match key_type {
+        KeyType::Ed25519 | KeyType::Ed448 => {
+            openssl::sign::Verifier::new_without_digest(issuer_public_key)?
+                .verify_oneshot(signature, data)
+                .unwrap_or(false)
+        }
+        KeyType::Rsa | KeyType::Ec | KeyType::Dsa => {
+            let md = identify_signature_message_digest(signature_algorithm)?;
+            let mut verifier = openssl::sign::Verifier::new(md, issuer_public_key)?;
+            if let common::AlgorithmParameters::RsaPss(opt_pss) = &signature_algorithm.params {
+                let pss = opt_pss.as_ref().ok_or_else(|| {
+                    pyo3::exceptions::PyValueError::new_err("Invalid RSA PSS parameters")
+                })?;
+                if pss.mask_gen_algorithm.oid != oid::MGF1_OID {
+                    return Err(CryptographyError::from(
+                        pyo3::exceptions::PyValueError::new_err(format!(
+                            "Unsupported mask generation OID: {}",
+                            pss.mask_gen_algorithm.oid
+                        )),
+                    ));
+                }
+                verifier.set_rsa_padding(openssl::rsa::Padding::PKCS1_PSS)?;
+                verifier.set_rsa_mgf1_md(hash_oid_message_digest(
+                    pss.mask_gen_algorithm.params.oid(),
+                )?)?;
+                verifier.set_rsa_pss_saltlen(openssl::sign::RsaPssSaltlen::custom(
+                    pss.salt_length.into(),
+                ))?;
+            }
+            verifier.update(data)?;
+            verifier.verify(signature).unwrap_or(false)
+        }
+    };
+    if !valid {
+        return Err(CryptographyError::from(
+            exceptions::InvalidSignature::new_err(()),
+        ));
+    }
+    Ok(())
+}
+
+fn identify_pkey_type(
+    pkey: &openssl::pkey::PKeyRef<openssl::pkey::Public>,
+) -> pyo3::PyResult<KeyType> {
+    match pkey.id() {
+        openssl::pkey::Id::RSA => Ok(KeyType::Rsa),
+        openssl::pkey::Id::DSA => Ok(KeyType::Dsa),
+        openssl::pkey::Id::EC => Ok(KeyType::Ec),
+        openssl::pkey::Id::ED25519 => Ok(KeyType::Ed25519),
+        #[cfg(all(not(CRYPTOGRAPHY_IS_LIBRESSL), not(CRYPTOGRAPHY_IS_BORINGSSL)))]
+        openssl::pkey::Id::ED448 => Ok(KeyType::Ed448),
+        _ => Err(pyo3::exceptions::PyTypeError::new_err(
+            "Key must be an rsa, dsa, ec, ed25519, or ed448 public key.",
+        )),
+    }
+}
+
+fn hash_oid_message_digest(
+    oid: &asn1::ObjectIdentifier,
+) -> CryptographyResult<openssl::hash::MessageDigest> {
+    match *oid {
+        oid::SHA1_OID => Ok(openssl::hash::MessageDigest::sha1()),
+        oid::SHA224_OID => Ok(openssl::hash::MessageDigest::sha224()),
+        oid::SHA256_OID => Ok(openssl::hash::MessageDigest::sha256()),
+        oid::SHA384_OID => Ok(openssl::hash::MessageDigest::sha384()),
+        oid::SHA512_OID => Ok(openssl::hash::MessageDigest::sha512()),
+        oid::SHA3_224_OID => Ok(openssl::hash::MessageDigest::sha3_224()),
+        oid::SHA3_256_OID => Ok(openssl::hash::MessageDigest::sha3_256()),
+        oid::SHA3_384_OID => Ok(openssl::hash::MessageDigest::sha3_384()),
+        oid::SHA3_512_OID => Ok(openssl::hash::MessageDigest::sha3_512()),
+        _ => Err(CryptographyError::from(
+            exceptions::UnsupportedAlgorithm::new_err(format!(
+                "Signature algorithm OID: {oid} not recognized"
+            )),
+        )),
+    }
+}
+
+fn identify_signature_message_digest(
+    signature_algorithm: &common::AlgorithmIdentifier<'_>,
+) -> CryptographyResult<openssl::hash::MessageDigest> {
+    let hash_oid = match &signature_algorithm.params {
+        common::AlgorithmParameters::RsaPss(opt_pss) => {
+            let pss = opt_pss.as_ref().ok_or_else(|| {
+                pyo3::exceptions::PyValueError::new_err("Invalid RSA PSS parameters")
+            })?;
+            pss.hash_algorithm.oid().clone()
+        }
+        common::AlgorithmParameters::RsaWithSha224(_)
+        | common::AlgorithmParameters::EcDsaWithSha224(_)
+        | common::AlgorithmParameters::DsaWithSha224(_) => oid::SHA224_OID,
+        common::AlgorithmParameters::RsaWithSha256(_)
+        | common::AlgorithmParameters::EcDsaWithSha256(_)
+        | common::AlgorithmParameters::DsaWithSha256(_) => oid::SHA256_OID,
+        common::AlgorithmParameters::RsaWithSha384(_)
+        | common::AlgorithmParameters::EcDsaWithSha384(_)
+        | common::AlgorithmParameters::DsaWithSha384(_) => oid::SHA384_OID,
+        common::AlgorithmParameters::RsaWithSha512(_)
+        | common::AlgorithmParameters::EcDsaWithSha512(_)
+        | common::AlgorithmParameters::DsaWithSha512(_) => oid::SHA512_OID,
+        common::AlgorithmParameters::RsaWithSha3_224(_)
+        | common::AlgorithmParameters::EcDsaWithSha3_224 => oid::SHA3_224_OID,
+        common::AlgorithmParameters::RsaWithSha3_256(_)
+        | common::AlgorithmParameters::EcDsaWithSha3_256 => oid::SHA3_256_OID,
+        common::AlgorithmParameters::RsaWithSha3_384(_)
+        | common::AlgorithmParameters::EcDsaWithSha3_384 => oid::SHA3_384_OID,
+        common::AlgorithmParameters::RsaWithSha3_512(_)
+        | common::AlgorithmParameters::EcDsaWithSha3_512 => oid::SHA3_512_OID,
+        _ => {
+            return Err(CryptographyError::from(
+                exceptions::UnsupportedAlgorithm::new_err(format!(
+                    "Signature algorithm OID: {} not recognized",
+                    signature_algorithm.oid()
+                )),
+            ))
+        }
+    };
+    hash_oid_message_digest(&hash_oid)
+}
+
 pub(crate) fn identify_public_key_type(
     py: pyo3::Python<'_>,
     public_key: pyo3::Bound<'_, pyo3::PyAny>,
@@ -2,49 +2,112 @@
 // 2.0, and the BSD License. See the LICENSE file in the root of this repository
 // for complete details.
 
+use std::collections::{HashMap, HashSet};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
 use cryptography_x509::{
-    certificate::Certificate, extensions::SubjectAlternativeName, oid::SUBJECT_ALTERNATIVE_NAME_OID,
+    certificate::Certificate,
+    common::{
+        AlgorithmIdentifier, AlgorithmParameters, EcParameters, RsaPssParameters,
+        SubjectPublicKeyInfo, WithTlv, PSS_SHA256_HASH_ALG, PSS_SHA256_MASK_GEN_ALG,
+        PSS_SHA384_HASH_ALG, PSS_SHA384_MASK_GEN_ALG, PSS_SHA512_HASH_ALG, PSS_SHA512_MASK_GEN_ALG,
+    },
+    extensions::Extension,
+    extensions::SubjectAlternativeName,
+    name::Name,
+    ocsp_resp::BasicOCSPResponse,
+    oid::{self, SUBJECT_ALTERNATIVE_NAME_OID},
 };
 use cryptography_x509_verification::{
+    cache::SignatureCache,
+    ct::{CTLog, CTPolicy},
+    dane::{TlsaMatchingType, TlsaRecord, TlsaSelector, TlsaUsage},
     ops::{CryptoOps, VerificationCertificate},
-    policy::{Policy, Subject},
-    trust_store::Store,
-    types::{DNSName, IPAddress},
+    policy::{
+        Criticality, CustomExtensionValidatorCallback, Policy, Presence, Subject, ValidationModel,
+    },
+    policy_tree::PolicyTreeConstraints,
+    revocation::{RevocationMode, RevocationPolicy},
+    trust_store::{AnchorConstraints, AnchorSource, KeyAnchor, Store},
+    types::{DNSName, IPAddress, SmtpUTF8Mailbox, SpiffeId, SpiffeTrustDomain},
+    Diagnostic, FailureReason, IssuerFetchError, IssuerFetcher, ValidationError,
+    VerificationFailure, VerifiedChain,
 };
-use pyo3::prelude::{PyAnyMethods, PyListMethods, PyModuleMethods};
+use pyo3::prelude::{PyAnyMethods, PyBytesMethods, PyListMethods, PyModuleMethods};
+use pyo3::types::IntoPyDict;
+use pyo3::IntoPy;
 
-use crate::backend::keys;
+use crate::asn1::{oid_to_py_oid, py_oid_to_oid};
 use crate::error::{CryptographyError, CryptographyResult};
+use crate::pkcs7;
 use crate::types;
 use crate::x509::certificate::Certificate as PyCertificate;
-use crate::x509::common::{datetime_now, datetime_to_py, py_to_datetime};
-use crate::x509::sign;
+use crate::x509::common::{datetime_now, datetime_to_py, encode_name, py_to_datetime};
+use crate::x509::crl::CertificateRevocationList as PyCertificateRevocationList;
+use crate::x509::ocsp_resp::OCSPResponse as PyOCSPResponse;
+use crate::x509::{certificate, extensions, ocsp, sign};
 
 use super::parse_general_names;
 
+/// The `CryptoOps` backend for the Python bindings. Keys are parsed and
+/// signatures verified with OpenSSL directly, so path building can run
+/// without holding the GIL.
 pub(crate) struct PyCryptoOps {}
 
 impl CryptoOps for PyCryptoOps {
-    type Key = pyo3::Py<pyo3::PyAny>;
+    type Key = openssl::pkey::PKey<openssl::pkey::Public>;
     type Err = CryptographyError;
     type CertificateExtra = pyo3::Py<PyCertificate>;
 
     fn public_key(&self, cert: &Certificate<'_>) -> Result<Self::Key, Self::Err> {
-        pyo3::Python::with_gil(|py| -> Result<Self::Key, Self::Err> {
-            keys::load_der_public_key_bytes(py, cert.tbs_cert.spki.tlv().full_data())
-        })
+        self.spki_public_key(&cert.tbs_cert.spki)
+    }
+
+    fn spki_public_key(
+        &self,
+        spki: &WithTlv<'_, SubjectPublicKeyInfo<'_>>,
+    ) -> Result<Self::Key, Self::Err> {
+        Ok(cryptography_key_parsing::spki::parse_public_key(
+            spki.tlv().full_data(),
+        )?)
     }
 
     fn verify_signed_by(&self, cert: &Certificate<'_>, key: &Self::Key) -> Result<(), Self::Err> {
-        pyo3::Python::with_gil(|py| -> CryptographyResult<()> {
-            sign::verify_signature_with_signature_algorithm(
-                py,
-                key.bind(py).clone(),
-                &cert.signature_alg,
-                cert.signature.as_bytes(),
-                &asn1::write_single(&cert.tbs_cert)?,
-            )
-        })
+        sign::verify_signature_with_public_key(
+            key,
+            &cert.signature_alg,
+            cert.signature.as_bytes(),
+            &asn1::write_single(&cert.tbs_cert)?,
+        )
+    }
+
+    fn verify_signature(
+        &self,
+        key: &Self::Key,
+        signature_algorithm: &AlgorithmIdentifier<'_>,
+        signature: &[u8],
+        data: &[u8],
+    ) -> Result<(), Self::Err> {
+        sign::verify_signature_with_public_key(key, signature_algorithm, signature, data)
+    }
+
+    fn digest(
+        &self,
+        hash_algorithm: &AlgorithmIdentifier<'_>,
+        data: &[u8],
+    ) -> Result<Vec<u8>, Self::Err> {
+        let md = ocsp::ALGORITHM_PARAMETERS_TO_HASH
+            .get(&hash_algorithm.params)
+            .and_then(|name| openssl::hash::MessageDigest::from_name(name))
+            .ok_or_else(|| {
+                CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+                    "Unsupported hash algorithm",
+                ))
+            })?;
+        Ok(openssl::hash::hash(md, data)?.to_vec())
     }
 }
 
@@ -57,80 +120,147 @@ pyo3::create_exception!(
 #[pyo3::pyclass(frozen, module = "cryptography.x509.verification")]
 struct PolicyBuilder {
     time: Option<asn1::DateTime>,
+    validation_model: Option<ValidationModel>,
+    clock_skew: Option<Duration>,
     store: Option<pyo3::Py<PyStore>>,
     max_chain_depth: Option<u8>,
+    crls: Option<Vec<pyo3::Py<PyCertificateRevocationList>>>,
+    revocation_mode: RevocationMode,
+    ocsp_clock_skew: Option<Duration>,
+    certificate_policies: Option<PolicyTreeConstraints>,
+    extension_overrides: Vec<ExtensionOverride>,
+    minimum_rsa_modulus: Option<usize>,
+    signature_algorithms: Option<Arc<HashSet<AlgorithmIdentifier<'static>>>>,
+    public_key_algorithms: Option<Vec<asn1::ObjectIdentifier>>,
+    ec_curves: Option<Vec<asn1::ObjectIdentifier>>,
+    certificate_transparency: Option<CTSettings>,
+    issuer_fetcher: Option<pyo3::PyObject>,
+    partial_chain: Option<bool>,
+    spki_pins: Option<Arc<HashSet<Vec<u8>>>>,
+    max_signature_checks: Option<usize>,
+    max_candidates: Option<usize>,
+    max_issuer_fetches: Option<usize>,
+    max_path_building_time: Option<Duration>,
+    signature_cache_size: Option<NonZeroUsize>,
 }
 
-#[pyo3::pymethods]
-impl PolicyBuilder {
-    #[new]
-    fn new() -> PolicyBuilder {
-        PolicyBuilder {
-            time: None,
-            store: None,
-            max_chain_depth: None,
+/// The Certificate Transparency requirements for server verification.
+struct CTSettings {
+    logs: Vec<pyo3::Py<PyCertificateTransparencyLog>>,
+    minimum_scts: usize,
+    minimum_operators: usize,
+}
+
+impl CTSettings {
+    fn py_clone(&self, py: pyo3::Python<'_>) -> CTSettings {
+        CTSettings {
+            logs: self.logs.iter().map(|l| l.clone_ref(py)).collect(),
+            minimum_scts: self.minimum_scts,
+            minimum_operators: self.minimum_operators,
         }
     }
+}
 
-    fn time(
-        &self,
-        py: pyo3::Python<'_>,
-        new_time: pyo3::Bound<'_, pyo3::PyAny>,
-    ) -> CryptographyResult<PolicyBuilder> {
-        if self.time.is_some() {
-            return Err(CryptographyError::from(
-                pyo3::exceptions::PyValueError::new_err(
-                    "The validation time may only be set once.",
-                ),
-            ));
+/// A caller-requested change to the CA or EE extension policy for a single
+/// extension.
+struct ExtensionOverride {
+    ca: bool,
+    oid: asn1::ObjectIdentifier,
+    presence: Option<Presence>,
+    criticality: Option<Criticality>,
+    validator: Option<pyo3::PyObject>,
+}
+
+impl ExtensionOverride {
+    fn py_clone(&self, py: pyo3::Python<'_>) -> ExtensionOverride {
+        ExtensionOverride {
+            ca: self.ca,
+            oid: self.oid.clone(),
+            presence: self.presence,
+            criticality: self.criticality,
+            validator: self.validator.as_ref().map(|v| v.clone_ref(py)),
         }
-        Ok(PolicyBuilder {
-            time: Some(py_to_datetime(py, new_time)?),
+    }
+}
+
+impl PolicyBuilder {
+    fn py_clone(&self, py: pyo3::Python<'_>) -> PolicyBuilder {
+        PolicyBuilder {
+            time: self.time.clone(),
+            validation_model: self.validation_model,
+            clock_skew: self.clock_skew,
             store: self.store.as_ref().map(|s| s.clone_ref(py)),
             max_chain_depth: self.max_chain_depth,
-        })
+            crls: self
+                .crls
+                .as_ref()
+                .map(|crls| crls.iter().map(|c| c.clone_ref(py)).collect()),
+            revocation_mode: self.revocation_mode,
+            ocsp_clock_skew: self.ocsp_clock_skew,
+            certificate_policies: self.certificate_policies.clone(),
+            extension_overrides: self
+                .extension_overrides
+                .iter()
+                .map(|o| o.py_clone(py))
+                .collect(),
+            minimum_rsa_modulus: self.minimum_rsa_modulus,
+            signature_algorithms: self.signature_algorithms.clone(),
+            public_key_algorithms: self.public_key_algorithms.clone(),
+            ec_curves: self.ec_curves.clone(),
+            certificate_transparency: self
+                .certificate_transparency
+                .as_ref()
+                .map(|ct| ct.py_clone(py)),
+            issuer_fetcher: self.issuer_fetcher.as_ref().map(|f| f.clone_ref(py)),
+            partial_chain: self.partial_chain,
+            spki_pins: self.spki_pins.clone(),
+            max_signature_checks: self.max_signature_checks,
+            max_candidates: self.max_candidates,
+            max_issuer_fetches: self.max_issuer_fetches,
+            max_path_building_time: self.max_path_building_time,
+            signature_cache_size: self.signature_cache_size,
+        }
     }
 
-    fn store(&self, new_store: pyo3::Py<PyStore>) -> CryptographyResult<PolicyBuilder> {
-        if self.store.is_some() {
-            return Err(CryptographyError::from(
-                pyo3::exceptions::PyValueError::new_err("The trust store may only be set once."),
-            ));
+    /// Returns a new signature cache for a verifier, if one was requested.
+    fn signature_cache(&self) -> Option<Arc<SignatureCache>> {
+        self.signature_cache_size
+            .map(|size| Arc::new(SignatureCache::with_capacity(size)))
+    }
+
+    fn policy_owner(&self, py: pyo3::Python<'_>, subject: Option<SubjectOwner>) -> PolicyOwner {
+        PolicyOwner {
+            subject,
+            crls: self
+                .crls
+                .as_ref()
+                .map(|crls| crls.iter().map(|c| c.clone_ref(py)).collect()),
         }
-        Ok(PolicyBuilder {
-            time: self.time.clone(),
-            store: Some(new_store),
-            max_chain_depth: self.max_chain_depth,
-        })
     }
 
-    fn max_chain_depth(
+    /// Builds a verifier for a profile whose EEs are not matched against a
+    /// subject, such as the client profile.
+    fn build_subjectless_verifier(
         &self,
         py: pyo3::Python<'_>,
-        new_max_chain_depth: u8,
-    ) -> CryptographyResult<PolicyBuilder> {
-        if self.max_chain_depth.is_some() {
+        kind: &str,
+        new_policy: fn(PyCryptoOps, asn1::DateTime, Option<u8>) -> Policy<'static, PyCryptoOps>,
+    ) -> CryptographyResult<PyClientVerifier> {
+        if self.certificate_transparency.is_some() {
             return Err(CryptographyError::from(
                 pyo3::exceptions::PyValueError::new_err(
-                    "The maximum chain depth may only be set once.",
+                    "Certificate Transparency is only supported by server verifiers.",
                 ),
             ));
         }
-        Ok(PolicyBuilder {
-            time: self.time.clone(),
-            store: self.store.as_ref().map(|s| s.clone_ref(py)),
-            max_chain_depth: Some(new_max_chain_depth),
-        })
-    }
 
-    fn build_client_verifier(&self, py: pyo3::Python<'_>) -> CryptographyResult<PyClientVerifier> {
         let store = match self.store.as_ref() {
             Some(s) => s.clone_ref(py),
             None => {
                 return Err(CryptographyError::from(
-                    pyo3::exceptions::PyValueError::new_err(
-                        "A client verifier must have a trust store.",
-                    ),
+                    pyo3::exceptions::PyValueError::new_err(format!(
+                        "A {kind} verifier must have a trust store."
+                    )),
                 ));
             }
         };
@@ -140,234 +270,2194 @@ impl PolicyBuilder {
             None => datetime_now(py)?,
         };
 
-        let policy = PyCryptoPolicy(Policy::client(PyCryptoOps {}, time, self.max_chain_depth));
+        let policy = OwnedPolicy::new(self.policy_owner(py, None), |owner| {
+            let mut policy = new_policy(PyCryptoOps {}, time, self.max_chain_depth);
+            self.configure_policy(py, &mut policy, owner);
+            PyCryptoPolicy(policy)
+        });
 
-        Ok(PyClientVerifier { policy, store })
+        Ok(PyClientVerifier {
+            policy,
+            store,
+            issuer_fetcher: self.issuer_fetcher.as_ref().map(|f| f.clone_ref(py)),
+            signature_cache: self.signature_cache(),
+        })
     }
 
-    fn build_server_verifier(
+    /// Returns the trust store for a SPIFFE verifier.
+    fn spiffe_store(&self, py: pyo3::Python<'_>) -> CryptographyResult<pyo3::Py<PyStore>> {
+        if self.certificate_transparency.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "Certificate Transparency is not supported by SPIFFE verifiers.",
+                ),
+            ));
+        }
+
+        match self.store.as_ref() {
+            Some(s) => Ok(s.clone_ref(py)),
+            None => Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "A SPIFFE verifier must have a trust store.",
+                ),
+            )),
+        }
+    }
+
+    /// Applies the builder's optional settings to a freshly constructed
+    /// policy.
+    fn configure_policy<'a>(
         &self,
         py: pyo3::Python<'_>,
-        subject: pyo3::PyObject,
-    ) -> CryptographyResult<PyServerVerifier> {
-        let store = match self.store.as_ref() {
-            Some(s) => s.clone_ref(py),
-            None => {
+        policy: &mut Policy<'a, PyCryptoOps>,
+        owner: &'a PolicyOwner,
+    ) {
+        if let Some(model) = self.validation_model {
+            policy.validation_model = model;
+        }
+        if let Some(skew) = self.clock_skew {
+            policy.clock_skew = skew;
+        }
+        policy.revocation = owner.crls.as_ref().map(|crls| {
+            RevocationPolicy::new(
+                crls.iter()
+                    .map(|c| c.get().owned.borrow_dependent().clone())
+                    .collect(),
+                self.revocation_mode,
+            )
+        });
+        if let Some(skew) = self.ocsp_clock_skew {
+            policy.ocsp_clock_skew = skew;
+        }
+        policy.certificate_policies = self.certificate_policies.clone();
+        if let Some(partial_chain) = self.partial_chain {
+            policy.partial_chain = partial_chain;
+        }
+        policy.spki_pins = self.spki_pins.clone();
+        if let Some(limit) = self.max_signature_checks {
+            policy.max_signature_checks = limit;
+        }
+        if let Some(limit) = self.max_candidates {
+            policy.max_candidates = limit;
+        }
+        if let Some(limit) = self.max_issuer_fetches {
+            policy.max_issuer_fetches = limit;
+        }
+        policy.max_path_building_time = self.max_path_building_time;
+        if let Some(bits) = self.minimum_rsa_modulus {
+            policy.minimum_rsa_modulus = bits;
+        }
+        if let Some(algorithms) = self.signature_algorithms.as_ref() {
+            policy.permitted_signature_algorithms = Arc::clone(algorithms);
+        }
+        if self.public_key_algorithms.is_some() || self.ec_curves.is_some() {
+            policy.permitted_public_key_algorithms = Arc::new(spki_algorithms(
+                self.public_key_algorithms
+                    .as_deref()
+                    .unwrap_or(&[oid::RSA_OID, oid::EC_OID]),
+                self.ec_curves.as_deref().unwrap_or(&[
+                    oid::EC_SECP256R1,
+                    oid::EC_SECP384R1,
+                    oid::EC_SECP521R1,
+                ]),
+            ));
+        }
+        policy.certificate_transparency = self.certificate_transparency.as_ref().map(|ct| {
+            CTPolicy::new(
+                ct.logs
+                    .iter()
+                    .map(|l| {
+                        let l = l.get();
+                        CTLog {
+                            id: l.log_id,
+                            key: l.pkey.clone(),
+                            operator: l.operator.clone(),
+                            not_before: l.not_before.clone(),
+                            not_after: l.not_after.clone(),
+                        }
+                    })
+                    .collect(),
+                ct.minimum_scts,
+                ct.minimum_operators,
+            )
+        });
+        for o in &self.extension_overrides {
+            let extension_policy = if o.ca {
+                &mut policy.ca_extension_policy
+            } else {
+                &mut policy.ee_extension_policy
+            };
+            extension_policy.permit(&o.oid);
+            if let Some(presence) = o.presence {
+                extension_policy.set_presence(&o.oid, presence);
+            }
+            if let Some(criticality) = o.criticality {
+                extension_policy.set_criticality(&o.oid, criticality);
+            }
+            if let Some(validator) = o.validator.as_ref() {
+                extension_policy.set_validator(
+                    &o.oid,
+                    py_extension_validator(&o.oid, validator.clone_ref(py)),
+                );
+            }
+        }
+    }
+
+    fn with_extension_override(
+        &self,
+        py: pyo3::Python<'_>,
+        ca: bool,
+        oid: pyo3::Bound<'_, pyo3::PyAny>,
+        presence: Option<pyo3::Bound<'_, pyo3::PyAny>>,
+        criticality: Option<pyo3::Bound<'_, pyo3::PyAny>>,
+        validator: Option<pyo3::Bound<'_, pyo3::PyAny>>,
+    ) -> CryptographyResult<PolicyBuilder> {
+        let oid = py_oid_to_oid(oid)?;
+        let kind = if ca { "CA" } else { "EE" };
+        if self
+            .extension_overrides
+            .iter()
+            .any(|o| o.ca == ca && o.oid == oid)
+        {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "The {kind} extension policy for {oid} may only be set once."
+                )),
+            ));
+        }
+        if let Some(validator) = validator.as_ref() {
+            if !validator.is_callable() {
                 return Err(CryptographyError::from(
-                    pyo3::exceptions::PyValueError::new_err(
-                        "A server verifier must have a trust store.",
-                    ),
+                    pyo3::exceptions::PyTypeError::new_err("validator must be callable"),
                 ));
             }
-        };
-
-        let time = match self.time.as_ref() {
-            Some(t) => t.clone(),
-            None => datetime_now(py)?,
-        };
-        let subject_owner = build_subject_owner(py, &subject)?;
-
-        let policy = OwnedPolicy::try_new(subject_owner, |subject_owner| {
-            let subject = build_subject(py, subject_owner)?;
-            Ok::<PyCryptoPolicy<'_>, pyo3::PyErr>(PyCryptoPolicy(Policy::server(
-                PyCryptoOps {},
-                subject,
-                time,
-                self.max_chain_depth,
-            )))
-        })?;
+        }
 
-        Ok(PyServerVerifier {
-            py_subject: subject,
-            policy,
-            store,
-        })
+        let mut builder = self.py_clone(py);
+        builder.extension_overrides.push(ExtensionOverride {
+            ca,
+            oid,
+            presence: presence.map(|p| py_to_presence(py, &p)).transpose()?,
+            criticality: criticality.map(|c| py_to_criticality(py, &c)).transpose()?,
+            validator: validator.map(|v| v.unbind()),
+        });
+        Ok(builder)
     }
 }
 
-struct PyCryptoPolicy<'a>(Policy<'a, PyCryptoOps>);
-
-/// This enum exists solely to provide heterogeneously typed ownership for `OwnedPolicy`.
-enum SubjectOwner {
-    // TODO: Switch this to `Py<PyString>` once Pyo3's `to_str()` preserves a
-    // lifetime relationship between an a `PyString` and its borrowed `&str`
-    // reference in all limited API builds. PyO3 can't currently do that in
-    // older limited API builds because it needs `PyUnicode_AsUTF8AndSize` to do
-    // so, which was only stabilized with 3.10.
-    DNSName(String),
-    IPAddress(pyo3::Py<pyo3::types::PyBytes>),
-}
-
-self_cell::self_cell!(
-    struct OwnedPolicy {
-        owner: SubjectOwner,
-
-        #[covariant]
-        dependent: PyCryptoPolicy,
-    }
-);
-
-#[pyo3::pyclass(
-    frozen,
-    name = "VerifiedClient",
-    module = "cryptography.hazmat.bindings._rust.x509"
-)]
-struct PyVerifiedClient {
-    #[pyo3(get)]
-    subjects: pyo3::Py<pyo3::PyAny>,
-    #[pyo3(get)]
-    chain: pyo3::Py<pyo3::types::PyList>,
+/// Returns the signature `AlgorithmIdentifier`s permitted by allowing the
+/// signature algorithm `oid`, or `None` if it is not supported for path
+/// validation. RSASSA-PSS is permitted with SHA-256, SHA-384 and SHA-512,
+/// each with a matching MGF1 hash and salt length.
+fn signature_algorithms_for_oid(
+    oid: &asn1::ObjectIdentifier,
+) -> Option<Vec<AlgorithmIdentifier<'static>>> {
+    let params = match *oid {
+        oid::RSA_WITH_SHA1_OID => AlgorithmParameters::RsaWithSha1(Some(())),
+        oid::RSA_WITH_SHA224_OID => AlgorithmParameters::RsaWithSha224(Some(())),
+        oid::RSA_WITH_SHA256_OID => AlgorithmParameters::RsaWithSha256(Some(())),
+        oid::RSA_WITH_SHA384_OID => AlgorithmParameters::RsaWithSha384(Some(())),
+        oid::RSA_WITH_SHA512_OID => AlgorithmParameters::RsaWithSha512(Some(())),
+        oid::RSA_WITH_SHA3_224_OID => AlgorithmParameters::RsaWithSha3_224(Some(())),
+        oid::RSA_WITH_SHA3_256_OID => AlgorithmParameters::RsaWithSha3_256(Some(())),
+        oid::RSA_WITH_SHA3_384_OID => AlgorithmParameters::RsaWithSha3_384(Some(())),
+        oid::RSA_WITH_SHA3_512_OID => AlgorithmParameters::RsaWithSha3_512(Some(())),
+        oid::ECDSA_WITH_SHA224_OID => AlgorithmParameters::EcDsaWithSha224(None),
+        oid::ECDSA_WITH_SHA256_OID => AlgorithmParameters::EcDsaWithSha256(None),
+        oid::ECDSA_WITH_SHA384_OID => AlgorithmParameters::EcDsaWithSha384(None),
+        oid::ECDSA_WITH_SHA512_OID => AlgorithmParameters::EcDsaWithSha512(None),
+        oid::ECDSA_WITH_SHA3_224_OID => AlgorithmParameters::EcDsaWithSha3_224,
+        oid::ECDSA_WITH_SHA3_256_OID => AlgorithmParameters::EcDsaWithSha3_256,
+        oid::ECDSA_WITH_SHA3_384_OID => AlgorithmParameters::EcDsaWithSha3_384,
+        oid::ECDSA_WITH_SHA3_512_OID => AlgorithmParameters::EcDsaWithSha3_512,
+        oid::ED25519_OID => AlgorithmParameters::Ed25519,
+        oid::ED448_OID => AlgorithmParameters::Ed448,
+        oid::RSASSA_PSS_OID => {
+            return Some(
+                [
+                    (PSS_SHA256_HASH_ALG, PSS_SHA256_MASK_GEN_ALG, 32),
+                    (PSS_SHA384_HASH_ALG, PSS_SHA384_MASK_GEN_ALG, 48),
+                    (PSS_SHA512_HASH_ALG, PSS_SHA512_MASK_GEN_ALG, 64),
+                ]
+                .into_iter()
+                .map(
+                    |(hash_algorithm, mask_gen_algorithm, salt_length)| AlgorithmIdentifier {
+                        oid: asn1::DefinedByMarker::marker(),
+                        params: AlgorithmParameters::RsaPss(Some(Box::new(RsaPssParameters {
+                            hash_algorithm,
+                            mask_gen_algorithm,
+                            salt_length,
+                            _trailer_field: 1,
+                        }))),
+                    },
+                )
+                .collect(),
+            )
+        }
+        _ => return None,
+    };
+    Some(vec![AlgorithmIdentifier {
+        oid: asn1::DefinedByMarker::marker(),
+        params,
+    }])
 }
 
-#[pyo3::pyclass(
-    frozen,
-    name = "ClientVerifier",
-    module = "cryptography.hazmat.bindings._rust.x509"
-)]
-struct PyClientVerifier {
-    policy: PyCryptoPolicy<'static>,
-    #[pyo3(get)]
-    store: pyo3::Py<PyStore>,
+fn is_supported_spki_algorithm(oid: &asn1::ObjectIdentifier) -> bool {
+    matches!(
+        *oid,
+        oid::RSA_OID | oid::RSASSA_PSS_OID | oid::EC_OID | oid::ED25519_OID | oid::ED448_OID
+    )
 }
 
-impl PyClientVerifier {
-    fn as_policy(&self) -> &Policy<'_, PyCryptoOps> {
-        &self.policy.0
+/// Builds the set of permitted SPKI `AlgorithmIdentifier`s from the permitted
+/// public key algorithms and, for EC keys, the permitted named curves.
+fn spki_algorithms(
+    algorithms: &[asn1::ObjectIdentifier],
+    curves: &[asn1::ObjectIdentifier],
+) -> HashSet<AlgorithmIdentifier<'static>> {
+    let mut params = vec![];
+    for algorithm in algorithms {
+        match *algorithm {
+            oid::RSA_OID => params.push(AlgorithmParameters::Rsa(Some(()))),
+            oid::RSASSA_PSS_OID => params.push(AlgorithmParameters::RsaPss(None)),
+            oid::EC_OID => params.extend(
+                curves
+                    .iter()
+                    .map(|c| AlgorithmParameters::Ec(EcParameters::NamedCurve(c.clone()))),
+            ),
+            oid::ED25519_OID => params.push(AlgorithmParameters::Ed25519),
+            oid::ED448_OID => params.push(AlgorithmParameters::Ed448),
+            // Unsupported algorithms are rejected by the builder.
+            _ => {}
+        }
     }
+    params
+        .into_iter()
+        .map(|params| AlgorithmIdentifier {
+            oid: asn1::DefinedByMarker::marker(),
+            params,
+        })
+        .collect()
 }
 
-#[pyo3::pymethods]
-impl PyClientVerifier {
-    #[getter]
-    fn validation_time<'p>(
-        &self,
-        py: pyo3::Python<'p>,
-    ) -> pyo3::PyResult<pyo3::Bound<'p, pyo3::PyAny>> {
-        datetime_to_py(py, &self.as_policy().validation_time)
+fn py_curve_to_oid(
+    py: pyo3::Python<'_>,
+    py_curve: &pyo3::Bound<'_, pyo3::PyAny>,
+) -> CryptographyResult<asn1::ObjectIdentifier> {
+    if !py_curve.is_instance(&types::ELLIPTIC_CURVE.get(py)?)? {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err("curve must be an EllipticCurve instance"),
+        ));
     }
 
-    #[getter]
-    fn max_chain_depth(&self) -> u8 {
-        self.as_policy().max_chain_depth
-    }
+    let py_curve_name = py_curve.getattr(pyo3::intern!(py, "name"))?;
+    let curve_oid = match &*py_curve_name.extract::<String>()? {
+        "secp192r1" => oid::EC_SECP192R1,
+        "secp224r1" => oid::EC_SECP224R1,
+        "secp256r1" => oid::EC_SECP256R1,
+        "secp384r1" => oid::EC_SECP384R1,
+        "secp521r1" => oid::EC_SECP521R1,
 
-    fn verify(
-        &self,
-        py: pyo3::Python<'_>,
-        leaf: pyo3::Py<PyCertificate>,
-        intermediates: Vec<pyo3::Py<PyCertificate>>,
-    ) -> CryptographyResult<PyVerifiedClient> {
-        let policy = self.as_policy();
-        let store = self.store.get();
+        "secp256k1" => oid::EC_SECP256K1,
 
-        let chain = cryptography_x509_verification::verify(
-            &VerificationCertificate::new(
-                leaf.get().raw.borrow_dependent().clone(),
-                leaf.clone_ref(py),
-            ),
-            intermediates.iter().map(|i| {
-                VerificationCertificate::new(
-                    i.get().raw.borrow_dependent().clone(),
-                    i.clone_ref(py),
-                )
-            }),
-            policy,
-            store.raw.borrow_dependent(),
-        )
-        .map_err(|e| VerificationError::new_err(format!("validation failed: {e:?}")))?;
+        "sect233r1" => oid::EC_SECT233R1,
+        "sect283r1" => oid::EC_SECT283R1,
+        "sect409r1" => oid::EC_SECT409R1,
+        "sect571r1" => oid::EC_SECT571R1,
 
-        let py_chain = pyo3::types::PyList::empty_bound(py);
-        for c in &chain {
-            py_chain.append(c.extra())?;
-        }
+        "sect163r2" => oid::EC_SECT163R2,
 
-        // NOTE: These `unwrap()`s cannot fail, since the underlying policy
-        // enforces the presence of a SAN and the well-formedness of the
-        // extension set.
-        let leaf_san = &chain[0]
-            .certificate()
-            .extensions()
-            .unwrap()
-            .get_extension(&SUBJECT_ALTERNATIVE_NAME_OID)
-            .unwrap();
+        "sect163k1" => oid::EC_SECT163K1,
+        "sect233k1" => oid::EC_SECT233K1,
+        "sect283k1" => oid::EC_SECT283K1,
+        "sect409k1" => oid::EC_SECT409K1,
+        "sect571k1" => oid::EC_SECT571K1,
 
-        let leaf_gns = leaf_san.value::<SubjectAlternativeName<'_>>()?;
-        let py_gns = parse_general_names(py, &leaf_gns)?;
+        "brainpoolP256r1" => oid::EC_BRAINPOOLP256R1,
+        "brainpoolP384r1" => oid::EC_BRAINPOOLP384R1,
+        "brainpoolP512r1" => oid::EC_BRAINPOOLP512R1,
 
-        Ok(PyVerifiedClient {
-            subjects: py_gns,
-            chain: py_chain.unbind(),
-        })
+        curve_name => {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "Curve {curve_name} is not supported"
+                )),
+            ));
+        }
+    };
+    Ok(curve_oid)
+}
+
+fn py_to_validation_model(
+    py: pyo3::Python<'_>,
+    model: &pyo3::Bound<'_, pyo3::PyAny>,
+) -> CryptographyResult<ValidationModel> {
+    if !model.is_instance(&types::VERIFICATION_VALIDATION_MODEL.get(py)?)? {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err("validation model must be a ValidationModel"),
+        ));
+    }
+    match &*model
+        .getattr(pyo3::intern!(py, "value"))?
+        .extract::<String>()?
+    {
+        "chain" => Ok(ValidationModel::Chain),
+        _ => Ok(ValidationModel::Shell),
     }
 }
 
-#[pyo3::pyclass(
-    frozen,
-    name = "ServerVerifier",
-    module = "cryptography.hazmat.bindings._rust.x509"
-)]
-struct PyServerVerifier {
-    #[pyo3(get, name = "subject")]
-    py_subject: pyo3::Py<pyo3::PyAny>,
-    policy: OwnedPolicy,
-    #[pyo3(get)]
-    store: pyo3::Py<PyStore>,
+fn py_to_tlsa_usage(
+    py: pyo3::Python<'_>,
+    usage: &pyo3::Bound<'_, pyo3::PyAny>,
+) -> CryptographyResult<TlsaUsage> {
+    if !usage.is_instance(&types::VERIFICATION_TLSA_USAGE.get(py)?)? {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err("usage must be a TLSAUsage"),
+        ));
+    }
+    match usage.getattr(pyo3::intern!(py, "value"))?.extract::<u8>()? {
+        0 => Ok(TlsaUsage::PkixTa),
+        1 => Ok(TlsaUsage::PkixEe),
+        2 => Ok(TlsaUsage::DaneTa),
+        _ => Ok(TlsaUsage::DaneEe),
+    }
 }
 
-impl PyServerVerifier {
-    fn as_policy(&self) -> &Policy<'_, PyCryptoOps> {
-        &self.policy.borrow_dependent().0
+fn py_to_tlsa_selector(
+    py: pyo3::Python<'_>,
+    selector: &pyo3::Bound<'_, pyo3::PyAny>,
+) -> CryptographyResult<TlsaSelector> {
+    if !selector.is_instance(&types::VERIFICATION_TLSA_SELECTOR.get(py)?)? {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err("selector must be a TLSASelector"),
+        ));
+    }
+    match selector
+        .getattr(pyo3::intern!(py, "value"))?
+        .extract::<u8>()?
+    {
+        0 => Ok(TlsaSelector::Certificate),
+        _ => Ok(TlsaSelector::SubjectPublicKeyInfo),
     }
 }
 
-#[pyo3::pymethods]
-impl PyServerVerifier {
-    #[getter]
-    fn validation_time<'p>(
-        &self,
-        py: pyo3::Python<'p>,
-    ) -> pyo3::PyResult<pyo3::Bound<'p, pyo3::PyAny>> {
-        datetime_to_py(py, &self.as_policy().validation_time)
+fn py_to_tlsa_matching_type(
+    py: pyo3::Python<'_>,
+    matching_type: &pyo3::Bound<'_, pyo3::PyAny>,
+) -> CryptographyResult<TlsaMatchingType> {
+    if !matching_type.is_instance(&types::VERIFICATION_TLSA_MATCHING_TYPE.get(py)?)? {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err("matching type must be a TLSAMatchingType"),
+        ));
+    }
+    match matching_type
+        .getattr(pyo3::intern!(py, "value"))?
+        .extract::<u8>()?
+    {
+        0 => Ok(TlsaMatchingType::Full),
+        1 => Ok(TlsaMatchingType::Sha256),
+        _ => Ok(TlsaMatchingType::Sha512),
     }
+}
 
-    #[getter]
-    fn max_chain_depth(&self) -> u8 {
-        self.as_policy().max_chain_depth
+fn py_to_presence(
+    py: pyo3::Python<'_>,
+    presence: &pyo3::Bound<'_, pyo3::PyAny>,
+) -> CryptographyResult<Presence> {
+    if !presence.is_instance(&types::VERIFICATION_EXTENSION_PRESENCE.get(py)?)? {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err("presence must be an ExtensionPresence"),
+        ));
+    }
+    match &*presence
+        .getattr(pyo3::intern!(py, "value"))?
+        .extract::<String>()?
+    {
+        "required" => Ok(Presence::Required),
+        "optional" => Ok(Presence::Optional),
+        _ => Ok(Presence::Prohibited),
     }
+}
 
-    fn verify<'p>(
-        &self,
-        py: pyo3::Python<'p>,
+fn py_to_criticality(
+    py: pyo3::Python<'_>,
+    criticality: &pyo3::Bound<'_, pyo3::PyAny>,
+) -> CryptographyResult<Criticality> {
+    if !criticality.is_instance(&types::VERIFICATION_CRITICALITY.get(py)?)? {
+        return Err(CryptographyError::from(
+            pyo3::exceptions::PyTypeError::new_err("criticality must be a Criticality"),
+        ));
+    }
+    match &*criticality
+        .getattr(pyo3::intern!(py, "value"))?
+        .extract::<String>()?
+    {
+        "critical" => Ok(Criticality::Critical),
+        "non_critical" => Ok(Criticality::NonCritical),
+        _ => Ok(Criticality::Agnostic),
+    }
+}
+
+/// Wraps a Python callable as an extension validator. The callable is passed
+/// the certificate and its `Extension` (or `None` if the extension is not
+/// present), and rejects the certificate by raising.
+fn py_extension_validator(
+    oid: &asn1::ObjectIdentifier,
+    validator: pyo3::PyObject,
+) -> CustomExtensionValidatorCallback<PyCryptoOps> {
+    let oid = oid.clone();
+    Arc::new(
+        move |_policy: &Policy<'_, PyCryptoOps>,
+              cert: &VerificationCertificate<'_, PyCryptoOps>,
+              extension: Option<&Extension<'_>>| {
+            pyo3::Python::with_gil(|py| -> pyo3::PyResult<()> {
+                let py_cert = cert.extra().bind(py);
+                let py_extension = match extension {
+                    Some(_) => Some(
+                        py_cert
+                            .getattr(pyo3::intern!(py, "extensions"))?
+                            .call_method1(
+                                pyo3::intern!(py, "get_extension_for_oid"),
+                                (oid_to_py_oid(py, &oid)?,),
+                            )?,
+                    ),
+                    None => None,
+                };
+                validator.call1(py, (py_cert, py_extension))?;
+                Ok(())
+            })
+            .map_err(|e| {
+                ValidationError::Other(format!("extension {oid} rejected by validator: {e}"))
+            })
+        },
+    )
+}
+
+#[pyo3::pymethods]
+impl PolicyBuilder {
+    #[new]
+    fn new() -> PolicyBuilder {
+        PolicyBuilder {
+            time: None,
+            validation_model: None,
+            clock_skew: None,
+            store: None,
+            max_chain_depth: None,
+            crls: None,
+            revocation_mode: RevocationMode::HardFail,
+            ocsp_clock_skew: None,
+            certificate_policies: None,
+            extension_overrides: vec![],
+            minimum_rsa_modulus: None,
+            signature_algorithms: None,
+            public_key_algorithms: None,
+            ec_curves: None,
+            certificate_transparency: None,
+            issuer_fetcher: None,
+            partial_chain: None,
+            spki_pins: None,
+            max_signature_checks: None,
+            max_candidates: None,
+            max_issuer_fetches: None,
+            max_path_building_time: None,
+            signature_cache_size: None,
+        }
+    }
+
+    fn time(
+        &self,
+        py: pyo3::Python<'_>,
+        new_time: pyo3::Bound<'_, pyo3::PyAny>,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.time.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The validation time may only be set once.",
+                ),
+            ));
+        }
+        Ok(PolicyBuilder {
+            time: Some(py_to_datetime(py, new_time)?),
+            ..self.py_clone(py)
+        })
+    }
+
+    fn validation_model(
+        &self,
+        py: pyo3::Python<'_>,
+        new_validation_model: pyo3::Bound<'_, pyo3::PyAny>,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.validation_model.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The validation model may only be set once.",
+                ),
+            ));
+        }
+        Ok(PolicyBuilder {
+            validation_model: Some(py_to_validation_model(py, &new_validation_model)?),
+            ..self.py_clone(py)
+        })
+    }
+
+    fn clock_skew(
+        &self,
+        py: pyo3::Python<'_>,
+        new_skew: Duration,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.clock_skew.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("The clock skew may only be set once."),
+            ));
+        }
+        Ok(PolicyBuilder {
+            clock_skew: Some(new_skew),
+            ..self.py_clone(py)
+        })
+    }
+
+    fn store(
+        &self,
+        py: pyo3::Python<'_>,
+        new_store: pyo3::Py<PyStore>,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.store.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("The trust store may only be set once."),
+            ));
+        }
+        Ok(PolicyBuilder {
+            store: Some(new_store),
+            ..self.py_clone(py)
+        })
+    }
+
+    fn max_chain_depth(
+        &self,
+        py: pyo3::Python<'_>,
+        new_max_chain_depth: u8,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.max_chain_depth.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The maximum chain depth may only be set once.",
+                ),
+            ));
+        }
+        Ok(PolicyBuilder {
+            max_chain_depth: Some(new_max_chain_depth),
+            ..self.py_clone(py)
+        })
+    }
+
+    fn max_signature_checks(
+        &self,
+        py: pyo3::Python<'_>,
+        new_max_signature_checks: usize,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.max_signature_checks.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The maximum signature checks may only be set once.",
+                ),
+            ));
+        }
+        Ok(PolicyBuilder {
+            max_signature_checks: Some(new_max_signature_checks),
+            ..self.py_clone(py)
+        })
+    }
+
+    fn max_candidates(
+        &self,
+        py: pyo3::Python<'_>,
+        new_max_candidates: usize,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.max_candidates.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The maximum candidates may only be set once.",
+                ),
+            ));
+        }
+        Ok(PolicyBuilder {
+            max_candidates: Some(new_max_candidates),
+            ..self.py_clone(py)
+        })
+    }
+
+    fn max_issuer_fetches(
+        &self,
+        py: pyo3::Python<'_>,
+        new_max_issuer_fetches: usize,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.max_issuer_fetches.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The maximum issuer fetches may only be set once.",
+                ),
+            ));
+        }
+        Ok(PolicyBuilder {
+            max_issuer_fetches: Some(new_max_issuer_fetches),
+            ..self.py_clone(py)
+        })
+    }
+
+    fn max_path_building_time(
+        &self,
+        py: pyo3::Python<'_>,
+        new_max_path_building_time: Duration,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.max_path_building_time.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The maximum path building time may only be set once.",
+                ),
+            ));
+        }
+        Ok(PolicyBuilder {
+            max_path_building_time: Some(new_max_path_building_time),
+            ..self.py_clone(py)
+        })
+    }
+
+    fn signature_cache_size(
+        &self,
+        py: pyo3::Python<'_>,
+        new_signature_cache_size: usize,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.signature_cache_size.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The signature cache size may only be set once.",
+                ),
+            ));
+        }
+        let new_signature_cache_size =
+            NonZeroUsize::new(new_signature_cache_size).ok_or_else(|| {
+                pyo3::exceptions::PyValueError::new_err(
+                    "The signature cache size must be a positive integer.",
+                )
+            })?;
+        Ok(PolicyBuilder {
+            signature_cache_size: Some(new_signature_cache_size),
+            ..self.py_clone(py)
+        })
+    }
+
+    #[pyo3(signature = (new_crls, *, soft_fail = false))]
+    fn crls(
+        &self,
+        py: pyo3::Python<'_>,
+        new_crls: Vec<pyo3::Py<PyCertificateRevocationList>>,
+        soft_fail: bool,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.crls.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("The CRLs may only be set once."),
+            ));
+        }
+        Ok(PolicyBuilder {
+            crls: Some(new_crls),
+            revocation_mode: if soft_fail {
+                RevocationMode::SoftFail
+            } else {
+                RevocationMode::HardFail
+            },
+            ..self.py_clone(py)
+        })
+    }
+
+    fn ocsp_clock_skew(
+        &self,
+        py: pyo3::Python<'_>,
+        new_skew: Duration,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.ocsp_clock_skew.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The OCSP clock skew may only be set once.",
+                ),
+            ));
+        }
+        Ok(PolicyBuilder {
+            ocsp_clock_skew: Some(new_skew),
+            ..self.py_clone(py)
+        })
+    }
+
+    #[pyo3(signature = (
+        initial_policy_set = None,
+        *,
+        explicit_policy = false,
+        policy_mapping_inhibit = false,
+        any_policy_inhibit = false,
+    ))]
+    fn certificate_policies(
+        &self,
+        py: pyo3::Python<'_>,
+        initial_policy_set: Option<Vec<pyo3::Bound<'_, pyo3::PyAny>>>,
+        explicit_policy: bool,
+        policy_mapping_inhibit: bool,
+        any_policy_inhibit: bool,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.certificate_policies.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "Certificate policy processing may only be configured once.",
+                ),
+            ));
+        }
+        let initial_policy_set = match initial_policy_set {
+            Some(oids) => Some(
+                oids.into_iter()
+                    .map(py_oid_to_oid)
+                    .collect::<pyo3::PyResult<HashSet<_>>>()?,
+            ),
+            None => None,
+        };
+        Ok(PolicyBuilder {
+            certificate_policies: Some(PolicyTreeConstraints {
+                initial_policy_set,
+                explicit_policy,
+                policy_mapping_inhibit,
+                any_policy_inhibit,
+            }),
+            ..self.py_clone(py)
+        })
+    }
+
+    fn minimum_rsa_modulus(
+        &self,
+        py: pyo3::Python<'_>,
+        new_minimum_rsa_modulus: usize,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.minimum_rsa_modulus.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The minimum RSA modulus may only be set once.",
+                ),
+            ));
+        }
+        Ok(PolicyBuilder {
+            minimum_rsa_modulus: Some(new_minimum_rsa_modulus),
+            ..self.py_clone(py)
+        })
+    }
+
+    fn signature_algorithms(
+        &self,
+        py: pyo3::Python<'_>,
+        new_signature_algorithms: Vec<pyo3::Bound<'_, pyo3::PyAny>>,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.signature_algorithms.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The signature algorithms may only be set once.",
+                ),
+            ));
+        }
+        let mut algorithms = HashSet::new();
+        for py_oid in new_signature_algorithms {
+            let oid = py_oid_to_oid(py_oid)?;
+            let Some(identifiers) = signature_algorithms_for_oid(&oid) else {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyValueError::new_err(format!(
+                        "Unsupported signature algorithm: {oid}"
+                    )),
+                ));
+            };
+            algorithms.extend(identifiers);
+        }
+        Ok(PolicyBuilder {
+            signature_algorithms: Some(Arc::new(algorithms)),
+            ..self.py_clone(py)
+        })
+    }
+
+    fn public_key_algorithms(
+        &self,
+        py: pyo3::Python<'_>,
+        new_public_key_algorithms: Vec<pyo3::Bound<'_, pyo3::PyAny>>,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.public_key_algorithms.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The public key algorithms may only be set once.",
+                ),
+            ));
+        }
+        let mut algorithms = vec![];
+        for py_oid in new_public_key_algorithms {
+            let oid = py_oid_to_oid(py_oid)?;
+            if !is_supported_spki_algorithm(&oid) {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyValueError::new_err(format!(
+                        "Unsupported public key algorithm: {oid}"
+                    )),
+                ));
+            }
+            algorithms.push(oid);
+        }
+        Ok(PolicyBuilder {
+            public_key_algorithms: Some(algorithms),
+            ..self.py_clone(py)
+        })
+    }
+
+    fn ec_curves(
+        &self,
+        py: pyo3::Python<'_>,
+        new_ec_curves: Vec<pyo3::Bound<'_, pyo3::PyAny>>,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.ec_curves.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("The EC curves may only be set once."),
+            ));
+        }
+        Ok(PolicyBuilder {
+            ec_curves: Some(
+                new_ec_curves
+                    .iter()
+                    .map(|c| py_curve_to_oid(py, c))
+                    .collect::<CryptographyResult<_>>()?,
+            ),
+            ..self.py_clone(py)
+        })
+    }
+
+    fn issuer_fetcher(
+        &self,
+        py: pyo3::Python<'_>,
+        new_issuer_fetcher: pyo3::PyObject,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.issuer_fetcher.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("The issuer fetcher may only be set once."),
+            ));
+        }
+        Ok(PolicyBuilder {
+            issuer_fetcher: Some(new_issuer_fetcher),
+            ..self.py_clone(py)
+        })
+    }
+
+    fn partial_chain(
+        &self,
+        py: pyo3::Python<'_>,
+        new_partial_chain: bool,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.partial_chain.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The partial chain mode may only be set once.",
+                ),
+            ));
+        }
+        Ok(PolicyBuilder {
+            partial_chain: Some(new_partial_chain),
+            ..self.py_clone(py)
+        })
+    }
+
+    fn spki_pins(
+        &self,
+        py: pyo3::Python<'_>,
+        new_spki_pins: pyo3::Bound<'_, pyo3::PyAny>,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.spki_pins.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("The SPKI pins may only be set once."),
+            ));
+        }
+        let mut pins = HashSet::new();
+        for pin in new_spki_pins.iter()? {
+            let pin = pin?.extract::<pyo3::pybacked::PyBackedBytes>()?;
+            if pin.len() != 32 {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyValueError::new_err("SPKI pins must be SHA-256 digests."),
+                ));
+            }
+            pins.insert(pin.to_vec());
+        }
+        if pins.is_empty() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("At least one SPKI pin is required."),
+            ));
+        }
+        Ok(PolicyBuilder {
+            spki_pins: Some(Arc::new(pins)),
+            ..self.py_clone(py)
+        })
+    }
+
+    #[pyo3(signature = (logs, *, minimum_scts = 2, minimum_operators = 2))]
+    fn certificate_transparency(
+        &self,
+        py: pyo3::Python<'_>,
+        logs: Vec<pyo3::Py<PyCertificateTransparencyLog>>,
+        minimum_scts: usize,
+        minimum_operators: usize,
+    ) -> CryptographyResult<PolicyBuilder> {
+        if self.certificate_transparency.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "The Certificate Transparency policy may only be set once.",
+                ),
+            ));
+        }
+        if minimum_operators > minimum_scts {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "minimum_operators must not be greater than minimum_scts",
+                ),
+            ));
+        }
+        Ok(PolicyBuilder {
+            certificate_transparency: Some(CTSettings {
+                logs,
+                minimum_scts,
+                minimum_operators,
+            }),
+            ..self.py_clone(py)
+        })
+    }
+
+    #[pyo3(signature = (oid, *, presence = None, criticality = None, validator = None))]
+    fn ca_extension(
+        &self,
+        py: pyo3::Python<'_>,
+        oid: pyo3::Bound<'_, pyo3::PyAny>,
+        presence: Option<pyo3::Bound<'_, pyo3::PyAny>>,
+        criticality: Option<pyo3::Bound<'_, pyo3::PyAny>>,
+        validator: Option<pyo3::Bound<'_, pyo3::PyAny>>,
+    ) -> CryptographyResult<PolicyBuilder> {
+        self.with_extension_override(py, true, oid, presence, criticality, validator)
+    }
+
+    #[pyo3(signature = (oid, *, presence = None, criticality = None, validator = None))]
+    fn ee_extension(
+        &self,
+        py: pyo3::Python<'_>,
+        oid: pyo3::Bound<'_, pyo3::PyAny>,
+        presence: Option<pyo3::Bound<'_, pyo3::PyAny>>,
+        criticality: Option<pyo3::Bound<'_, pyo3::PyAny>>,
+        validator: Option<pyo3::Bound<'_, pyo3::PyAny>>,
+    ) -> CryptographyResult<PolicyBuilder> {
+        self.with_extension_override(py, false, oid, presence, criticality, validator)
+    }
+
+    fn build_client_verifier(&self, py: pyo3::Python<'_>) -> CryptographyResult<PyClientVerifier> {
+        self.build_subjectless_verifier(py, "client", Policy::client)
+    }
+
+    fn build_code_signing_verifier(
+        &self,
+        py: pyo3::Python<'_>,
+    ) -> CryptographyResult<PyClientVerifier> {
+        self.build_subjectless_verifier(py, "code signing", Policy::code_signing)
+    }
+
+    fn build_time_stamping_verifier(
+        &self,
+        py: pyo3::Python<'_>,
+    ) -> CryptographyResult<PyClientVerifier> {
+        self.build_subjectless_verifier(py, "time stamping", Policy::time_stamping)
+    }
+
+    fn build_email_protection_verifier(
+        &self,
+        py: pyo3::Python<'_>,
+        subject: pyo3::PyObject,
+    ) -> CryptographyResult<PyServerVerifier> {
+        if self.certificate_transparency.is_some() {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err(
+                    "Certificate Transparency is only supported by server verifiers.",
+                ),
+            ));
+        }
+
+        let store = match self.store.as_ref() {
+            Some(s) => s.clone_ref(py),
+            None => {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyValueError::new_err(
+                        "An email protection verifier must have a trust store.",
+                    ),
+                ));
+            }
+        };
+
+        let time = match self.time.as_ref() {
+            Some(t) => t.clone(),
+            None => datetime_now(py)?,
+        };
+
+        let py_subject = subject.bind(py);
+        if !py_subject.is_instance(&types::RFC822_NAME.get(py)?)? {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyTypeError::new_err("unsupported subject type"),
+            ));
+        }
+        let subject_owner = SubjectOwner::Email(
+            py_subject
+                .getattr(pyo3::intern!(py, "value"))?
+                .extract::<String>()?,
+        );
+
+        let policy = OwnedPolicy::try_new(self.policy_owner(py, Some(subject_owner)), |owner| {
+            // NOTE: This `unwrap()` and `unreachable!()` cannot be hit, since
+            // we always construct this policy's owner with an email subject.
+            let Subject::Email(subject) = build_subject(py, owner.subject.as_ref().unwrap())?
+            else {
+                unreachable!()
+            };
+            let mut policy =
+                Policy::email_protection(PyCryptoOps {}, subject, time, self.max_chain_depth);
+            self.configure_policy(py, &mut policy, owner);
+            Ok::<PyCryptoPolicy<'_>, pyo3::PyErr>(PyCryptoPolicy(policy))
+        })?;
+
+        Ok(PyServerVerifier {
+            py_subject: subject,
+            policy,
+            store,
+            issuer_fetcher: self.issuer_fetcher.as_ref().map(|f| f.clone_ref(py)),
+            signature_cache: self.signature_cache(),
+        })
+    }
+
+    fn build_spiffe_server_verifier(
+        &self,
+        py: pyo3::Python<'_>,
+        subject: pyo3::PyObject,
+    ) -> CryptographyResult<PyServerVerifier> {
+        let store = self.spiffe_store(py)?;
+        let time = match self.time.as_ref() {
+            Some(t) => t.clone(),
+            None => datetime_now(py)?,
+        };
+        let subject_owner = build_spiffe_subject_owner(py, &subject)?;
+
+        let policy = OwnedPolicy::try_new(self.policy_owner(py, Some(subject_owner)), |owner| {
+            // NOTE: This `unwrap()` cannot fail, since we always construct
+            // this policy's owner with a subject.
+            let subject = build_subject(py, owner.subject.as_ref().unwrap())?;
+            let mut policy =
+                Policy::spiffe_server(PyCryptoOps {}, Some(subject), time, self.max_chain_depth);
+            self.configure_policy(py, &mut policy, owner);
+            Ok::<PyCryptoPolicy<'_>, pyo3::PyErr>(PyCryptoPolicy(policy))
+        })?;
+
+        Ok(PyServerVerifier {
+            py_subject: subject,
+            policy,
+            store,
+            issuer_fetcher: self.issuer_fetcher.as_ref().map(|f| f.clone_ref(py)),
+            signature_cache: self.signature_cache(),
+        })
+    }
+
+    #[pyo3(signature = (subject = None))]
+    fn build_spiffe_client_verifier(
+        &self,
+        py: pyo3::Python<'_>,
+        subject: Option<pyo3::PyObject>,
+    ) -> CryptographyResult<PyClientVerifier> {
+        let store = self.spiffe_store(py)?;
+        let time = match self.time.as_ref() {
+            Some(t) => t.clone(),
+            None => datetime_now(py)?,
+        };
+        let subject_owner = subject
+            .map(|s| build_spiffe_subject_owner(py, &s))
+            .transpose()?;
+
+        let policy = OwnedPolicy::try_new(self.policy_owner(py, subject_owner), |owner| {
+            let subject = owner
+                .subject
+                .as_ref()
+                .map(|s| build_subject(py, s))
+                .transpose()?;
+            let mut policy =
+                Policy::spiffe_client(PyCryptoOps {}, subject, time, self.max_chain_depth);
+            self.configure_policy(py, &mut policy, owner);
+            Ok::<PyCryptoPolicy<'_>, pyo3::PyErr>(PyCryptoPolicy(policy))
+        })?;
+
+        Ok(PyClientVerifier {
+            policy,
+            store,
+            issuer_fetcher: self.issuer_fetcher.as_ref().map(|f| f.clone_ref(py)),
+            signature_cache: self.signature_cache(),
+        })
+    }
+
+    fn build_server_verifier(
+        &self,
+        py: pyo3::Python<'_>,
+        subject: pyo3::PyObject,
+    ) -> CryptographyResult<PyServerVerifier> {
+        let store = match self.store.as_ref() {
+            Some(s) => s.clone_ref(py),
+            None => {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyValueError::new_err(
+                        "A server verifier must have a trust store.",
+                    ),
+                ));
+            }
+        };
+
+        let time = match self.time.as_ref() {
+            Some(t) => t.clone(),
+            None => datetime_now(py)?,
+        };
+        let subject_owner = build_subject_owner(py, &subject)?;
+
+        let policy = OwnedPolicy::try_new(self.policy_owner(py, Some(subject_owner)), |owner| {
+            // NOTE: This `unwrap()` cannot fail, since we always
+            // construct a server policy's owner with a subject.
+            let subject = build_subject(py, owner.subject.as_ref().unwrap())?;
+            let mut policy = Policy::server(PyCryptoOps {}, subject, time, self.max_chain_depth);
+            self.configure_policy(py, &mut policy, owner);
+            Ok::<PyCryptoPolicy<'_>, pyo3::PyErr>(PyCryptoPolicy(policy))
+        })?;
+
+        Ok(PyServerVerifier {
+            py_subject: subject,
+            policy,
+            store,
+            issuer_fetcher: self.issuer_fetcher.as_ref().map(|f| f.clone_ref(py)),
+            signature_cache: self.signature_cache(),
+        })
+    }
+}
+
+struct PyCryptoPolicy<'a>(Policy<'a, PyCryptoOps>);
+
+/// This enum exists solely to provide heterogeneously typed ownership for `PolicyOwner`.
+enum SubjectOwner {
+    // TODO: Switch this to `Py<PyString>` once Pyo3's `to_str()` preserves a
+    // lifetime relationship between an a `PyString` and its borrowed `&str`
+    // reference in all limited API builds. PyO3 can't currently do that in
+    // older limited API builds because it needs `PyUnicode_AsUTF8AndSize` to do
+    // so, which was only stabilized with 3.10.
+    DNSName(String),
+    IPAddress(pyo3::Py<pyo3::types::PyBytes>),
+    Email(String),
+    Uri(String),
+}
+
+/// This struct exists solely to own the Python objects that an `OwnedPolicy`
+/// borrows from.
+struct PolicyOwner {
+    subject: Option<SubjectOwner>,
+    crls: Option<Vec<pyo3::Py<PyCertificateRevocationList>>>,
+}
+
+self_cell::self_cell!(
+    struct OwnedPolicy {
+        owner: PolicyOwner,
+
+        #[covariant]
+        dependent: PyCryptoPolicy,
+    }
+);
+
+#[pyo3::pyclass(
+    frozen,
+    name = "VerifiedClient",
+    module = "cryptography.hazmat.bindings._rust.x509"
+)]
+struct PyVerifiedClient {
+    #[pyo3(get)]
+    subjects: pyo3::Py<pyo3::PyAny>,
+    #[pyo3(get)]
+    chain: pyo3::Py<pyo3::types::PyList>,
+    #[pyo3(get)]
+    trust_anchor: Option<pyo3::Py<PyTrustAnchor>>,
+    #[pyo3(get)]
+    policies: pyo3::Py<pyo3::PyAny>,
+}
+
+#[pyo3::pyclass(
+    frozen,
+    name = "VerificationDiagnostic",
+    module = "cryptography.hazmat.bindings._rust.x509"
+)]
+struct PyVerificationDiagnostic {
+    #[pyo3(get)]
+    certificate: pyo3::Py<PyCertificate>,
+    #[pyo3(get)]
+    depth: u8,
+    #[pyo3(get)]
+    reason: pyo3::Py<pyo3::PyAny>,
+    #[pyo3(get)]
+    message: String,
+    #[pyo3(get)]
+    candidates: pyo3::Py<pyo3::types::PyList>,
+}
+
+#[pyo3::pyclass(
+    frozen,
+    name = "VerifiedChain",
+    module = "cryptography.hazmat.bindings._rust.x509"
+)]
+struct PyVerifiedChain {
+    #[pyo3(get)]
+    chain: pyo3::Py<pyo3::types::PyList>,
+    #[pyo3(get)]
+    trust_anchor: Option<pyo3::Py<PyTrustAnchor>>,
+    #[pyo3(get)]
+    policies: pyo3::Py<pyo3::PyAny>,
+}
+
+#[pyo3::pyclass(
+    frozen,
+    name = "ClientVerifier",
+    module = "cryptography.hazmat.bindings._rust.x509"
+)]
+struct PyClientVerifier {
+    policy: OwnedPolicy,
+    #[pyo3(get)]
+    store: pyo3::Py<PyStore>,
+    issuer_fetcher: Option<pyo3::PyObject>,
+    signature_cache: Option<Arc<SignatureCache>>,
+}
+
+impl PyClientVerifier {
+    fn as_policy(&self) -> &Policy<'_, PyCryptoOps> {
+        &self.policy.borrow_dependent().0
+    }
+}
+
+#[pyo3::pymethods]
+impl PyClientVerifier {
+    #[getter]
+    fn validation_time<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> pyo3::PyResult<pyo3::Bound<'p, pyo3::PyAny>> {
+        datetime_to_py(py, &self.as_policy().validation_time)
+    }
+
+    #[getter]
+    fn max_chain_depth(&self) -> u8 {
+        self.as_policy().max_chain_depth
+    }
+
+    #[pyo3(signature = (leaf, intermediates, ocsp_responses = None))]
+    fn verify(
+        &self,
+        py: pyo3::Python<'_>,
+        leaf: pyo3::Py<PyCertificate>,
+        intermediates: Vec<pyo3::Py<PyCertificate>>,
+        ocsp_responses: Option<Vec<pyo3::Py<PyOCSPResponse>>>,
+    ) -> CryptographyResult<PyVerifiedClient> {
+        let ocsp_responses = ocsp_responses.unwrap_or_default();
+        verify_chain(
+            py,
+            self.as_policy(),
+            self.store.get(),
+            self.issuer_fetcher.as_ref(),
+            self.signature_cache.as_deref(),
+            &leaf,
+            &intermediates,
+            &ocsp_responses,
+            |py, verified| verified_client(py, self.store.get(), verified),
+        )
+    }
+
+    #[pyo3(signature = (
+        leaf,
+        intermediates,
+        ocsp_responses = None,
+        *,
+        limit = DEFAULT_VERIFY_ALL_LIMIT,
+        key = None,
+    ))]
+    fn verify_all<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        leaf: pyo3::Py<PyCertificate>,
+        intermediates: Vec<pyo3::Py<PyCertificate>>,
+        ocsp_responses: Option<Vec<pyo3::Py<PyOCSPResponse>>>,
+        limit: usize,
+        key: Option<pyo3::PyObject>,
+    ) -> CryptographyResult<pyo3::Bound<'p, pyo3::types::PyList>> {
+        let ocsp_responses = ocsp_responses.unwrap_or_default();
+        let to_py = |py: pyo3::Python<'_>,
+                     verified: &VerifiedChain<'_, PyCryptoOps>|
+         -> CryptographyResult<pyo3::PyObject> {
+            Ok(pyo3::Bound::new(py, verified_client(py, self.store.get(), verified)?)?.into_py(py))
+        };
+        let result = verify_chains(
+            py,
+            self.as_policy(),
+            self.store.get(),
+            self.issuer_fetcher.as_ref(),
+            self.signature_cache.as_deref(),
+            &leaf,
+            &intermediates,
+            &ocsp_responses,
+            verify_all_limit(limit)?,
+            key.as_ref().map(|key| ChainRanking { key, to_py: &to_py }),
+            |py, chains| {
+                let result = pyo3::types::PyList::empty_bound(py);
+                for verified in chains {
+                    result.append(to_py(py, verified)?)?;
+                }
+                Ok(result.unbind())
+            },
+        )?;
+        Ok(result.into_bound(py))
+    }
+
+    #[pyo3(signature = (items, *, max_workers = None))]
+    fn verify_many<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        items: Vec<BatchItem>,
+        max_workers: Option<usize>,
+    ) -> CryptographyResult<pyo3::Bound<'p, pyo3::types::PyList>> {
+        verify_many(
+            py,
+            self.as_policy(),
+            self.store.get(),
+            self.issuer_fetcher.as_ref(),
+            self.signature_cache.as_deref(),
+            &items,
+            max_workers,
+            |py, verified| Ok(verified_client(py, self.store.get(), verified)?.into_py(py)),
+        )
+    }
+}
+
+#[pyo3::pyclass(
+    frozen,
+    name = "ServerVerifier",
+    module = "cryptography.hazmat.bindings._rust.x509"
+)]
+struct PyServerVerifier {
+    #[pyo3(get, name = "subject")]
+    py_subject: pyo3::Py<pyo3::PyAny>,
+    policy: OwnedPolicy,
+    #[pyo3(get)]
+    store: pyo3::Py<PyStore>,
+    issuer_fetcher: Option<pyo3::PyObject>,
+    signature_cache: Option<Arc<SignatureCache>>,
+}
+
+impl PyServerVerifier {
+    fn as_policy(&self) -> &Policy<'_, PyCryptoOps> {
+        &self.policy.borrow_dependent().0
+    }
+}
+
+#[pyo3::pymethods]
+impl PyServerVerifier {
+    #[getter]
+    fn validation_time<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> pyo3::PyResult<pyo3::Bound<'p, pyo3::PyAny>> {
+        datetime_to_py(py, &self.as_policy().validation_time)
+    }
+
+    #[getter]
+    fn max_chain_depth(&self) -> u8 {
+        self.as_policy().max_chain_depth
+    }
+
+    #[pyo3(signature = (leaf, intermediates, ocsp_responses = None))]
+    fn verify<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        leaf: pyo3::Py<PyCertificate>,
+        intermediates: Vec<pyo3::Py<PyCertificate>>,
+        ocsp_responses: Option<Vec<pyo3::Py<PyOCSPResponse>>>,
+    ) -> CryptographyResult<pyo3::Bound<'p, pyo3::types::PyList>> {
+        let ocsp_responses = ocsp_responses.unwrap_or_default();
+        let result = verify_chain(
+            py,
+            self.as_policy(),
+            self.store.get(),
+            self.issuer_fetcher.as_ref(),
+            self.signature_cache.as_deref(),
+            &leaf,
+            &intermediates,
+            &ocsp_responses,
+            |py, verified| {
+                let result = pyo3::types::PyList::empty_bound(py);
+                for c in &verified.chain {
+                    result.append(c.extra())?;
+                }
+                Ok(result.unbind())
+            },
+        )?;
+        Ok(result.into_bound(py))
+    }
+
+    #[pyo3(signature = (leaf, intermediates, ocsp_responses = None))]
+    fn verify_chain(
+        &self,
+        py: pyo3::Python<'_>,
+        leaf: pyo3::Py<PyCertificate>,
+        intermediates: Vec<pyo3::Py<PyCertificate>>,
+        ocsp_responses: Option<Vec<pyo3::Py<PyOCSPResponse>>>,
+    ) -> CryptographyResult<PyVerifiedChain> {
+        let ocsp_responses = ocsp_responses.unwrap_or_default();
+        verify_chain(
+            py,
+            self.as_policy(),
+            self.store.get(),
+            self.issuer_fetcher.as_ref(),
+            self.signature_cache.as_deref(),
+            &leaf,
+            &intermediates,
+            &ocsp_responses,
+            |py, verified| verified_chain(py, self.store.get(), verified),
+        )
+    }
+
+    #[pyo3(signature = (leaf, intermediates, tlsa_records, ocsp_responses = None))]
+    fn verify_dane<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        leaf: pyo3::Py<PyCertificate>,
+        intermediates: Vec<pyo3::Py<PyCertificate>>,
+        tlsa_records: Vec<pyo3::Py<PyTlsaRecord>>,
+        ocsp_responses: Option<Vec<pyo3::Py<PyOCSPResponse>>>,
+    ) -> CryptographyResult<pyo3::Bound<'p, pyo3::types::PyList>> {
+        let ocsp_responses = ocsp_responses.unwrap_or_default();
+        let ocsp_responses = basic_ocsp_responses(&ocsp_responses)?;
+        let leaf = verification_certificate(py, &leaf);
+        // Certificates published in DANE-TA records may be used as if the
+        // server had presented them.
+        let intermediates = intermediates
+            .iter()
+            .chain(
+                tlsa_records
+                    .iter()
+                    .filter_map(|r| r.get().certificate.as_ref()),
+            )
+            .map(|i| verification_certificate(py, i))
+            .collect();
+        let tlsa_records = tlsa_records
+            .iter()
+            .map(|r| r.get().record())
+            .collect::<Vec<_>>();
+
+        let result = py.allow_threads(|| {
+            build_chains(
+                self.as_policy(),
+                self.store.get(),
+                self.issuer_fetcher.as_ref(),
+                &leaf,
+                intermediates,
+                &ocsp_responses,
+                Some(&tlsa_records),
+                self.signature_cache.as_deref(),
+                NonZeroUsize::new(1).unwrap(),
+                None,
+                |py, chains| {
+                    let result = pyo3::types::PyList::empty_bound(py);
+                    for c in &chains[0].chain {
+                        result.append(c.extra())?;
+                    }
+                    Ok(result.unbind())
+                },
+            )
+        })?;
+        Ok(result.into_bound(py))
+    }
+
+    #[pyo3(signature = (
+        leaf,
+        intermediates,
+        ocsp_responses = None,
+        *,
+        limit = DEFAULT_VERIFY_ALL_LIMIT,
+        key = None,
+    ))]
+    fn verify_all<'p>(
+        &self,
+        py: pyo3::Python<'p>,
         leaf: pyo3::Py<PyCertificate>,
         intermediates: Vec<pyo3::Py<PyCertificate>>,
+        ocsp_responses: Option<Vec<pyo3::Py<PyOCSPResponse>>>,
+        limit: usize,
+        key: Option<pyo3::PyObject>,
+    ) -> CryptographyResult<pyo3::Bound<'p, pyo3::types::PyList>> {
+        let ocsp_responses = ocsp_responses.unwrap_or_default();
+        let to_py = |py: pyo3::Python<'_>,
+                     verified: &VerifiedChain<'_, PyCryptoOps>|
+         -> CryptographyResult<pyo3::PyObject> {
+            Ok(pyo3::Bound::new(py, verified_chain(py, self.store.get(), verified)?)?.into_py(py))
+        };
+        let result = verify_chains(
+            py,
+            self.as_policy(),
+            self.store.get(),
+            self.issuer_fetcher.as_ref(),
+            self.signature_cache.as_deref(),
+            &leaf,
+            &intermediates,
+            &ocsp_responses,
+            verify_all_limit(limit)?,
+            key.as_ref().map(|key| ChainRanking { key, to_py: &to_py }),
+            |py, chains| {
+                let result = pyo3::types::PyList::empty_bound(py);
+                for verified in chains {
+                    result.append(to_py(py, verified)?)?;
+                }
+                Ok(result.unbind())
+            },
+        )?;
+        Ok(result.into_bound(py))
+    }
+
+    #[pyo3(signature = (items, *, max_workers = None))]
+    fn verify_many<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+        items: Vec<BatchItem>,
+        max_workers: Option<usize>,
     ) -> CryptographyResult<pyo3::Bound<'p, pyo3::types::PyList>> {
-        let policy = self.as_policy();
-        let store = self.store.get();
+        verify_many(
+            py,
+            self.as_policy(),
+            self.store.get(),
+            self.issuer_fetcher.as_ref(),
+            self.signature_cache.as_deref(),
+            &items,
+            max_workers,
+            |py, verified| {
+                let result = pyo3::types::PyList::empty_bound(py);
+                for c in &verified.chain {
+                    result.append(c.extra())?;
+                }
+                Ok(result.into_any().unbind())
+            },
+        )
+    }
+}
+
+// The default maximum number of chains returned by `verify_all`.
+const DEFAULT_VERIFY_ALL_LIMIT: usize = 16;
+
+// The number of intermediate signatures cached for a `verify_many` batch,
+// when the verifier has no signature cache of its own.
+const BATCH_SIGNATURE_CACHE_SIZE: usize = 1024;
+
+fn verify_all_limit(limit: usize) -> CryptographyResult<NonZeroUsize> {
+    NonZeroUsize::new(limit).ok_or_else(|| {
+        CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+            "limit must be a positive integer",
+        ))
+    })
+}
+
+/// Builds a single chain for `leaf`, and converts it with `convert`.
+#[allow(clippy::too_many_arguments)]
+fn verify_chain<T: Send>(
+    py: pyo3::Python<'_>,
+    policy: &Policy<'_, PyCryptoOps>,
+    store: &PyStore,
+    issuer_fetcher: Option<&pyo3::PyObject>,
+    signature_cache: Option<&SignatureCache>,
+    leaf: &pyo3::Py<PyCertificate>,
+    intermediates: &[pyo3::Py<PyCertificate>],
+    ocsp_responses: &[pyo3::Py<PyOCSPResponse>],
+    convert: impl FnOnce(pyo3::Python<'_>, &VerifiedChain<'_, PyCryptoOps>) -> CryptographyResult<T>
+        + Send,
+) -> CryptographyResult<T> {
+    verify_chains(
+        py,
+        policy,
+        store,
+        issuer_fetcher,
+        signature_cache,
+        leaf,
+        intermediates,
+        ocsp_responses,
+        NonZeroUsize::new(1).unwrap(),
+        None,
+        |py, chains| convert(py, &chains[0]),
+    )
+}
+
+/// Builds up to `limit` chains for `leaf`, and converts them with `convert`.
+/// The GIL is released for the whole path build.
+///
+/// If a `ranking` is given, every chain is found first, and the best `limit`
+/// by its key are converted.
+#[allow(clippy::too_many_arguments)]
+fn verify_chains<T: Send>(
+    py: pyo3::Python<'_>,
+    policy: &Policy<'_, PyCryptoOps>,
+    store: &PyStore,
+    issuer_fetcher: Option<&pyo3::PyObject>,
+    signature_cache: Option<&SignatureCache>,
+    leaf: &pyo3::Py<PyCertificate>,
+    intermediates: &[pyo3::Py<PyCertificate>],
+    ocsp_responses: &[pyo3::Py<PyOCSPResponse>],
+    limit: NonZeroUsize,
+    ranking: Option<ChainRanking<'_>>,
+    convert: impl FnOnce(pyo3::Python<'_>, &[VerifiedChain<'_, PyCryptoOps>]) -> CryptographyResult<T>
+        + Send,
+) -> CryptographyResult<T> {
+    let ocsp_responses = basic_ocsp_responses(ocsp_responses)?;
+    let leaf = verification_certificate(py, leaf);
+    let intermediates = intermediates
+        .iter()
+        .map(|i| verification_certificate(py, i))
+        .collect();
+
+    py.allow_threads(move || {
+        build_chains(
+            policy,
+            store,
+            issuer_fetcher,
+            &leaf,
+            intermediates,
+            &ocsp_responses,
+            None,
+            signature_cache,
+            limit,
+            ranking.as_ref(),
+            convert,
+        )
+    })
+}
+
+/// An item passed to `verify_many`: a leaf and its intermediates, with any
+/// stapled OCSP responses for the chain.
+#[derive(pyo3::prelude::FromPyObject)]
+enum BatchItem {
+    Stapled(
+        pyo3::Py<PyCertificate>,
+        Vec<pyo3::Py<PyCertificate>>,
+        Option<Vec<pyo3::Py<PyOCSPResponse>>>,
+    ),
+    Unstapled(pyo3::Py<PyCertificate>, Vec<pyo3::Py<PyCertificate>>),
+}
+
+impl BatchItem {
+    fn parts(
+        &self,
+    ) -> (
+        &pyo3::Py<PyCertificate>,
+        &[pyo3::Py<PyCertificate>],
+        &[pyo3::Py<PyOCSPResponse>],
+    ) {
+        match self {
+            BatchItem::Stapled(leaf, intermediates, ocsp_responses) => (
+                leaf,
+                intermediates,
+                ocsp_responses.as_deref().unwrap_or_default(),
+            ),
+            BatchItem::Unstapled(leaf, intermediates) => (leaf, intermediates, &[]),
+        }
+    }
+}
 
-        let chain = cryptography_x509_verification::verify(
-            &VerificationCertificate::new(
-                leaf.get().raw.borrow_dependent().clone(),
-                leaf.clone_ref(py),
-            ),
-            intermediates.iter().map(|i| {
-                VerificationCertificate::new(
-                    i.get().raw.borrow_dependent().clone(),
-                    i.clone_ref(py),
-                )
-            }),
+/// Verifies each item in `items` on a pool of up to `max_workers` threads,
+/// with the GIL released. Each item's stapled OCSP responses are used for
+/// its chain alone. Signatures verified for one item are cached for the rest
+/// of the batch (in the verifier's cache, if it has one), so intermediates
+/// shared between items are only checked once.
+/// A worker that panics fails the whole batch with a `RuntimeError`.
+///
+/// The result for each item is either its chain, converted with `convert`,
+/// or the `VerificationError` it failed with.
+#[allow(clippy::too_many_arguments)]
+fn verify_many<'p>(
+    py: pyo3::Python<'p>,
+    policy: &Policy<'_, PyCryptoOps>,
+    store: &PyStore,
+    issuer_fetcher: Option<&pyo3::PyObject>,
+    signature_cache: Option<&SignatureCache>,
+    items: &[BatchItem],
+    max_workers: Option<usize>,
+    convert: impl Fn(pyo3::Python<'_>, &VerifiedChain<'_, PyCryptoOps>) -> CryptographyResult<pyo3::PyObject>
+        + Sync,
+) -> CryptographyResult<pyo3::Bound<'p, pyo3::types::PyList>> {
+    let max_workers = match max_workers {
+        Some(max_workers) => NonZeroUsize::new(max_workers).ok_or_else(|| {
+            CryptographyError::from(pyo3::exceptions::PyValueError::new_err(
+                "max_workers must be a positive integer",
+            ))
+        })?,
+        None => std::thread::available_parallelism().unwrap_or(NonZeroUsize::new(1).unwrap()),
+    };
+    let workers = max_workers.get().min(items.len());
+    let queue = Mutex::new(
+        items
+            .iter()
+            .map(|item| {
+                let (leaf, intermediates, ocsp_responses) = item.parts();
+                Ok((
+                    verification_certificate(py, leaf),
+                    intermediates
+                        .iter()
+                        .map(|i| verification_certificate(py, i))
+                        .collect::<Vec<_>>(),
+                    basic_ocsp_responses(ocsp_responses)?,
+                ))
+            })
+            .collect::<pyo3::PyResult<Vec<_>>>()?
+            .into_iter()
+            .enumerate(),
+    );
+    let batch_cache;
+    let signature_cache = match signature_cache {
+        Some(signature_cache) => signature_cache,
+        None => {
+            batch_cache = SignatureCache::without_leaves(
+                NonZeroUsize::new(BATCH_SIGNATURE_CACHE_SIZE).unwrap(),
+            );
+            &batch_cache
+        }
+    };
+
+    let mut results = py
+        .allow_threads(|| {
+            std::thread::scope(|scope| {
+                let handles = (0..workers)
+                    .map(|_| {
+                        scope.spawn(|| {
+                            let mut results = vec![];
+                            loop {
+                                let next = queue.lock().unwrap().next();
+                                let Some((i, (leaf, intermediates, ocsp_responses))) = next else {
+                                    return results;
+                                };
+                                results.push((
+                                    i,
+                                    build_chains(
+                                        policy,
+                                        store,
+                                        issuer_fetcher,
+                                        &leaf,
+                                        intermediates,
+                                        &ocsp_responses,
+                                        None,
+                                        Some(signature_cache),
+                                        NonZeroUsize::new(1).unwrap(),
+                                        None,
+                                        |py, chains| convert(py, &chains[0]),
+                                    ),
+                                ));
+                            }
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                // NOTE: Every worker is joined before any panic is reported,
+                // since `scope` itself panics if a worker it joins did.
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join())
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .collect::<Result<Vec<_>, _>>()
+            })
+        })
+        .map_err(|_| {
+            CryptographyError::from(pyo3::exceptions::PyRuntimeError::new_err(
+                "a verification worker thread panicked",
+            ))
+        })?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    results.sort_by_key(|(i, _)| *i);
+
+    let py_results = pyo3::types::PyList::empty_bound(py);
+    for (_, result) in results {
+        match result {
+            Ok(verified) => py_results.append(verified)?,
+            Err(CryptographyError::Py(e)) if e.is_instance_of::<VerificationError>(py) => {
+                py_results.append(e.value_bound(py))?
+            }
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(py_results)
+}
+
+fn verification_certificate<'a>(
+    py: pyo3::Python<'_>,
+    cert: &'a pyo3::Py<PyCertificate>,
+) -> VerificationCertificate<'a, PyCryptoOps> {
+    VerificationCertificate::new(
+        cert.get().raw.borrow_dependent().clone(),
+        cert.clone_ref(py),
+    )
+}
+
+/// Builds up to `limit` chains for `leaf`, which must be called with the GIL
+/// released. The GIL is re-acquired to call `convert`, since the chains
+/// borrow from any certificates that were fetched while building them.
+///
+/// If `tlsa_records` are supplied, a single chain is validated against them
+/// with DANE instead. Otherwise, if a `ranking` is supplied, the best `limit`
+/// chains by its key are built, rather than the first `limit` found.
+#[allow(clippy::too_many_arguments)]
+fn build_chains<'a, T: Send>(
+    policy: &Policy<'_, PyCryptoOps>,
+    store: &'a PyStore,
+    issuer_fetcher: Option<&pyo3::PyObject>,
+    leaf: &VerificationCertificate<'a, PyCryptoOps>,
+    intermediates: Vec<VerificationCertificate<'a, PyCryptoOps>>,
+    ocsp_responses: &[&BasicOCSPResponse<'_>],
+    tlsa_records: Option<&[TlsaRecord<'_>]>,
+    signature_cache: Option<&SignatureCache>,
+    limit: NonZeroUsize,
+    ranking: Option<&ChainRanking<'_>>,
+    convert: impl FnOnce(pyo3::Python<'_>, &[VerifiedChain<'_, PyCryptoOps>]) -> CryptographyResult<T>,
+) -> CryptographyResult<T> {
+    let keepalive = cryptography_keepalive::KeepAlive::new();
+    let fetch_error = std::cell::RefCell::new(None);
+    let fetcher = issuer_fetcher.map(|fetcher| PyIssuerFetcher {
+        fetcher,
+        keepalive: &keepalive,
+        error: &fetch_error,
+    });
+    let fetcher = fetcher
+        .as_ref()
+        .map(|f| f as &dyn IssuerFetcher<'_, PyCryptoOps>);
+    let mut rank_error = None;
+    let result = match tlsa_records {
+        Some(records) => cryptography_x509_verification::verify_dane(
+            leaf,
+            intermediates,
+            ocsp_responses,
             policy,
             store.raw.borrow_dependent(),
+            records,
+            fetcher,
+            signature_cache,
         )
-        .map_err(|e| VerificationError::new_err(format!("validation failed: {e:?}")))?;
+        .map(|chain| vec![chain]),
+        None => match ranking {
+            Some(ranking) => cryptography_x509_verification::verify_ranked(
+                leaf,
+                intermediates,
+                ocsp_responses,
+                policy,
+                store.raw.borrow_dependent(),
+                fetcher,
+                signature_cache,
+                limit,
+                |chains| {
+                    if let Err(e) = ranking.rank(chains) {
+                        rank_error = Some(e);
+                    }
+                },
+            ),
+            None => cryptography_x509_verification::verify_all(
+                leaf,
+                intermediates,
+                ocsp_responses,
+                policy,
+                store.raw.borrow_dependent(),
+                fetcher,
+                signature_cache,
+                limit,
+            ),
+        },
+    };
+    let fetch_error = fetch_error.borrow_mut().take();
+
+    pyo3::Python::with_gil(|py| {
+        // An interrupted fetch (e.g. a `KeyboardInterrupt`) is re-raised
+        // as-is, rather than reported as a failure to verify.
+        if let Some(e) = &fetch_error {
+            if !e.is_instance_of::<pyo3::exceptions::PyException>(py) {
+                return Err(CryptographyError::from(e.clone_ref(py)));
+            }
+        }
+        if let Some(e) = rank_error {
+            return Err(e);
+        }
+
+        match result {
+            Ok(chains) => convert(py, &chains),
+            Err(failure) => {
+                let err = verification_error(py, failure).unwrap_or_else(|e| e);
+                err.set_cause(py, fetch_error);
+                Err(CryptographyError::from(err))
+            }
+        }
+    })
+}
+
+/// Fetches issuer certificates by calling a user-supplied callable with each
+/// AIA `caIssuers` URI.
+struct PyIssuerFetcher<'a> {
+    fetcher: &'a pyo3::PyObject,
+    keepalive: &'a cryptography_keepalive::KeepAlive<pyo3::pybacked::PyBackedBytes>,
+    // The exception raised by the most recent failed fetch, which becomes
+    // the cause of any `VerificationError`.
+    error: &'a std::cell::RefCell<Option<pyo3::PyErr>>,
+}
+
+impl<'a> IssuerFetcher<'a, PyCryptoOps> for PyIssuerFetcher<'a> {
+    fn fetch(
+        &self,
+        uri: &str,
+    ) -> Result<Vec<VerificationCertificate<'a, PyCryptoOps>>, IssuerFetchError> {
+        pyo3::Python::with_gil(|py| {
+            self.load(py, uri).map_err(|e| {
+                let e = pyo3::PyErr::from(e);
+                let result = if e.is_instance_of::<pyo3::exceptions::PyException>(py) {
+                    IssuerFetchError::Failed(e.to_string())
+                } else {
+                    IssuerFetchError::Aborted
+                };
+                *self.error.borrow_mut() = Some(e);
+                result
+            })
+        })
+    }
+}
+
+impl<'a> PyIssuerFetcher<'a> {
+    /// Calls the fetcher with `uri`, and parses the certificates it returns.
+    fn load(
+        &self,
+        py: pyo3::Python<'_>,
+        uri: &str,
+    ) -> CryptographyResult<Vec<VerificationCertificate<'a, PyCryptoOps>>> {
+        let data = self.fetcher.bind(py).call1((uri,))?;
+        if data.is_none() {
+            return Ok(vec![]);
+        }
+
+        // The callable may return a single DER certificate, or a DER
+        // PKCS#7 "certs-only" bundle (RFC 5280 4.2.2.1).
+        let data = data.downcast::<pyo3::types::PyBytes>()?;
+        let certs = match certificate::load_der_x509_certificate(py, data.clone().unbind(), None) {
+            Ok(cert) => vec![pyo3::Bound::new(py, cert)?],
+            Err(_) => pkcs7::load_der_pkcs7_certificates(py, data.as_bytes())?
+                .iter()
+                .map(|c| Ok(c.downcast::<PyCertificate>()?.clone()))
+                .collect::<CryptographyResult<_>>()?,
+        };
+
+        certs
+            .into_iter()
+            .map(|c| {
+                // Path building borrows from the certificates it's given,
+                // so their DER needs to outlive this call.
+                let der = self
+                    .keepalive
+                    .add(c.get().raw.borrow_owner().bind(py).clone().into());
+                Ok(VerificationCertificate::new(
+                    asn1::parse_single(der)?,
+                    c.unbind(),
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Converts a path validation failure into a `VerificationError`, with its
+/// diagnostic tree attached.
+fn verification_error(
+    py: pyo3::Python<'_>,
+    failure: VerificationFailure<'_, PyCryptoOps>,
+) -> pyo3::PyResult<pyo3::PyErr> {
+    let err = VerificationError::new_err(format!("validation failed: {:?}", failure.error));
+    err.value_bound(py).setattr(
+        pyo3::intern!(py, "diagnostic"),
+        pyo3::Bound::new(py, diagnostic_to_py(py, failure.diagnostic)?)?,
+    )?;
+    Ok(err)
+}
+
+fn diagnostic_to_py(
+    py: pyo3::Python<'_>,
+    diagnostic: Diagnostic<'_, PyCryptoOps>,
+) -> pyo3::PyResult<PyVerificationDiagnostic> {
+    let reason = match diagnostic.reason {
+        FailureReason::Expired => "expired",
+        FailureReason::NameConstraintViolation => "name_constraint_violation",
+        FailureReason::ExtendedKeyUsageMismatch => "extended_key_usage_mismatch",
+        FailureReason::BadSignature => "bad_signature",
+        FailureReason::DepthExceeded => "depth_exceeded",
+        FailureReason::BudgetExhausted => "budget_exhausted",
+        FailureReason::Revoked => "revoked",
+        FailureReason::RevocationCheckFailed => "revocation_check_failed",
+        FailureReason::CertificatePolicyViolation => "certificate_policy_violation",
+        FailureReason::CertificateTransparencyViolation => "certificate_transparency_violation",
+        FailureReason::MustStapleViolation => "must_staple_violation",
+        FailureReason::DaneMismatch => "dane_mismatch",
+        FailureReason::PinMismatch => "pin_mismatch",
+        FailureReason::Malformed => "malformed",
+        FailureReason::CandidatesExhausted => "candidates_exhausted",
+        FailureReason::Other => "other",
+    };
+
+    let candidates = pyo3::types::PyList::empty_bound(py);
+    for candidate in diagnostic.candidates {
+        candidates.append(pyo3::Bound::new(py, diagnostic_to_py(py, candidate)?)?)?;
+    }
+
+    Ok(PyVerificationDiagnostic {
+        certificate: diagnostic.certificate.extra().clone_ref(py),
+        depth: diagnostic.depth,
+        reason: types::VERIFICATION_FAILURE_REASON
+            .get(py)?
+            .call1((reason,))?
+            .unbind(),
+        message: diagnostic.message,
+        candidates: candidates.unbind(),
+    })
+}
+
+/// The caller's `key` for ranking the chains found by `verify_all`, and
+/// how to convert each chain into the object that `key` is called with.
+struct ChainRanking<'a> {
+    key: &'a pyo3::PyObject,
+    to_py: &'a (dyn Fn(
+        pyo3::Python<'_>,
+        &VerifiedChain<'_, PyCryptoOps>,
+    ) -> CryptographyResult<pyo3::PyObject>
+             + Sync),
+}
+
+impl ChainRanking<'_> {
+    /// Sorts `chains` by `key`, with Python's (stable) `sorted`, so that
+    /// chains with equal keys stay in the order they were found.
+    fn rank(&self, chains: &mut Vec<VerifiedChain<'_, PyCryptoOps>>) -> CryptographyResult<()> {
+        let order = pyo3::Python::with_gil(|py| -> CryptographyResult<Vec<usize>> {
+            let keys = pyo3::types::PyList::empty_bound(py);
+            for verified in chains.iter() {
+                keys.append(self.key.bind(py).call1(((self.to_py)(py, verified)?,))?)?;
+            }
+            let kwargs = [(
+                pyo3::intern!(py, "key"),
+                keys.getattr(pyo3::intern!(py, "__getitem__"))?,
+            )]
+            .into_py_dict_bound(py);
+            Ok(py
+                .import_bound(pyo3::intern!(py, "builtins"))?
+                .call_method(
+                    pyo3::intern!(py, "sorted"),
+                    ((0..chains.len()).collect::<Vec<_>>(),),
+                    Some(&kwargs),
+                )?
+                .extract()?)
+        })?;
+
+        let mut unranked = std::mem::take(chains)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+        chains.extend(order.into_iter().filter_map(|i| unranked[i].take()));
+        Ok(())
+    }
+}
+
+/// Returns `store`'s `TrustAnchor` for the trust anchor without a
+/// certificate that `verified` ends at, if any.
+fn trust_anchor(
+    py: pyo3::Python<'_>,
+    store: &PyStore,
+    verified: &VerifiedChain<'_, PyCryptoOps>,
+) -> Option<pyo3::Py<PyTrustAnchor>> {
+    let anchor = verified.anchor.as_ref()?;
+    store
+        .raw
+        .borrow_owner()
+        .anchors
+        .iter()
+        .find(|a| a.get().key_anchor().as_ref() == Some(anchor))
+        .map(|a| a.clone_ref(py))
+}
 
-        let result = pyo3::types::PyList::empty_bound(py);
-        for c in chain {
-            result.append(c.extra())?;
+fn verified_chain(
+    py: pyo3::Python<'_>,
+    store: &PyStore,
+    verified: &VerifiedChain<'_, PyCryptoOps>,
+) -> CryptographyResult<PyVerifiedChain> {
+    let py_chain = pyo3::types::PyList::empty_bound(py);
+    for c in &verified.chain {
+        py_chain.append(c.extra())?;
+    }
+    Ok(PyVerifiedChain {
+        chain: py_chain.unbind(),
+        trust_anchor: trust_anchor(py, store, verified),
+        policies: policies_to_py(py, &verified.policies)?.unbind(),
+    })
+}
+
+fn verified_client(
+    py: pyo3::Python<'_>,
+    store: &PyStore,
+    verified: &VerifiedChain<'_, PyCryptoOps>,
+) -> CryptographyResult<PyVerifiedClient> {
+    let chain = &verified.chain;
+
+    let py_chain = pyo3::types::PyList::empty_bound(py);
+    for c in chain {
+        py_chain.append(c.extra())?;
+    }
+
+    // NOTE: This `unwrap()` cannot fail, since the underlying policy
+    // enforces the well-formedness of the extension set. The SAN itself
+    // is optional in the code signing and time stamping profiles.
+    let py_gns = match chain[0]
+        .certificate()
+        .extensions()
+        .unwrap()
+        .get_extension(&SUBJECT_ALTERNATIVE_NAME_OID)
+    {
+        Some(leaf_san) => {
+            let leaf_gns = leaf_san.value::<SubjectAlternativeName<'_>>()?;
+            parse_general_names(py, &leaf_gns)?
         }
-        Ok(result)
+        None => py.None(),
+    };
+
+    Ok(PyVerifiedClient {
+        subjects: py_gns,
+        chain: py_chain.unbind(),
+        trust_anchor: trust_anchor(py, store, verified),
+        policies: policies_to_py(py, &verified.policies)?.unbind(),
+    })
+}
+
+fn policies_to_py<'p>(
+    py: pyo3::Python<'p>,
+    policies: &Option<Vec<asn1::ObjectIdentifier>>,
+) -> pyo3::PyResult<pyo3::Bound<'p, pyo3::PyAny>> {
+    match policies {
+        Some(policies) => {
+            let py_policies = pyo3::types::PyList::empty_bound(py);
+            for policy in policies {
+                py_policies.append(oid_to_py_oid(py, policy)?)?;
+            }
+            Ok(py_policies.into_any())
+        }
+        None => Ok(py.None().into_bound(py)),
     }
 }
 
+fn basic_ocsp_responses(
+    ocsp_responses: &[pyo3::Py<PyOCSPResponse>],
+) -> pyo3::PyResult<Vec<&BasicOCSPResponse<'_>>> {
+    ocsp_responses
+        .iter()
+        .map(|r| r.get().requires_successful_response())
+        .collect()
+}
+
 fn build_subject_owner(
     py: pyo3::Python<'_>,
     subject: &pyo3::Py<pyo3::PyAny>,
@@ -388,6 +2478,11 @@ fn build_subject_owner(
             .downcast::<pyo3::types::PyBytes>()?
             .clone();
         Ok(SubjectOwner::IPAddress(value.unbind()))
+    } else if subject.is_instance(&types::UNIFORM_RESOURCE_IDENTIFIER.get(py)?)? {
+        let value = subject
+            .getattr(pyo3::intern!(py, "value"))?
+            .extract::<String>()?;
+        Ok(SubjectOwner::Uri(value))
     } else {
         Err(pyo3::exceptions::PyTypeError::new_err(
             "unsupported subject type",
@@ -412,14 +2507,51 @@ fn build_subject<'a>(
 
             Ok(Subject::IP(ip_addr))
         }
+        SubjectOwner::Email(email) => {
+            let email = SmtpUTF8Mailbox::new(email)
+                .ok_or_else(|| pyo3::exceptions::PyValueError::new_err("invalid email address"))?;
+
+            Ok(Subject::Email(email))
+        }
+        SubjectOwner::Uri(uri) => Ok(Subject::Uri(uri)),
+    }
+}
+
+/// Like `build_subject_owner`, but for the SPIFFE profile, whose subject is
+/// the SPIFFE ID of a workload or of a trust domain.
+fn build_spiffe_subject_owner(
+    py: pyo3::Python<'_>,
+    subject: &pyo3::Py<pyo3::PyAny>,
+) -> pyo3::PyResult<SubjectOwner> {
+    let subject = subject.bind(py);
+    if !subject.is_instance(&types::UNIFORM_RESOURCE_IDENTIFIER.get(py)?)? {
+        return Err(pyo3::exceptions::PyTypeError::new_err(
+            "unsupported subject type",
+        ));
     }
+
+    let value = subject
+        .getattr(pyo3::intern!(py, "value"))?
+        .extract::<String>()?;
+    if SpiffeId::new(&value).is_none() {
+        return Err(pyo3::exceptions::PyValueError::new_err("invalid SPIFFE ID"));
+    }
+    Ok(SubjectOwner::Uri(value))
 }
 
 type PyCryptoOpsStore<'a> = Store<'a, PyCryptoOps>;
 
+/// The data that a `PyStore`'s `Store` borrows from.
+struct StoreOwner {
+    anchors: Vec<pyo3::Py<PyTrustAnchor>>,
+    // The hashed directory that trust anchors are loaded from on demand, if
+    // any.
+    directory: Option<HashedDirectory>,
+}
+
 self_cell::self_cell!(
     struct RawPyStore {
-        owner: Vec<pyo3::Py<PyCertificate>>,
+        owner: StoreOwner,
 
         #[covariant]
         dependent: PyCryptoOpsStore,
@@ -435,33 +2567,617 @@ struct PyStore {
     raw: RawPyStore,
 }
 
+impl PyStore {
+    fn from_owner(py: pyo3::Python<'_>, owner: StoreOwner) -> Self {
+        PyStore {
+            raw: RawPyStore::new(owner, |owner| match &owner.directory {
+                Some(directory) => Store::from_source(directory),
+                None => Store::with_constraints(owner.anchors.iter().filter_map(|a| {
+                    let a = a.get();
+                    let TrustAnchorKind::Certificate(certificate) = &a.kind else {
+                        return None;
+                    };
+                    let cert = VerificationCertificate::new(
+                        certificate.get().raw.borrow_dependent().clone(),
+                        certificate.clone_ref(py),
+                    );
+                    Some((cert, a.constraints()))
+                }))
+                .with_key_anchors(owner.anchors.iter().filter_map(|a| {
+                    let a = a.get();
+                    Some((a.key_anchor()?, a.constraints()))
+                })),
+            }),
+        }
+    }
+
+    fn from_anchors(py: pyo3::Python<'_>, anchors: Vec<pyo3::Py<PyTrustAnchor>>) -> Self {
+        Self::from_owner(
+            py,
+            StoreOwner {
+                anchors,
+                directory: None,
+            },
+        )
+    }
+}
+
 #[pyo3::pymethods]
 impl PyStore {
     #[new]
-    fn new(py: pyo3::Python<'_>, certs: Vec<pyo3::Py<PyCertificate>>) -> pyo3::PyResult<Self> {
+    fn new(py: pyo3::Python<'_>, certs: Vec<pyo3::Bound<'_, pyo3::PyAny>>) -> pyo3::PyResult<Self> {
         if certs.is_empty() {
             return Err(pyo3::exceptions::PyValueError::new_err(
                 "can't create an empty store",
             ));
         }
-        Ok(Self {
-            raw: RawPyStore::new(certs, |v| {
-                Store::new(v.iter().map(|t| {
-                    VerificationCertificate::new(
-                        t.get().raw.borrow_dependent().clone(),
-                        t.clone_ref(py),
+        let anchors = certs
+            .into_iter()
+            .map(|c| {
+                if let Ok(anchor) = c.downcast::<PyTrustAnchor>() {
+                    return Ok(anchor.clone().unbind());
+                }
+                let certificate = c.downcast::<PyCertificate>().map_err(|_| {
+                    pyo3::exceptions::PyTypeError::new_err(
+                        "certs must be a list of Certificate or TrustAnchor",
                     )
-                }))
-            }),
+                })?;
+                pyo3::Py::new(
+                    py,
+                    PyTrustAnchor::unconstrained(certificate.clone().unbind()),
+                )
+            })
+            .collect::<pyo3::PyResult<Vec<_>>>()?;
+        Ok(Self::from_anchors(py, anchors))
+    }
+
+    #[staticmethod]
+    fn from_pem_bundle(py: pyo3::Python<'_>, path: PathBuf) -> CryptographyResult<Self> {
+        let data = std::fs::read(path).map_err(pyo3::PyErr::from)?;
+        let anchors = certificate::load_pem_x509_certificates(py, &data)?
+            .into_iter()
+            .map(|c| {
+                let certificate = pyo3::Py::new(py, c)?;
+                pyo3::Py::new(py, PyTrustAnchor::unconstrained(certificate))
+            })
+            .collect::<pyo3::PyResult<Vec<_>>>()?;
+        Ok(Self::from_anchors(py, anchors))
+    }
+
+    #[staticmethod]
+    fn from_hashed_directory(py: pyo3::Python<'_>, path: PathBuf) -> CryptographyResult<Self> {
+        if !std::fs::metadata(&path)
+            .map_err(pyo3::PyErr::from)?
+            .is_dir()
+        {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyValueError::new_err("path must be a directory"),
+            ));
+        }
+        Ok(Self::from_owner(
+            py,
+            StoreOwner {
+                anchors: vec![],
+                directory: Some(HashedDirectory {
+                    path,
+                    loaded: Mutex::new(HashMap::new()),
+                }),
+            },
+        ))
+    }
+}
+
+/// Loads trust anchors on demand from an OpenSSL-style hashed directory
+/// (e.g. as produced by `c_rehash` or `openssl rehash`), in which the PEM
+/// certificates with a given subject are in the files `HHHHHHHH.0`,
+/// `HHHHHHHH.1`, etc., named for OpenSSL's hash of the subject.
+struct HashedDirectory {
+    path: PathBuf,
+    // The certificates loaded so far, by the DER of the subject they were
+    // loaded for. Lookups that found nothing are cached too. Entries are
+    // never removed or replaced.
+    loaded: Mutex<HashMap<Vec<u8>, Vec<pyo3::Py<PyCertificate>>>>,
+}
+
+impl HashedDirectory {
+    /// Loads the certificates whose subject is `subject` from the files
+    /// named for its `hash`. The first missing file ends the search, but a
+    /// file that can't be read or that isn't a PEM certificate is an error.
+    fn load(&self, hash: u32, subject: &Name<'_>) -> Result<Vec<pyo3::Py<PyCertificate>>, String> {
+        pyo3::Python::with_gil(|py| {
+            let mut certs = vec![];
+            for i in 0.. {
+                let path = self.path.join(format!("{hash:08x}.{i}"));
+                let data = match std::fs::read(&path) {
+                    Ok(data) => data,
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => break,
+                    Err(e) => return Err(format!("{}: {e}", path.display())),
+                };
+                let loaded = certificate::load_pem_x509_certificates(py, &data)
+                    .map_err(|_| format!("{}: not a PEM certificate file", path.display()))?;
+                for c in loaded {
+                    // Certificates with different subjects may share a hash.
+                    if &c.raw.borrow_dependent().tbs_cert.subject == subject {
+                        certs.push(pyo3::Py::new(py, c).map_err(|e| e.to_string())?);
+                    }
+                }
+            }
+            Ok(certs)
+        })
+    }
+}
+
+impl AnchorSource<PyCryptoOps> for HashedDirectory {
+    fn get_by_subject<'s>(
+        &'s self,
+        subject: &Name<'_>,
+    ) -> Result<Vec<VerificationCertificate<'s, PyCryptoOps>>, String> {
+        let key = asn1::write_single(subject).map_err(|_| "unable to encode subject")?;
+
+        // NOTE: The lock isn't held while loading, since that needs the GIL.
+        // Failures aren't cached, so they're retried by later lookups.
+        if !self.loaded.lock().unwrap().contains_key(&key) {
+            let hash = name_hash(subject).ok_or("unable to hash subject")?;
+            let certs = self.load(hash, subject)?;
+            self.loaded
+                .lock()
+                .unwrap()
+                .entry(key.clone())
+                .or_insert(certs);
+        }
+
+        let loaded = self.loaded.lock().unwrap();
+        Ok(loaded[&key]
+            .iter()
+            .map(|c| {
+                // SAFETY: `c` is never removed from `self.loaded`, so the
+                // `PyCertificate` it refers to is kept alive for as long as
+                // `self` is. It's a frozen Python object, so it isn't moved
+                // or mutated in the meantime, even if `self.loaded` is.
+                let cert: &'s PyCertificate = unsafe { &*(c.get() as *const PyCertificate) };
+                VerificationCertificate::new(cert.raw.borrow_dependent().clone(), c.clone())
+            })
+            .collect())
+    }
+
+    fn is_loaded(&self, cert: &Certificate<'_>) -> bool {
+        let Ok(key) = asn1::write_single(&cert.tbs_cert.subject) else {
+            return false;
+        };
+        self.loaded
+            .lock()
+            .unwrap()
+            .get(&key)
+            .map_or(false, |certs| {
+                certs.iter().any(|c| c.get().raw.borrow_dependent() == cert)
+            })
+    }
+}
+
+/// Returns OpenSSL's hash of `name`, as used by `X509_subject_name_hash`.
+fn name_hash(name: &Name<'_>) -> Option<u32> {
+    let name = openssl::x509::X509Name::from_der(&asn1::write_single(name).ok()?).ok()?;
+    // NOTE: OpenSSL only exposes the hash of a name via a certificate.
+    let mut builder = openssl::x509::X509Builder::new().ok()?;
+    builder.set_subject_name(&name).ok()?;
+    Some(builder.build().subject_name_hash())
+}
+
+#[pyo3::pyclass(
+    frozen,
+    name = "TrustAnchor",
+    module = "cryptography.hazmat.bindings._rust.x509"
+)]
+struct PyTrustAnchor {
+    kind: TrustAnchorKind,
+    #[pyo3(get, name = "name_constraints")]
+    py_name_constraints: Option<pyo3::PyObject>,
+    // The DER encoding of `py_name_constraints`.
+    name_constraints: Option<Vec<u8>>,
+    #[pyo3(get)]
+    max_path_length: Option<u8>,
+    #[pyo3(get, name = "extended_key_usages")]
+    py_extended_key_usages: Option<pyo3::PyObject>,
+    extended_key_usages: Option<Vec<asn1::ObjectIdentifier>>,
+    #[pyo3(get)]
+    spiffe_trust_domain: Option<String>,
+}
+
+/// What a `PyTrustAnchor` trusts: a certificate, or a name and public key.
+enum TrustAnchorKind {
+    Certificate(pyo3::Py<PyCertificate>),
+    PublicKey {
+        py_name: pyo3::PyObject,
+        py_public_key: pyo3::PyObject,
+        // The DER encodings of `py_name` and `py_public_key`.
+        name: Vec<u8>,
+        spki: Vec<u8>,
+    },
+}
+
+impl PyTrustAnchor {
+    /// Returns a trust anchor for `certificate` with no constraints beyond
+    /// its own.
+    fn unconstrained(certificate: pyo3::Py<PyCertificate>) -> Self {
+        PyTrustAnchor {
+            kind: TrustAnchorKind::Certificate(certificate),
+            py_name_constraints: None,
+            name_constraints: None,
+            max_path_length: None,
+            py_extended_key_usages: None,
+            extended_key_usages: None,
+            spiffe_trust_domain: None,
+        }
+    }
+
+    fn constraints(&self) -> AnchorConstraints<'_> {
+        AnchorConstraints {
+            // NOTE: This `unwrap()` cannot fail, since we encoded the
+            // name constraints ourselves.
+            name_constraints: self
+                .name_constraints
+                .as_deref()
+                .map(|der| asn1::parse_single(der).unwrap()),
+            max_path_length: self.max_path_length,
+            extended_key_usages: self.extended_key_usages.clone(),
+            // NOTE: This `unwrap()` cannot fail, since we validated the
+            // trust domain on construction.
+            spiffe_trust_domain: self
+                .spiffe_trust_domain
+                .as_deref()
+                .map(|td| SpiffeTrustDomain::new(td).unwrap()),
+        }
+    }
+
+    /// Returns this trust anchor's name and public key, if it isn't a
+    /// certificate.
+    fn key_anchor(&self) -> Option<KeyAnchor<'_, PyCryptoOps>> {
+        match &self.kind {
+            TrustAnchorKind::Certificate(_) => None,
+            // NOTE: These `unwrap()`s cannot fail, since we encoded the name
+            // ourselves and validated the public key on construction.
+            TrustAnchorKind::PublicKey { name, spki, .. } => Some(KeyAnchor::new(
+                asn1::parse_single(name).unwrap(),
+                asn1::parse_single(spki).unwrap(),
+            )),
+        }
+    }
+
+    fn with_constraints(
+        py: pyo3::Python<'_>,
+        kind: TrustAnchorKind,
+        name_constraints: Option<pyo3::Bound<'_, pyo3::PyAny>>,
+        max_path_length: Option<u8>,
+        extended_key_usages: Option<pyo3::Bound<'_, pyo3::types::PyList>>,
+        spiffe_trust_domain: Option<String>,
+    ) -> CryptographyResult<Self> {
+        if let Some(td) = &spiffe_trust_domain {
+            if SpiffeTrustDomain::new(td).is_none() {
+                return Err(CryptographyError::from(
+                    pyo3::exceptions::PyValueError::new_err("invalid SPIFFE trust domain"),
+                ));
+            }
+        }
+        let der = match &name_constraints {
+            Some(nc) => {
+                if !nc.is_instance(&types::NAME_CONSTRAINTS.get(py)?)? {
+                    return Err(CryptographyError::from(
+                        pyo3::exceptions::PyTypeError::new_err(
+                            "name_constraints must be a NameConstraints",
+                        ),
+                    ));
+                }
+                extensions::encode_extension(py, &oid::NAME_CONSTRAINTS_OID, nc)?
+            }
+            None => None,
+        };
+        let ekus = extended_key_usages
+            .as_ref()
+            .map(|ekus| {
+                ekus.iter()
+                    .map(py_oid_to_oid)
+                    .collect::<pyo3::PyResult<Vec<_>>>()
+            })
+            .transpose()?;
+
+        Ok(Self {
+            kind,
+            py_name_constraints: name_constraints.map(|nc| nc.unbind()),
+            name_constraints: der,
+            max_path_length,
+            py_extended_key_usages: extended_key_usages.map(|ekus| ekus.into_any().unbind()),
+            extended_key_usages: ekus,
+            spiffe_trust_domain,
+        })
+    }
+}
+
+#[pyo3::pymethods]
+impl PyTrustAnchor {
+    #[new]
+    #[pyo3(signature = (
+        certificate,
+        *,
+        name_constraints = None,
+        max_path_length = None,
+        extended_key_usages = None,
+        spiffe_trust_domain = None,
+    ))]
+    fn new(
+        py: pyo3::Python<'_>,
+        certificate: pyo3::Py<PyCertificate>,
+        name_constraints: Option<pyo3::Bound<'_, pyo3::PyAny>>,
+        max_path_length: Option<u8>,
+        extended_key_usages: Option<pyo3::Bound<'_, pyo3::types::PyList>>,
+        spiffe_trust_domain: Option<String>,
+    ) -> CryptographyResult<Self> {
+        Self::with_constraints(
+            py,
+            TrustAnchorKind::Certificate(certificate),
+            name_constraints,
+            max_path_length,
+            extended_key_usages,
+            spiffe_trust_domain,
+        )
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (
+        name,
+        public_key,
+        *,
+        name_constraints = None,
+        max_path_length = None,
+        extended_key_usages = None,
+        spiffe_trust_domain = None,
+    ))]
+    fn from_public_key(
+        py: pyo3::Python<'_>,
+        name: pyo3::Bound<'_, pyo3::PyAny>,
+        public_key: pyo3::Bound<'_, pyo3::PyAny>,
+        name_constraints: Option<pyo3::Bound<'_, pyo3::PyAny>>,
+        max_path_length: Option<u8>,
+        extended_key_usages: Option<pyo3::Bound<'_, pyo3::types::PyList>>,
+        spiffe_trust_domain: Option<String>,
+    ) -> CryptographyResult<Self> {
+        if !name.is_instance(&types::NAME.get(py)?)? {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyTypeError::new_err("name must be a Name"),
+            ));
+        }
+        sign::identify_public_key_type(py, public_key.clone())?;
+
+        let name_der = asn1::write_single(&encode_name(py, &name)?)?;
+        let spki = public_key
+            .call_method1(
+                pyo3::intern!(py, "public_bytes"),
+                (
+                    types::ENCODING_DER.get(py)?,
+                    types::PUBLIC_FORMAT_SUBJECT_PUBLIC_KEY_INFO.get(py)?,
+                ),
+            )?
+            .extract::<Vec<u8>>()?;
+        cryptography_key_parsing::spki::parse_public_key(&spki)?;
+
+        Self::with_constraints(
+            py,
+            TrustAnchorKind::PublicKey {
+                py_name: name.unbind(),
+                py_public_key: public_key.unbind(),
+                name: name_der,
+                spki,
+            },
+            name_constraints,
+            max_path_length,
+            extended_key_usages,
+            spiffe_trust_domain,
+        )
+    }
+
+    #[getter]
+    fn certificate(&self, py: pyo3::Python<'_>) -> Option<pyo3::Py<PyCertificate>> {
+        match &self.kind {
+            TrustAnchorKind::Certificate(certificate) => Some(certificate.clone_ref(py)),
+            TrustAnchorKind::PublicKey { .. } => None,
+        }
+    }
+
+    #[getter]
+    fn name<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<pyo3::Bound<'p, pyo3::PyAny>> {
+        match &self.kind {
+            TrustAnchorKind::Certificate(certificate) => {
+                certificate.bind(py).getattr(pyo3::intern!(py, "subject"))
+            }
+            TrustAnchorKind::PublicKey { py_name, .. } => Ok(py_name.bind(py).clone()),
+        }
+    }
+
+    fn public_key<'p>(&self, py: pyo3::Python<'p>) -> pyo3::PyResult<pyo3::Bound<'p, pyo3::PyAny>> {
+        match &self.kind {
+            TrustAnchorKind::Certificate(certificate) => certificate
+                .bind(py)
+                .call_method0(pyo3::intern!(py, "public_key")),
+            TrustAnchorKind::PublicKey { py_public_key, .. } => Ok(py_public_key.bind(py).clone()),
+        }
+    }
+}
+
+#[pyo3::pyclass(
+    frozen,
+    name = "TLSARecord",
+    module = "cryptography.hazmat.bindings._rust.x509"
+)]
+struct PyTlsaRecord {
+    #[pyo3(get, name = "usage")]
+    py_usage: pyo3::PyObject,
+    #[pyo3(get, name = "selector")]
+    py_selector: pyo3::PyObject,
+    #[pyo3(get, name = "matching_type")]
+    py_matching_type: pyo3::PyObject,
+    usage: TlsaUsage,
+    selector: TlsaSelector,
+    matching_type: TlsaMatchingType,
+    data: Vec<u8>,
+    // The trust anchor published by a DANE-TA record that carries a full
+    // certificate, which the server needn't present.
+    certificate: Option<pyo3::Py<PyCertificate>>,
+}
+
+impl PyTlsaRecord {
+    fn record(&self) -> TlsaRecord<'_> {
+        TlsaRecord {
+            usage: self.usage,
+            selector: self.selector,
+            matching_type: self.matching_type,
+            data: &self.data,
+        }
+    }
+}
+
+#[pyo3::pymethods]
+impl PyTlsaRecord {
+    #[new]
+    fn new(
+        py: pyo3::Python<'_>,
+        usage: pyo3::Bound<'_, pyo3::PyAny>,
+        selector: pyo3::Bound<'_, pyo3::PyAny>,
+        matching_type: pyo3::Bound<'_, pyo3::PyAny>,
+        data: &[u8],
+    ) -> CryptographyResult<Self> {
+        let tlsa_usage = py_to_tlsa_usage(py, &usage)?;
+        let tlsa_selector = py_to_tlsa_selector(py, &selector)?;
+        let tlsa_matching_type = py_to_tlsa_matching_type(py, &matching_type)?;
+        // NOTE: Data that isn't a certificate makes the record unusable,
+        // rather than invalid (RFC 7671 4.1).
+        let certificate = match (tlsa_usage, tlsa_selector, tlsa_matching_type) {
+            (TlsaUsage::DaneTa, TlsaSelector::Certificate, TlsaMatchingType::Full) => {
+                let der = pyo3::types::PyBytes::new_bound(py, data).unbind();
+                match certificate::load_der_x509_certificate(py, der, None) {
+                    Ok(cert) => Some(pyo3::Py::new(py, cert)?),
+                    Err(_) => None,
+                }
+            }
+            _ => None,
+        };
+        Ok(Self {
+            usage: tlsa_usage,
+            selector: tlsa_selector,
+            matching_type: tlsa_matching_type,
+            py_usage: usage.unbind(),
+            py_selector: selector.unbind(),
+            py_matching_type: matching_type.unbind(),
+            data: data.to_vec(),
+            certificate,
+        })
+    }
+
+    #[getter]
+    fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn matches(&self, certificate: pyo3::PyRef<'_, PyCertificate>) -> bool {
+        self.record()
+            .matches(&PyCryptoOps {}, certificate.raw.borrow_dependent())
+    }
+}
+
+#[pyo3::pyclass(
+    frozen,
+    name = "CertificateTransparencyLog",
+    module = "cryptography.hazmat.bindings._rust.x509"
+)]
+struct PyCertificateTransparencyLog {
+    log_id: [u8; 32],
+    #[pyo3(get)]
+    key: pyo3::Py<pyo3::PyAny>,
+    pkey: openssl::pkey::PKey<openssl::pkey::Public>,
+    #[pyo3(get)]
+    operator: String,
+    not_before: Option<asn1::DateTime>,
+    not_after: Option<asn1::DateTime>,
+}
+
+#[pyo3::pymethods]
+impl PyCertificateTransparencyLog {
+    #[new]
+    #[pyo3(signature = (log_id, key, operator, *, not_before = None, not_after = None))]
+    fn new(
+        py: pyo3::Python<'_>,
+        log_id: &[u8],
+        key: pyo3::Bound<'_, pyo3::PyAny>,
+        operator: String,
+        not_before: Option<pyo3::Bound<'_, pyo3::PyAny>>,
+        not_after: Option<pyo3::Bound<'_, pyo3::PyAny>>,
+    ) -> CryptographyResult<Self> {
+        let log_id = log_id
+            .try_into()
+            .map_err(|_| pyo3::exceptions::PyValueError::new_err("log_id must be 32 bytes long"))?;
+        if !key.is_instance(&types::ELLIPTIC_CURVE_PUBLIC_KEY.get(py)?)?
+            && !key.is_instance(&types::RSA_PUBLIC_KEY.get(py)?)?
+        {
+            return Err(CryptographyError::from(
+                pyo3::exceptions::PyTypeError::new_err(
+                    "key must be an EllipticCurvePublicKey or RSAPublicKey",
+                ),
+            ));
+        }
+        let spki = key.call_method1(
+            pyo3::intern!(py, "public_bytes"),
+            (
+                types::ENCODING_DER.get(py)?,
+                types::PUBLIC_FORMAT_SUBJECT_PUBLIC_KEY_INFO.get(py)?,
+            ),
+        )?;
+        let pkey = cryptography_key_parsing::spki::parse_public_key(spki.extract()?)?;
+        Ok(Self {
+            log_id,
+            key: key.unbind(),
+            pkey,
+            operator,
+            not_before: not_before.map(|t| py_to_datetime(py, t)).transpose()?,
+            not_after: not_after.map(|t| py_to_datetime(py, t)).transpose()?,
         })
     }
+
+    #[getter]
+    fn log_id(&self) -> &[u8] {
+        &self.log_id
+    }
+
+    #[getter]
+    fn not_before<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> pyo3::PyResult<Option<pyo3::Bound<'p, pyo3::PyAny>>> {
+        self.not_before
+            .as_ref()
+            .map(|t| datetime_to_py(py, t))
+            .transpose()
+    }
+
+    #[getter]
+    fn not_after<'p>(
+        &self,
+        py: pyo3::Python<'p>,
+    ) -> pyo3::PyResult<Option<pyo3::Bound<'p, pyo3::PyAny>>> {
+        self.not_after
+            .as_ref()
+            .map(|t| datetime_to_py(py, t))
+            .transpose()
+    }
 }
 
 pub(crate) fn add_to_module(module: &pyo3::Bound<'_, pyo3::types::PyModule>) -> pyo3::PyResult<()> {
     module.add_class::<PyVerifiedClient>()?;
+    module.add_class::<PyVerifiedChain>()?;
+    module.add_class::<PyVerificationDiagnostic>()?;
     module.add_class::<PyClientVerifier>()?;
     module.add_class::<PyServerVerifier>()?;
     module.add_class::<PyStore>()?;
+    module.add_class::<PyTlsaRecord>()?;
+    module.add_class::<PyTrustAnchor>()?;
+    module.add_class::<PyCertificateTransparencyLog>()?;
     module.add_class::<PolicyBuilder>()?;
     module.add(
         "VerificationError",
@@ -2,18 +2,69 @@
 # 2.0, and the BSD License. See the LICENSE file in the root of this repository
 # for complete details.
 
+import base64
+import concurrent.futures
 import datetime
 import os
+import typing
 from functools import lru_cache
 from ipaddress import IPv4Address
 
 import pytest
 
 from cryptography import x509
-from cryptography.x509.general_name import DNSName, IPAddress
-from cryptography.x509.verification import PolicyBuilder, Store
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.serialization import pkcs7
+from cryptography.hazmat.primitives.asymmetric import (
+    ec,
+    ed25519,
+    padding,
+    rsa,
+)
+from cryptography.hazmat.primitives.asymmetric.types import (
+    CertificateIssuerPrivateKeyTypes,
+    CertificatePublicKeyTypes,
+)
+from cryptography.x509.general_name import (
+    DirectoryName,
+    DNSName,
+    IPAddress,
+    RFC822Name,
+    UniformResourceIdentifier,
+)
+from cryptography.x509 import ocsp
+from cryptography.x509.oid import (
+    AuthorityInformationAccessOID,
+    ExtendedKeyUsageOID,
+    ExtensionOID,
+    NameOID,
+    PublicKeyAlgorithmOID,
+    SignatureAlgorithmOID,
+)
+from cryptography.x509.verification import (
+    CertificateTransparencyLog,
+    Criticality,
+    ExtensionPresence,
+    FailureReason,
+    PolicyBuilder,
+    ServerVerifier,
+    Store,
+    TLSAMatchingType,
+    TLSARecord,
+    TLSASelector,
+    TLSAUsage,
+    TrustAnchor,
+    ValidationModel,
+    VerificationError,
+    VerifiedClient,
+)
+from tests.utils import load_vectors_from_file
 from tests.x509.test_x509 import _load_cert
 
+NOT_BEFORE = datetime.datetime(2024, 1, 1, tzinfo=datetime.timezone.utc)
+NOT_AFTER = datetime.datetime(2025, 1, 1, tzinfo=datetime.timezone.utc)
+VALIDATION_TIME = datetime.datetime(2024, 6, 1, tzinfo=datetime.timezone.utc)
+
 
 @lru_cache(maxsize=1)
 def dummy_store() -> Store:
@@ -24,6 +75,229 @@ def dummy_store() -> Store:
     return Store([cert])
 
 
+def _name(common_name: str) -> x509.Name:
+    return x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, common_name)])
+
+
+def _build_cert(
+    subject: str,
+    issuer: str,
+    public_key: CertificatePublicKeyTypes,
+    issuer_key: CertificateIssuerPrivateKeyTypes,
+    ca: bool,
+    extensions: typing.Sequence[x509.ExtensionType] = (),
+    subject_key_identifier: typing.Optional[bytes] = None,
+    critical_extensions: typing.Sequence[x509.ExtensionType] = (),
+    hash_algorithm: typing.Optional[hashes.HashAlgorithm] = hashes.SHA256(),
+    rsa_padding: typing.Optional[padding.AsymmetricPadding] = None,
+    sans: typing.Sequence[x509.GeneralName] = (DNSName("cryptography.io"),),
+    not_before: datetime.datetime = NOT_BEFORE,
+    not_after: datetime.datetime = NOT_AFTER,
+) -> x509.Certificate:
+    if subject_key_identifier is None:
+        ski = x509.SubjectKeyIdentifier.from_public_key(public_key)
+    else:
+        ski = x509.SubjectKeyIdentifier(subject_key_identifier)
+    builder = (
+        x509.CertificateBuilder()
+        .subject_name(_name(subject))
+        .issuer_name(_name(issuer))
+        .public_key(public_key)
+        .serial_number(x509.random_serial_number())
+        .not_valid_before(not_before)
+        .not_valid_after(not_after)
+        .add_extension(x509.BasicConstraints(ca=ca, path_length=None), True)
+        .add_extension(
+            x509.AuthorityKeyIdentifier.from_issuer_public_key(
+                issuer_key.public_key()
+            ),
+            False,
+        )
+        .add_extension(ski, False)
+    )
+    if ca:
+        builder = builder.add_extension(
+            x509.KeyUsage(
+                digital_signature=False,
+                content_commitment=False,
+                key_encipherment=False,
+                data_encipherment=False,
+                key_agreement=False,
+                key_cert_sign=True,
+                crl_sign=True,
+                encipher_only=False,
+                decipher_only=False,
+            ),
+            True,
+        )
+    elif sans:
+        builder = builder.add_extension(
+            x509.SubjectAlternativeName(list(sans)), False
+        )
+    for ext in extensions:
+        builder = builder.add_extension(ext, False)
+    for ext in critical_extensions:
+        builder = builder.add_extension(ext, True)
+    return builder.sign(issuer_key, hash_algorithm, rsa_padding=rsa_padding)
+
+
+def _build_ca(
+    subject: str,
+    issuer: typing.Optional[str] = None,
+    issuer_key: typing.Optional[ec.EllipticCurvePrivateKey] = None,
+    **kwargs: typing.Any,
+) -> typing.Tuple[ec.EllipticCurvePrivateKey, x509.Certificate]:
+    # A self-signed root, unless an issuer is given.
+    key = ec.generate_private_key(ec.SECP256R1())
+    cert = _build_cert(
+        subject,
+        issuer or subject,
+        key.public_key(),
+        issuer_key or key,
+        ca=True,
+        **kwargs,
+    )
+    return key, cert
+
+
+def _build_leaf(
+    issuer: str,
+    issuer_key: ec.EllipticCurvePrivateKey,
+    **kwargs: typing.Any,
+) -> x509.Certificate:
+    key = ec.generate_private_key(ec.SECP256R1())
+    return _build_cert(
+        "leaf", issuer, key.public_key(), issuer_key, ca=False, **kwargs
+    )
+
+
+def _build_chain(
+    leaf_extensions: typing.Sequence[x509.ExtensionType] = (),
+    **leaf_kwargs: typing.Any,
+) -> typing.Tuple[
+    ec.EllipticCurvePrivateKey, x509.Certificate, x509.Certificate
+]:
+    root_key, root = _build_ca("root")
+    leaf = _build_leaf(
+        "root", root_key, extensions=leaf_extensions, **leaf_kwargs
+    )
+    return root_key, root, leaf
+
+
+def _build_cross_signed() -> typing.Tuple[
+    x509.Certificate,
+    x509.Certificate,
+    x509.Certificate,
+    x509.Certificate,
+    x509.Certificate,
+]:
+    # A leaf whose intermediate is signed by two different roots.
+    root_a_key, root_a = _build_ca("root-a")
+    root_b_key, root_b = _build_ca("root-b")
+    intermediate_key = ec.generate_private_key(ec.SECP256R1())
+    intermediate_a = _build_cert(
+        "intermediate",
+        "root-a",
+        intermediate_key.public_key(),
+        root_a_key,
+        ca=True,
+    )
+    intermediate_b = _build_cert(
+        "intermediate",
+        "root-b",
+        intermediate_key.public_key(),
+        root_b_key,
+        ca=True,
+    )
+    leaf = _build_leaf("intermediate", intermediate_key)
+    return root_a, root_b, intermediate_a, intermediate_b, leaf
+
+
+def _builder(
+    *anchors: typing.Union[x509.Certificate, TrustAnchor],
+    store: typing.Optional[Store] = None,
+) -> PolicyBuilder:
+    # A builder trusting `anchors` (or `store`) at `VALIDATION_TIME`.
+    if store is None:
+        store = Store(list(anchors))
+    return PolicyBuilder().store(store).time(VALIDATION_TIME)
+
+
+def _server_verifier(builder: PolicyBuilder) -> ServerVerifier:
+    return builder.build_server_verifier(DNSName("cryptography.io"))
+
+
+def _spki_digest(
+    public_key: CertificatePublicKeyTypes,
+    algorithm: hashes.HashAlgorithm = hashes.SHA256(),
+) -> bytes:
+    digest = hashes.Hash(algorithm)
+    digest.update(
+        public_key.public_bytes(
+            serialization.Encoding.DER,
+            serialization.PublicFormat.SubjectPublicKeyInfo,
+        )
+    )
+    return digest.finalize()
+
+
+def _build_crl(
+    issuer_key: ec.EllipticCurvePrivateKey,
+    issuer: str,
+    revoked: typing.Sequence[x509.Certificate] = (),
+    last_update: datetime.datetime = NOT_BEFORE,
+    next_update: datetime.datetime = NOT_AFTER,
+    extensions: typing.Sequence[x509.ExtensionType] = (),
+) -> x509.CertificateRevocationList:
+    builder = (
+        x509.CertificateRevocationListBuilder()
+        .issuer_name(_name(issuer))
+        .last_update(last_update)
+        .next_update(next_update)
+    )
+    for cert in revoked:
+        builder = builder.add_revoked_certificate(
+            x509.RevokedCertificateBuilder()
+            .serial_number(cert.serial_number)
+            .revocation_date(NOT_BEFORE)
+            .build()
+        )
+    for ext in extensions:
+        builder = builder.add_extension(ext, True)
+    return builder.sign(issuer_key, hashes.SHA256())
+
+
+def _build_ocsp_response(
+    cert: x509.Certificate,
+    issuer: x509.Certificate,
+    responder: x509.Certificate,
+    responder_key: ec.EllipticCurvePrivateKey,
+    status: ocsp.OCSPCertStatus = ocsp.OCSPCertStatus.GOOD,
+    this_update: datetime.datetime = NOT_BEFORE,
+    next_update: typing.Optional[datetime.datetime] = NOT_AFTER,
+    encoding: ocsp.OCSPResponderEncoding = ocsp.OCSPResponderEncoding.NAME,
+    certificates: typing.Sequence[x509.Certificate] = (),
+) -> ocsp.OCSPResponse:
+    revoked = status == ocsp.OCSPCertStatus.REVOKED
+    builder = (
+        ocsp.OCSPResponseBuilder()
+        .add_response(
+            cert,
+            issuer,
+            hashes.SHA1(),
+            status,
+            this_update,
+            next_update,
+            NOT_BEFORE if revoked else None,
+            None,
+        )
+        .responder_id(encoding, responder)
+    )
+    if certificates:
+        builder = builder.certificates(list(certificates))
+    return builder.sign(responder_key, hashes.SHA256())
+
+
 class TestPolicyBuilder:
     def test_time_already_set(self):
         with pytest.raises(ValueError):
@@ -39,6 +313,20 @@ class TestPolicyBuilder:
         with pytest.raises(ValueError):
             PolicyBuilder().max_chain_depth(8).max_chain_depth(9)
 
+    def test_crls_already_set(self):
+        with pytest.raises(ValueError):
+            PolicyBuilder().crls([]).crls([])
+
+    def test_certificate_policies_already_set(self):
+        with pytest.raises(ValueError):
+            PolicyBuilder().certificate_policies().certificate_policies()
+
+    def test_ocsp_clock_skew_already_set(self):
+        with pytest.raises(ValueError):
+            PolicyBuilder().ocsp_clock_skew(
+                datetime.timedelta(minutes=5)
+            ).ocsp_clock_skew(datetime.timedelta(minutes=5))
+
     def test_ipaddress_subject(self):
         policy = (
             PolicyBuilder()
@@ -173,3 +461,3864 @@ class TestServerVerifier:
                 match="cert is not valid at validation time",
             ):
                 verifier.verify(leaf, [])
+
+    def test_verify_concurrently(self):
+        # Path building releases the GIL, so verifications on other threads
+        # can run at the same time.
+        root, intermediates, leaf = _build_mesh(2, 2)
+        verifier = (
+            PolicyBuilder()
+            .store(Store([root]))
+            .time(VALIDATION_TIME)
+            .build_server_verifier(DNSName("cryptography.io"))
+        )
+        with concurrent.futures.ThreadPoolExecutor(max_workers=4) as pool:
+            chains = list(
+                pool.map(
+                    lambda _: verifier.verify(leaf, intermediates), range(16)
+                )
+            )
+        assert all(chain == chains[0] for chain in chains)
+        assert chains[0][0] == leaf
+        assert chains[0][-1] == root
+
+
+class TestCRLRevocation:
+    def _verifier(self, root, crls, soft_fail=False):
+        return (
+            PolicyBuilder()
+            .store(Store([root]))
+            .time(VALIDATION_TIME)
+            .crls(crls, soft_fail=soft_fail)
+            .build_server_verifier(DNSName("cryptography.io"))
+        )
+
+    def test_not_revoked(self):
+        root_key, root, leaf = _build_chain()
+        crl = _build_crl(root_key, "root")
+        assert self._verifier(root, [crl]).verify(leaf, []) == [leaf, root]
+
+    def test_revoked(self):
+        root_key, root, leaf = _build_chain()
+        crl = _build_crl(root_key, "root", revoked=[leaf])
+        with pytest.raises(VerificationError, match="revoked"):
+            self._verifier(root, [crl]).verify(leaf, [])
+
+        # Soft-fail doesn't apply to certificates that are actually revoked.
+        with pytest.raises(VerificationError, match="revoked"):
+            self._verifier(root, [crl], soft_fail=True).verify(leaf, [])
+
+    def test_revoked_client(self):
+        root_key, root, leaf = _build_chain()
+        crl = _build_crl(root_key, "root", revoked=[leaf])
+        verifier = (
+            PolicyBuilder()
+            .store(Store([root]))
+            .time(VALIDATION_TIME)
+            .crls([crl])
+            .build_client_verifier()
+        )
+        with pytest.raises(VerificationError, match="revoked"):
+            verifier.verify(leaf, [])
+
+    def test_no_applicable_crl(self):
+        root_key, root, leaf = _build_chain()
+        with pytest.raises(
+            VerificationError, match="no applicable revocation"
+        ):
+            self._verifier(root, []).verify(leaf, [])
+        assert self._verifier(root, [], soft_fail=True).verify(leaf, []) == [
+            leaf,
+            root,
+        ]
+
+    def test_crl_wrong_signer(self):
+        root_key, root, leaf = _build_chain()
+        other_key = ec.generate_private_key(ec.SECP256R1())
+        crl = _build_crl(other_key, "root", revoked=[leaf])
+        with pytest.raises(
+            VerificationError, match="no applicable revocation"
+        ):
+            self._verifier(root, [crl]).verify(leaf, [])
+
+    def test_crl_out_of_date(self):
+        root_key, root, leaf = _build_chain()
+        crl = _build_crl(
+            root_key,
+            "root",
+            revoked=[leaf],
+            last_update=NOT_BEFORE,
+            next_update=NOT_BEFORE + datetime.timedelta(days=1),
+        )
+        with pytest.raises(
+            VerificationError, match="no applicable revocation"
+        ):
+            self._verifier(root, [crl]).verify(leaf, [])
+        assert self._verifier(root, [crl], soft_fail=True).verify(
+            leaf, []
+        ) == [leaf, root]
+
+    def test_crl_idp_scope(self):
+        dp = x509.UniformResourceIdentifier("http://example.com/leaf.crl")
+        root_key, root, leaf = _build_chain(
+            leaf_extensions=[
+                x509.CRLDistributionPoints(
+                    [x509.DistributionPoint([dp], None, None, None)]
+                )
+            ]
+        )
+
+        # A CA-only CRL doesn't cover the leaf.
+        ca_only = _build_crl(
+            root_key,
+            "root",
+            revoked=[leaf],
+            extensions=[
+                x509.IssuingDistributionPoint(
+                    None, None, False, True, None, False, False
+                )
+            ],
+        )
+        with pytest.raises(
+            VerificationError, match="no applicable revocation"
+        ):
+            self._verifier(root, [ca_only]).verify(leaf, [])
+
+        # A CRL for a different distribution point doesn't cover the leaf.
+        other_dp = _build_crl(
+            root_key,
+            "root",
+            revoked=[leaf],
+            extensions=[
+                x509.IssuingDistributionPoint(
+                    [x509.UniformResourceIdentifier("http://example.com/x")],
+                    None,
+                    False,
+                    False,
+                    None,
+                    False,
+                    False,
+                )
+            ],
+        )
+        with pytest.raises(
+            VerificationError, match="no applicable revocation"
+        ):
+            self._verifier(root, [other_dp]).verify(leaf, [])
+
+        # A CRL for the leaf's distribution point does.
+        matching_dp = _build_crl(
+            root_key,
+            "root",
+            revoked=[leaf],
+            extensions=[
+                x509.IssuingDistributionPoint(
+                    [dp], None, True, False, None, False, False
+                )
+            ],
+        )
+        with pytest.raises(VerificationError, match="revoked"):
+            self._verifier(root, [matching_dp]).verify(leaf, [])
+
+
+class TestOCSPStapling:
+    def _verifier(self, root, **kwargs):
+        builder = PolicyBuilder().store(Store([root])).time(VALIDATION_TIME)
+        if "crls" in kwargs:
+            builder = builder.crls(kwargs["crls"])
+        if "skew" in kwargs:
+            builder = builder.ocsp_clock_skew(kwargs["skew"])
+        return builder.build_server_verifier(DNSName("cryptography.io"))
+
+    def test_good(self):
+        root_key, root, leaf = _build_chain()
+        response = _build_ocsp_response(leaf, root, root, root_key)
+        verifier = self._verifier(root)
+        assert verifier.verify(leaf, [], ocsp_responses=[response]) == [
+            leaf,
+            root,
+        ]
+
+        # A good response satisfies hard-fail revocation checking.
+        verifier = self._verifier(root, crls=[])
+        assert verifier.verify(leaf, [], ocsp_responses=[response]) == [
+            leaf,
+            root,
+        ]
+
+    def test_good_by_key_hash(self):
+        root_key, root, leaf = _build_chain()
+        response = _build_ocsp_response(
+            leaf,
+            root,
+            root,
+            root_key,
+            encoding=ocsp.OCSPResponderEncoding.HASH,
+        )
+        assert self._verifier(root).verify(
+            leaf, [], ocsp_responses=[response]
+        ) == [leaf, root]
+
+    def test_revoked(self):
+        root_key, root, leaf = _build_chain()
+        response = _build_ocsp_response(
+            leaf, root, root, root_key, status=ocsp.OCSPCertStatus.REVOKED
+        )
+        with pytest.raises(VerificationError, match="revoked"):
+            self._verifier(root).verify(leaf, [], ocsp_responses=[response])
+
+    def test_revoked_client(self):
+        root_key, root, leaf = _build_chain()
+        response = _build_ocsp_response(
+            leaf, root, root, root_key, status=ocsp.OCSPCertStatus.REVOKED
+        )
+        verifier = (
+            PolicyBuilder()
+            .store(Store([root]))
+            .time(VALIDATION_TIME)
+            .build_client_verifier()
+        )
+        with pytest.raises(VerificationError, match="revoked"):
+            verifier.verify(leaf, [], ocsp_responses=[response])
+
+    def test_unknown(self):
+        root_key, root, leaf = _build_chain()
+        response = _build_ocsp_response(
+            leaf, root, root, root_key, status=ocsp.OCSPCertStatus.UNKNOWN
+        )
+        with pytest.raises(VerificationError, match="unknown"):
+            self._verifier(root).verify(leaf, [], ocsp_responses=[response])
+
+    def test_unrelated_response_ignored(self):
+        root_key, root, leaf = _build_chain()
+        _, _, other_leaf = _build_chain()
+        response = _build_ocsp_response(
+            other_leaf,
+            root,
+            root,
+            root_key,
+            status=ocsp.OCSPCertStatus.REVOKED,
+        )
+        assert self._verifier(root).verify(
+            leaf, [], ocsp_responses=[response]
+        ) == [leaf, root]
+
+    def test_wrong_signer(self):
+        root_key, root, leaf = _build_chain()
+        # A response that claims to come from the root, but that is signed
+        # by an unrelated key.
+        other_key = ec.generate_private_key(ec.SECP256R1())
+        fake_root = _build_cert(
+            "root", "root", other_key.public_key(), other_key, ca=True
+        )
+        response = _build_ocsp_response(leaf, root, fake_root, other_key)
+        with pytest.raises(VerificationError, match="authorized responder"):
+            self._verifier(root).verify(leaf, [], ocsp_responses=[response])
+
+    def test_delegated_responder(self):
+        root_key, root, leaf = _build_chain()
+        responder_key = ec.generate_private_key(ec.SECP256R1())
+        responder = _build_cert(
+            "responder",
+            "root",
+            responder_key.public_key(),
+            root_key,
+            ca=False,
+            extensions=[
+                x509.ExtendedKeyUsage([ExtendedKeyUsageOID.OCSP_SIGNING])
+            ],
+        )
+        response = _build_ocsp_response(
+            leaf,
+            root,
+            responder,
+            responder_key,
+            status=ocsp.OCSPCertStatus.REVOKED,
+            certificates=[responder],
+        )
+        with pytest.raises(VerificationError, match="revoked"):
+            self._verifier(root).verify(leaf, [], ocsp_responses=[response])
+
+    def test_delegated_responder_without_eku(self):
+        root_key, root, leaf = _build_chain()
+        responder_key = ec.generate_private_key(ec.SECP256R1())
+        responder = _build_cert(
+            "responder",
+            "root",
+            responder_key.public_key(),
+            root_key,
+            ca=False,
+            extensions=[
+                x509.ExtendedKeyUsage([ExtendedKeyUsageOID.SERVER_AUTH])
+            ],
+        )
+        response = _build_ocsp_response(
+            leaf, root, responder, responder_key, certificates=[responder]
+        )
+        with pytest.raises(VerificationError, match="authorized responder"):
+            self._verifier(root).verify(leaf, [], ocsp_responses=[response])
+
+    def test_not_current(self):
+        root_key, root, leaf = _build_chain()
+        stale = _build_ocsp_response(
+            leaf,
+            root,
+            root,
+            root_key,
+            this_update=VALIDATION_TIME - datetime.timedelta(days=2),
+            next_update=VALIDATION_TIME - datetime.timedelta(minutes=1),
+        )
+        future = _build_ocsp_response(
+            leaf,
+            root,
+            root,
+            root_key,
+            this_update=VALIDATION_TIME + datetime.timedelta(minutes=1),
+            next_update=VALIDATION_TIME + datetime.timedelta(days=2),
+        )
+        for response in [stale, future]:
+            with pytest.raises(VerificationError, match="not current"):
+                self._verifier(root).verify(
+                    leaf, [], ocsp_responses=[response]
+                )
+
+            verifier = self._verifier(
+                root, skew=datetime.timedelta(minutes=5)
+            )
+            assert verifier.verify(leaf, [], ocsp_responses=[response]) == [
+                leaf,
+                root,
+            ]
+
+    def test_unsuccessful_response(self):
+        root_key, root, leaf = _build_chain()
+        response = ocsp.OCSPResponseBuilder.build_unsuccessful(
+            ocsp.OCSPResponseStatus.TRY_LATER
+        )
+        with pytest.raises(ValueError):
+            self._verifier(root).verify(leaf, [], ocsp_responses=[response])
+
+
+POLICY_1 = x509.ObjectIdentifier("1.3.6.1.4.1.11129.2.5.1")
+POLICY_2 = x509.ObjectIdentifier("1.3.6.1.4.1.11129.2.5.2")
+
+
+def _policies(*oids: x509.ObjectIdentifier) -> x509.CertificatePolicies:
+    return x509.CertificatePolicies(
+        [x509.PolicyInformation(oid, None) for oid in oids]
+    )
+
+
+def _der_oid(oid: x509.ObjectIdentifier) -> bytes:
+    arcs = [int(arc) for arc in oid.dotted_string.split(".")]
+    body = bytes([arcs[0] * 40 + arcs[1]])
+    for arc in arcs[2:]:
+        encoded = [arc & 0x7F]
+        arc >>= 7
+        while arc:
+            encoded.insert(0, 0x80 | (arc & 0x7F))
+            arc >>= 7
+        body += bytes(encoded)
+    return bytes([0x06, len(body)]) + body
+
+
+def _policy_mappings(
+    *mappings: typing.Tuple[x509.ObjectIdentifier, x509.ObjectIdentifier],
+) -> x509.UnrecognizedExtension:
+    # There is no PolicyMappings extension type to build this with.
+    body = b""
+    for issuer_policy, subject_policy in mappings:
+        mapping = _der_oid(issuer_policy) + _der_oid(subject_policy)
+        body += bytes([0x30, len(mapping)]) + mapping
+    return x509.UnrecognizedExtension(
+        x509.ExtensionOID.POLICY_MAPPINGS,
+        bytes([0x30, len(body)]) + body,
+    )
+
+
+class TestCertificatePolicies:
+    def _chain(
+        self,
+        intermediate_extensions: typing.Sequence[x509.ExtensionType] = (),
+        leaf_extensions: typing.Sequence[x509.ExtensionType] = (),
+    ) -> typing.Tuple[x509.Certificate, x509.Certificate, x509.Certificate]:
+        root_key = ec.generate_private_key(ec.SECP256R1())
+        root = _build_cert(
+            "root", "root", root_key.public_key(), root_key, ca=True
+        )
+        intermediate_key = ec.generate_private_key(ec.SECP256R1())
+        intermediate = _build_cert(
+            "intermediate",
+            "root",
+            intermediate_key.public_key(),
+            root_key,
+            ca=True,
+            extensions=intermediate_extensions,
+        )
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        leaf = _build_cert(
+            "leaf",
+            "intermediate",
+            leaf_key.public_key(),
+            intermediate_key,
+            ca=False,
+            extensions=leaf_extensions,
+        )
+        return root, intermediate, leaf
+
+    def _builder(self, root):
+        return PolicyBuilder().store(Store([root])).time(VALIDATION_TIME)
+
+    def _verify(self, builder, intermediate, leaf):
+        verifier = builder.build_server_verifier(DNSName("cryptography.io"))
+        return verifier.verify_chain(leaf, [intermediate])
+
+    def test_disabled(self):
+        root, intermediate, leaf = self._chain(
+            intermediate_extensions=[
+                x509.PolicyConstraints(
+                    require_explicit_policy=0, inhibit_policy_mapping=None
+                )
+            ]
+        )
+        verified = self._verify(self._builder(root), intermediate, leaf)
+        assert verified.chain == [leaf, intermediate, root]
+        assert verified.policies is None
+
+    def test_no_policies(self):
+        root, intermediate, leaf = self._chain()
+        builder = self._builder(root).certificate_policies()
+        verified = self._verify(builder, intermediate, leaf)
+        assert verified.chain == [leaf, intermediate, root]
+        assert verified.policies == []
+
+    def test_explicit_policy(self):
+        root, intermediate, leaf = self._chain()
+        builder = self._builder(root).certificate_policies(
+            explicit_policy=True
+        )
+        with pytest.raises(VerificationError, match="no valid policy"):
+            self._verify(builder, intermediate, leaf)
+
+    def test_require_explicit_policy_constraint(self):
+        root, intermediate, leaf = self._chain(
+            intermediate_extensions=[
+                x509.PolicyConstraints(
+                    require_explicit_policy=0, inhibit_policy_mapping=None
+                )
+            ]
+        )
+        builder = self._builder(root).certificate_policies()
+        with pytest.raises(VerificationError, match="no valid policy"):
+            self._verify(builder, intermediate, leaf)
+
+    def test_valid_policy(self):
+        root, intermediate, leaf = self._chain(
+            intermediate_extensions=[_policies(POLICY_1, POLICY_2)],
+            leaf_extensions=[_policies(POLICY_1)],
+        )
+        builder = self._builder(root).certificate_policies(
+            explicit_policy=True
+        )
+        assert self._verify(builder, intermediate, leaf).policies == [
+            POLICY_1
+        ]
+
+    def test_initial_policy_set(self):
+        root, intermediate, leaf = self._chain(
+            intermediate_extensions=[_policies(POLICY_1, POLICY_2)],
+            leaf_extensions=[_policies(POLICY_1, POLICY_2)],
+        )
+        builder = self._builder(root).certificate_policies(
+            [POLICY_2], explicit_policy=True
+        )
+        assert self._verify(builder, intermediate, leaf).policies == [
+            POLICY_2
+        ]
+
+        root, intermediate, leaf = self._chain(
+            intermediate_extensions=[_policies(POLICY_1)],
+            leaf_extensions=[_policies(POLICY_1)],
+        )
+        builder = self._builder(root).certificate_policies(
+            [POLICY_2], explicit_policy=True
+        )
+        with pytest.raises(VerificationError, match="no acceptable policy"):
+            self._verify(builder, intermediate, leaf)
+
+    def test_any_policy(self):
+        root, intermediate, leaf = self._chain(
+            intermediate_extensions=[
+                _policies(x509.CertificatePoliciesOID.ANY_POLICY)
+            ],
+            leaf_extensions=[_policies(POLICY_1)],
+        )
+        builder = self._builder(root).certificate_policies(
+            explicit_policy=True
+        )
+        assert self._verify(builder, intermediate, leaf).policies == [
+            POLICY_1
+        ]
+
+        builder = self._builder(root).certificate_policies(
+            explicit_policy=True, any_policy_inhibit=True
+        )
+        with pytest.raises(VerificationError, match="no valid policy"):
+            self._verify(builder, intermediate, leaf)
+
+    def test_inhibit_any_policy_extension(self):
+        root, intermediate, leaf = self._chain(
+            intermediate_extensions=[
+                _policies(x509.CertificatePoliciesOID.ANY_POLICY),
+                x509.InhibitAnyPolicy(0),
+            ],
+            leaf_extensions=[
+                _policies(x509.CertificatePoliciesOID.ANY_POLICY)
+            ],
+        )
+        builder = self._builder(root).certificate_policies(
+            explicit_policy=True
+        )
+        with pytest.raises(VerificationError, match="no valid policy"):
+            self._verify(builder, intermediate, leaf)
+
+    def test_policy_mapping(self):
+        root, intermediate, leaf = self._chain(
+            intermediate_extensions=[
+                _policies(POLICY_1),
+                _policy_mappings((POLICY_1, POLICY_2)),
+            ],
+            leaf_extensions=[_policies(POLICY_2)],
+        )
+        builder = self._builder(root).certificate_policies(
+            explicit_policy=True
+        )
+        # Policies are reported in the trust anchor's domain.
+        assert self._verify(builder, intermediate, leaf).policies == [
+            POLICY_1
+        ]
+
+        builder = self._builder(root).certificate_policies(
+            explicit_policy=True, policy_mapping_inhibit=True
+        )
+        with pytest.raises(VerificationError, match="no valid policy"):
+            self._verify(builder, intermediate, leaf)
+
+    def test_policy_mapping_any_policy_rejected(self):
+        root, intermediate, leaf = self._chain(
+            intermediate_extensions=[
+                _policy_mappings(
+                    (x509.CertificatePoliciesOID.ANY_POLICY, POLICY_2)
+                ),
+            ],
+        )
+        builder = self._builder(root)
+        with pytest.raises(VerificationError):
+            self._verify(builder, intermediate, leaf)
+
+    def test_client_verifier(self):
+        root, intermediate, leaf = self._chain(
+            intermediate_extensions=[_policies(POLICY_1)],
+            leaf_extensions=[_policies(POLICY_1)],
+        )
+        verifier = self._builder(root).build_client_verifier()
+        assert verifier.verify(leaf, [intermediate]).policies is None
+
+        verifier = (
+            self._builder(root)
+            .certificate_policies(explicit_policy=True)
+            .build_client_verifier()
+        )
+        verified_client = verifier.verify(leaf, [intermediate])
+        assert verified_client.chain == [leaf, intermediate, root]
+        assert verified_client.policies == [POLICY_1]
+
+
+class TestVerifyAll:
+    def test_verify_all(self):
+        root_a, root_b, intermediate_a, intermediate_b, leaf = (
+            _build_cross_signed()
+        )
+        verifier = _server_verifier(_builder(root_a, root_b))
+        intermediates = [intermediate_a, intermediate_b]
+
+        paths = verifier.verify_all(leaf, intermediates)
+        assert [path.chain for path in paths] == [
+            [leaf, intermediate_a, root_a],
+            [leaf, intermediate_b, root_b],
+        ]
+        assert all(path.policies is None for path in paths)
+        # The first path is the one `verify` picks.
+        assert paths[0].chain == verifier.verify(leaf, intermediates)
+
+        paths = verifier.verify_all(leaf, intermediates, limit=1)
+        assert [path.chain for path in paths] == [
+            [leaf, intermediate_a, root_a]
+        ]
+
+    def test_verify_all_skips_invalid_paths(self):
+        root_a, root_b, intermediate_a, intermediate_b, leaf = (
+            _build_cross_signed()
+        )
+        verifier = _server_verifier(_builder(root_b))
+        paths = verifier.verify_all(leaf, [intermediate_a, intermediate_b])
+        assert [path.chain for path in paths] == [
+            [leaf, intermediate_b, root_b]
+        ]
+
+        _, unrelated_root, _ = _build_chain()
+        verifier = _server_verifier(_builder(unrelated_root))
+        with pytest.raises(VerificationError):
+            verifier.verify_all(leaf, [intermediate_a, intermediate_b])
+
+    def test_verify_all_ranking(self):
+        root_a, root_b, intermediate_a, intermediate_b, leaf = (
+            _build_cross_signed()
+        )
+        verifier = _server_verifier(_builder(root_a, root_b))
+        paths = verifier.verify_all(
+            leaf,
+            [intermediate_a, intermediate_b],
+            key=lambda path: path.chain[-1] != root_b,
+        )
+        assert [path.chain[-1] for path in paths] == [root_b, root_a]
+
+        # The best path is returned even though it's found after `limit`
+        # paths have been.
+        paths = verifier.verify_all(
+            leaf,
+            [intermediate_a, intermediate_b],
+            limit=1,
+            key=lambda path: path.chain[-1] != root_b,
+        )
+        assert [path.chain for path in paths] == [
+            [leaf, intermediate_b, root_b]
+        ]
+
+        def bad_key(path):
+            raise ZeroDivisionError
+
+        with pytest.raises(ZeroDivisionError):
+            verifier.verify_all(
+                leaf, [intermediate_a, intermediate_b], key=bad_key
+            )
+
+    def test_verify_all_client(self):
+        root_a, root_b, intermediate_a, intermediate_b, leaf = (
+            _build_cross_signed()
+        )
+        verifier = _builder(root_a, root_b).build_client_verifier()
+        paths = verifier.verify_all(
+            leaf,
+            [intermediate_a, intermediate_b],
+            key=lambda path: path.chain[-1] != root_b,
+        )
+        assert [path.chain for path in paths] == [
+            [leaf, intermediate_b, root_b],
+            [leaf, intermediate_a, root_a],
+        ]
+        assert all(
+            path.subjects == [DNSName("cryptography.io")] for path in paths
+        )
+
+    def test_verify_all_invalid_limit(self):
+        _, root, leaf = _build_chain()
+        verifier = _server_verifier(_builder(root))
+        with pytest.raises(ValueError):
+            verifier.verify_all(leaf, [], limit=0)
+
+
+def _build_batch() -> typing.Tuple[
+    x509.Certificate,
+    x509.Certificate,
+    typing.List[typing.Tuple[x509.Certificate, typing.List[x509.Certificate]]],
+]:
+    # Several leaves sharing an intermediate, and one from an unknown issuer.
+    root_key, root = _build_ca("root")
+    intermediate_key, intermediate = _build_ca(
+        "intermediate", "root", root_key
+    )
+    other_key = ec.generate_private_key(ec.SECP256R1())
+    items = [
+        (_build_leaf("intermediate", issuer_key), [intermediate])
+        for issuer_key in [intermediate_key] * 4 + [other_key]
+    ]
+    return root, intermediate, items
+
+
+class TestVerifyMany:
+    @pytest.mark.parametrize("max_workers", [None, 1, 2])
+    def test_server_verify_many(self, max_workers):
+        root, intermediate, items = _build_batch()
+        verifier = _server_verifier(_builder(root))
+
+        results = verifier.verify_many(items, max_workers=max_workers)
+        assert len(results) == len(items)
+        for (leaf, _), result in zip(items[:-1], results[:-1]):
+            assert result == [leaf, intermediate, root]
+        assert isinstance(results[-1], VerificationError)
+        assert (
+            results[-1].diagnostic.reason == FailureReason.CANDIDATES_EXHAUSTED
+        )
+
+    def test_client_verify_many(self):
+        root, intermediate, items = _build_batch()
+        verifier = _builder(root).build_client_verifier()
+
+        results = verifier.verify_many(items)
+        assert len(results) == len(items)
+        for (leaf, _), result in zip(items[:-1], results[:-1]):
+            assert isinstance(result, VerifiedClient)
+            assert result.chain == [leaf, intermediate, root]
+            assert result.subjects == [DNSName("cryptography.io")]
+        assert isinstance(results[-1], VerificationError)
+
+    def test_verify_many_empty(self):
+        _, root, _ = _build_chain()
+        assert _server_verifier(_builder(root)).verify_many([]) == []
+
+    def test_verify_many_must_staple(self):
+        root_key, root, leaf = _build_chain(leaf_extensions=[_must_staple()])
+        response = _build_ocsp_response(leaf, root, root, root_key)
+        revoked = _build_ocsp_response(
+            leaf, root, root, root_key, status=ocsp.OCSPCertStatus.REVOKED
+        )
+        verifier = _server_verifier(_builder(root))
+
+        results = verifier.verify_many(
+            [
+                (leaf, []),
+                (leaf, [], None),
+                (leaf, [], [response]),
+                (leaf, [], [revoked]),
+            ]
+        )
+        assert len(results) == 4
+        for result in results[:2]:
+            assert isinstance(result, VerificationError)
+            assert (
+                result.diagnostic.candidates[0].reason
+                == FailureReason.MUST_STAPLE_VIOLATION
+            )
+        assert results[2] == [leaf, root]
+        assert isinstance(results[3], VerificationError)
+        assert "revoked" in str(results[3])
+
+    def test_verify_many_invalid_max_workers(self):
+        root, _, items = _build_batch()
+        verifier = _server_verifier(_builder(root))
+        with pytest.raises(ValueError, match="max_workers"):
+            verifier.verify_many(items, max_workers=0)
+
+
+class TestIssuerLookup:
+    def test_aki_match_preferred(self):
+        root_key = ec.generate_private_key(ec.SECP256R1())
+        root = _build_cert(
+            "root", "root", root_key.public_key(), root_key, ca=True
+        )
+        # Two intermediates with the same subject and key, but only one of
+        # which has the SKI that the leaf's AKI refers to.
+        intermediate_key = ec.generate_private_key(ec.SECP256R1())
+        intermediates = [
+            _build_cert(
+                "intermediate",
+                "root",
+                intermediate_key.public_key(),
+                root_key,
+                ca=True,
+                subject_key_identifier=b"\x00" * 20,
+            ),
+            _build_cert(
+                "intermediate",
+                "root",
+                intermediate_key.public_key(),
+                root_key,
+                ca=True,
+            ),
+        ]
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        leaf = _build_cert(
+            "leaf",
+            "intermediate",
+            leaf_key.public_key(),
+            intermediate_key,
+            ca=False,
+        )
+
+        verifier = (
+            PolicyBuilder()
+            .store(Store([root]))
+            .time(VALIDATION_TIME)
+            .build_server_verifier(DNSName("cryptography.io"))
+        )
+        assert verifier.verify(leaf, intermediates) == [
+            leaf,
+            intermediates[1],
+            root,
+        ]
+        paths = verifier.verify_all(leaf, intermediates)
+        assert [path.chain for path in paths] == [
+            [leaf, intermediates[1], root],
+            [leaf, intermediates[0], root],
+        ]
+
+    def test_rekeyed_root(self):
+        old_root_key = ec.generate_private_key(ec.SECP256R1())
+        old_root = _build_cert(
+            "root", "root", old_root_key.public_key(), old_root_key, ca=True
+        )
+        root_key = ec.generate_private_key(ec.SECP256R1())
+        root = _build_cert(
+            "root", "root", root_key.public_key(), root_key, ca=True
+        )
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        leaf = _build_cert(
+            "leaf", "root", leaf_key.public_key(), root_key, ca=False
+        )
+
+        verifier = (
+            PolicyBuilder()
+            .store(Store([old_root, root]))
+            .time(VALIDATION_TIME)
+            .build_server_verifier(DNSName("cryptography.io"))
+        )
+        assert verifier.verify(leaf, []) == [leaf, root]
+
+
+class TestDiagnostics:
+    def _verifier(self, *roots, max_chain_depth=None):
+        builder = (
+            PolicyBuilder().store(Store(list(roots))).time(VALIDATION_TIME)
+        )
+        if max_chain_depth is not None:
+            builder = builder.max_chain_depth(max_chain_depth)
+        return builder.build_server_verifier(DNSName("cryptography.io"))
+
+    def test_leaf_rejected(self):
+        _, root, leaf = _build_chain(
+            leaf_extensions=[
+                x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CLIENT_AUTH])
+            ]
+        )
+        with pytest.raises(VerificationError) as exc_info:
+            self._verifier(root).verify(leaf, [])
+
+        diagnostic = exc_info.value.diagnostic
+        assert diagnostic.certificate == leaf
+        assert diagnostic.depth == 0
+        assert diagnostic.reason == FailureReason.EXTENDED_KEY_USAGE_MISMATCH
+        assert diagnostic.message == "required EKU not found"
+        assert diagnostic.candidates == []
+
+    def test_expired(self):
+        _, root, leaf = _build_chain()
+        verifier = (
+            PolicyBuilder()
+            .store(Store([root]))
+            .time(NOT_AFTER + datetime.timedelta(days=1))
+            .build_server_verifier(DNSName("cryptography.io"))
+        )
+        with pytest.raises(VerificationError) as exc_info:
+            verifier.verify(leaf, [])
+
+        diagnostic = exc_info.value.diagnostic
+        assert diagnostic.reason == FailureReason.EXPIRED
+        assert diagnostic.candidates == []
+
+    def test_bad_signature(self):
+        _, root, _ = _build_chain()
+        other_key = ec.generate_private_key(ec.SECP256R1())
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        leaf = _build_cert(
+            "leaf", "root", leaf_key.public_key(), other_key, ca=False
+        )
+        with pytest.raises(VerificationError) as exc_info:
+            self._verifier(root).verify(leaf, [])
+
+        diagnostic = exc_info.value.diagnostic
+        assert diagnostic.certificate == leaf
+        assert diagnostic.reason == FailureReason.CANDIDATES_EXHAUSTED
+        [candidate] = diagnostic.candidates
+        assert candidate.certificate == root
+        assert candidate.depth == 1
+        assert candidate.reason == FailureReason.BAD_SIGNATURE
+        assert candidate.candidates == []
+
+    def test_no_candidates(self):
+        _, _, leaf = _build_chain()
+        other_key = ec.generate_private_key(ec.SECP256R1())
+        other_root = _build_cert(
+            "other", "other", other_key.public_key(), other_key, ca=True
+        )
+        with pytest.raises(VerificationError) as exc_info:
+            self._verifier(other_root).verify(leaf, [])
+
+        diagnostic = exc_info.value.diagnostic
+        assert diagnostic.reason == FailureReason.CANDIDATES_EXHAUSTED
+        assert diagnostic.candidates == []
+
+    def test_revoked(self):
+        root_key, root, leaf = _build_chain()
+        verifier = (
+            PolicyBuilder()
+            .store(Store([root]))
+            .time(VALIDATION_TIME)
+            .crls([_build_crl(root_key, "root", revoked=[leaf])])
+            .build_server_verifier(DNSName("cryptography.io"))
+        )
+        with pytest.raises(VerificationError) as exc_info:
+            verifier.verify(leaf, [])
+
+        [candidate] = exc_info.value.diagnostic.candidates
+        assert candidate.certificate == root
+        assert candidate.reason == FailureReason.REVOKED
+
+    def test_tree(self):
+        root_key = ec.generate_private_key(ec.SECP256R1())
+        root = _build_cert(
+            "root", "root", root_key.public_key(), root_key, ca=True
+        )
+        intermediate_key = ec.generate_private_key(ec.SECP256R1())
+        # One intermediate excludes the leaf's name, and the other is
+        # fine but too deep.
+        constrained = _build_cert(
+            "intermediate",
+            "root",
+            intermediate_key.public_key(),
+            root_key,
+            ca=True,
+            extensions=[
+                x509.NameConstraints(
+                    permitted_subtrees=None,
+                    excluded_subtrees=[DNSName("cryptography.io")],
+                )
+            ],
+        )
+        unconstrained = _build_cert(
+            "intermediate",
+            "root",
+            intermediate_key.public_key(),
+            root_key,
+            ca=True,
+            subject_key_identifier=b"\x00" * 20,
+        )
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        leaf = _build_cert(
+            "leaf",
+            "intermediate",
+            leaf_key.public_key(),
+            intermediate_key,
+            ca=False,
+        )
+
+        verifier = self._verifier(root, max_chain_depth=0)
+        with pytest.raises(VerificationError) as exc_info:
+            verifier.verify(leaf, [constrained, unconstrained])
+
+        diagnostic = exc_info.value.diagnostic
+        assert diagnostic.certificate == leaf
+        assert diagnostic.reason == FailureReason.CANDIDATES_EXHAUSTED
+        assert [
+            (c.certificate, c.depth, c.reason) for c in diagnostic.candidates
+        ] == [
+            (constrained, 1, FailureReason.NAME_CONSTRAINT_VIOLATION),
+            (unconstrained, 1, FailureReason.DEPTH_EXCEEDED),
+        ]
+        assert all(c.candidates == [] for c in diagnostic.candidates)
+
+        # With enough depth, the unconstrained intermediate leads to a root.
+        verifier = self._verifier(root)
+        assert verifier.verify(leaf, [constrained, unconstrained]) == [
+            leaf,
+            unconstrained,
+            root,
+        ]
+
+
+class TestExtensionPolicies:
+    PRIVATE_OID = x509.ObjectIdentifier("1.3.6.1.4.1.55555.1")
+
+    def _private_extension(self) -> x509.UnrecognizedExtension:
+        return x509.UnrecognizedExtension(self.PRIVATE_OID, b"\x05\x00")
+
+    def _builder(self, root: x509.Certificate) -> PolicyBuilder:
+        return PolicyBuilder().store(Store([root])).time(VALIDATION_TIME)
+
+    def _leaf(self, root_key, **kwargs) -> x509.Certificate:
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        return _build_cert(
+            "leaf", "root", leaf_key.public_key(), root_key, False, **kwargs
+        )
+
+    def test_unknown_critical_extension(self):
+        root_key, root, _ = _build_chain()
+        leaf = self._leaf(
+            root_key, critical_extensions=[self._private_extension()]
+        )
+
+        verifier = self._builder(root).build_client_verifier()
+        with pytest.raises(VerificationError, match="critical extensions"):
+            verifier.verify(leaf, [])
+
+        # Registering the extension accepts it, even though it's critical.
+        verifier = (
+            self._builder(root)
+            .ee_extension(self.PRIVATE_OID)
+            .build_client_verifier()
+        )
+        assert verifier.verify(leaf, []).chain == [leaf, root]
+
+    def test_validator(self):
+        root_key, root, _ = _build_chain()
+        leaf = self._leaf(
+            root_key, critical_extensions=[self._private_extension()]
+        )
+        calls = []
+
+        def validator(cert, ext):
+            calls.append((cert, ext))
+
+        verifier = (
+            self._builder(root)
+            .ee_extension(self.PRIVATE_OID, validator=validator)
+            .build_client_verifier()
+        )
+        verifier.verify(leaf, [])
+        assert calls == [
+            (
+                leaf,
+                x509.Extension(
+                    self.PRIVATE_OID, True, self._private_extension()
+                ),
+            )
+        ]
+
+        # Validators also run when their extension is absent.
+        calls.clear()
+        verifier.verify(self._leaf(root_key), [])
+        assert len(calls) == 1
+        assert calls[0][1] is None
+
+    def test_validator_rejects(self):
+        root_key, root, _ = _build_chain()
+        leaf = self._leaf(root_key)
+
+        def validator(cert, ext):
+            raise ValueError("not today")
+
+        verifier = (
+            self._builder(root)
+            .ca_extension(ExtensionOID.BASIC_CONSTRAINTS, validator=validator)
+            .build_client_verifier()
+        )
+        with pytest.raises(VerificationError, match="not today"):
+            verifier.verify(leaf, [])
+
+    def test_presence(self):
+        root_key, root, _ = _build_chain()
+        leaf = self._leaf(root_key)
+
+        verifier = (
+            self._builder(root)
+            .ca_extension(
+                ExtensionOID.NAME_CONSTRAINTS,
+                presence=ExtensionPresence.REQUIRED,
+            )
+            .build_client_verifier()
+        )
+        with pytest.raises(VerificationError, match="missing required"):
+            verifier.verify(leaf, [])
+
+        verifier = (
+            self._builder(root)
+            .ee_extension(
+                ExtensionOID.SUBJECT_KEY_IDENTIFIER,
+                presence=ExtensionPresence.PROHIBITED,
+            )
+            .build_client_verifier()
+        )
+        with pytest.raises(VerificationError, match="prohibited"):
+            verifier.verify(leaf, [])
+
+    def test_criticality(self):
+        root_key, root, _ = _build_chain()
+        leaf = self._leaf(root_key)
+
+        verifier = (
+            self._builder(root)
+            .ee_extension(
+                ExtensionOID.SUBJECT_KEY_IDENTIFIER,
+                criticality=Criticality.CRITICAL,
+            )
+            .build_client_verifier()
+        )
+        with pytest.raises(VerificationError, match="incorrect criticality"):
+            verifier.verify(leaf, [])
+
+    def test_invalid_arguments(self):
+        builder = PolicyBuilder().ee_extension(self.PRIVATE_OID)
+        with pytest.raises(ValueError):
+            builder.ee_extension(self.PRIVATE_OID)
+        # The CA and EE policies are configured independently.
+        builder.ca_extension(self.PRIVATE_OID)
+
+        with pytest.raises(TypeError):
+            PolicyBuilder().ee_extension(
+                self.PRIVATE_OID,
+                presence="required",  # type: ignore[arg-type]
+            )
+        with pytest.raises(TypeError):
+            PolicyBuilder().ee_extension(
+                self.PRIVATE_OID,
+                criticality="critical",  # type: ignore[arg-type]
+            )
+        with pytest.raises(TypeError):
+            PolicyBuilder().ee_extension(
+                self.PRIVATE_OID,
+                validator=1,  # type: ignore[arg-type]
+            )
+
+
+def _der(tag: int, content: bytes) -> bytes:
+    if len(content) < 0x80:
+        length = bytes([len(content)])
+    else:
+        n = len(content).to_bytes((len(content).bit_length() + 7) // 8, "big")
+        length = bytes([0x80 | len(n)]) + n
+    return bytes([tag]) + length + content
+
+
+def _der_header(der: bytes) -> typing.Tuple[int, int]:
+    # Returns the length of the first TLV's header, and of its content.
+    if der[1] < 0x80:
+        return 2, der[1]
+    n = der[1] & 0x7F
+    return 2 + n, int.from_bytes(der[2 : 2 + n], "big")
+
+
+def _der_content(der: bytes) -> bytes:
+    header, length = _der_header(der)
+    return der[header : header + length]
+
+
+def _der_items(der: bytes) -> typing.List[bytes]:
+    items = []
+    while der:
+        header, length = _der_header(der)
+        items.append(der[: header + length])
+        der = der[header + length :]
+    return items
+
+
+def _replace_spki(cert: x509.Certificate, spki: bytes) -> x509.Certificate:
+    # Re-encodes `cert` with `spki` as its SubjectPublicKeyInfo, without
+    # re-signing it.
+    old_spki = cert.public_key().public_bytes(
+        serialization.Encoding.DER,
+        serialization.PublicFormat.SubjectPublicKeyInfo,
+    )
+    der = cert.public_bytes(serialization.Encoding.DER)
+    tbs, signature_algorithm, signature = _der_items(_der_content(der))
+    tbs = _der(0x30, _der_content(tbs).replace(old_spki, spki))
+    return x509.load_der_x509_certificate(
+        _der(0x30, tbs + signature_algorithm + signature)
+    )
+
+
+class TestAlgorithmPolicies:
+    def _builder(self, root: x509.Certificate) -> PolicyBuilder:
+        return PolicyBuilder().store(Store([root])).time(VALIDATION_TIME)
+
+    def _chain(
+        self, root_key, leaf_key, hash_algorithm=hashes.SHA256()
+    ) -> typing.Tuple[x509.Certificate, x509.Certificate]:
+        root = _build_cert(
+            "root",
+            "root",
+            root_key.public_key(),
+            root_key,
+            ca=True,
+            hash_algorithm=hash_algorithm,
+        )
+        leaf = _build_cert(
+            "leaf",
+            "root",
+            leaf_key.public_key(),
+            root_key,
+            ca=False,
+            hash_algorithm=hash_algorithm,
+        )
+        return root, leaf
+
+    def test_minimum_rsa_modulus(self):
+        root_key = rsa.generate_private_key(65537, 1024)
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        root, leaf = self._chain(root_key, leaf_key)
+
+        verifier = self._builder(root).build_client_verifier()
+        with pytest.raises(VerificationError, match="RSA key is too weak"):
+            verifier.verify(leaf, [])
+
+        verifier = (
+            self._builder(root)
+            .minimum_rsa_modulus(1024)
+            .build_client_verifier()
+        )
+        assert verifier.verify(leaf, []).chain == [leaf, root]
+
+    def test_signature_algorithms(self):
+        root_key = ec.generate_private_key(ec.SECP256R1())
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        root, leaf = self._chain(root_key, leaf_key)
+
+        verifier = (
+            self._builder(root)
+            .signature_algorithms([SignatureAlgorithmOID.ECDSA_WITH_SHA384])
+            .build_client_verifier()
+        )
+        with pytest.raises(VerificationError, match="Forbidden signature"):
+            verifier.verify(leaf, [])
+
+        verifier = (
+            self._builder(root)
+            .signature_algorithms([SignatureAlgorithmOID.ECDSA_WITH_SHA256])
+            .build_client_verifier()
+        )
+        assert verifier.verify(leaf, []).chain == [leaf, root]
+
+    def test_non_webpki_signature_algorithm(self):
+        root_key = rsa.generate_private_key(65537, 2048)
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        root, leaf = self._chain(root_key, leaf_key, hashes.SHA224())
+
+        verifier = self._builder(root).build_client_verifier()
+        with pytest.raises(VerificationError, match="Forbidden signature"):
+            verifier.verify(leaf, [])
+
+        verifier = (
+            self._builder(root)
+            .signature_algorithms([SignatureAlgorithmOID.RSA_WITH_SHA224])
+            .build_client_verifier()
+        )
+        assert verifier.verify(leaf, []).chain == [leaf, root]
+
+    def test_rsa_pss_key_parameters(self):
+        # An id-RSASSA-PSS key that's restricted to SHA-256, as OpenSSL
+        # encodes it in RSASSA-PSS-params.
+        pem = load_vectors_from_file(
+            os.path.join(
+                "asymmetric", "PKCS8", "rsa_pss_2048_hash_mask_salt.pem"
+            ),
+            lambda f: f.read(),
+            mode="rb",
+        )
+        root_key = serialization.load_pem_private_key(
+            pem, None, unsafe_skip_rsa_key_validation=True
+        )
+        assert isinstance(root_key, rsa.RSAPrivateKey)
+        pss = padding.PSS(padding.MGF1(hashes.SHA256()), 32)
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        root = _build_cert(
+            "root",
+            "root",
+            root_key.public_key(),
+            root_key,
+            ca=True,
+            rsa_padding=pss,
+        )
+        leaf = _build_cert(
+            "leaf",
+            "root",
+            leaf_key.public_key(),
+            root_key,
+            ca=False,
+            rsa_padding=pss,
+        )
+
+        # Loading the key drops its restrictions, so put them back in the
+        # root's SPKI. The root's own signature isn't checked.
+        pkcs8 = base64.b64decode(b"".join(pem.splitlines()[1:-1]))
+        _, algorithm, _ = _der_items(_der_content(pkcs8))
+        key_der = root_key.public_key().public_bytes(
+            serialization.Encoding.DER, serialization.PublicFormat.PKCS1
+        )
+        spki = _der(0x30, algorithm + _der(0x03, b"\x00" + key_der))
+        root = _replace_spki(root, spki)
+
+        verifier = self._builder(root).build_client_verifier()
+        with pytest.raises(VerificationError, match="Forbidden public key"):
+            verifier.verify(leaf, [])
+
+        verifier = (
+            self._builder(root)
+            .public_key_algorithms([PublicKeyAlgorithmOID.RSASSA_PSS])
+            .build_client_verifier()
+        )
+        assert verifier.verify(leaf, []).chain == [leaf, root]
+
+    def test_ed25519(self):
+        root_key = ed25519.Ed25519PrivateKey.generate()
+        leaf_key = ed25519.Ed25519PrivateKey.generate()
+        root, leaf = self._chain(root_key, leaf_key, None)
+
+        verifier = self._builder(root).build_client_verifier()
+        with pytest.raises(VerificationError, match="Forbidden"):
+            verifier.verify(leaf, [])
+
+        verifier = (
+            self._builder(root)
+            .public_key_algorithms([PublicKeyAlgorithmOID.ED25519])
+            .signature_algorithms([SignatureAlgorithmOID.ED25519])
+            .build_client_verifier()
+        )
+        assert verifier.verify(leaf, []).chain == [leaf, root]
+
+    def test_ec_curves(self):
+        root_key = ec.generate_private_key(ec.SECP256K1())
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        root, leaf = self._chain(root_key, leaf_key)
+
+        verifier = self._builder(root).build_client_verifier()
+        with pytest.raises(VerificationError, match="Forbidden public key"):
+            verifier.verify(leaf, [])
+
+        verifier = (
+            self._builder(root)
+            .ec_curves([ec.SECP256K1()])
+            .build_client_verifier()
+        )
+        assert verifier.verify(leaf, []).chain == [leaf, root]
+
+        # Without EC in the permitted public key algorithms, the curves
+        # are irrelevant.
+        verifier = (
+            self._builder(root)
+            .ec_curves([ec.SECP256K1()])
+            .public_key_algorithms([PublicKeyAlgorithmOID.RSAES_PKCS1_v1_5])
+            .build_client_verifier()
+        )
+        with pytest.raises(VerificationError, match="Forbidden public key"):
+            verifier.verify(leaf, [])
+
+    def test_invalid_arguments(self):
+        builder = (
+            PolicyBuilder()
+            .minimum_rsa_modulus(1024)
+            .signature_algorithms([SignatureAlgorithmOID.ED25519])
+            .public_key_algorithms([PublicKeyAlgorithmOID.ED25519])
+            .ec_curves([ec.SECP256R1()])
+        )
+        with pytest.raises(ValueError):
+            builder.minimum_rsa_modulus(2048)
+        with pytest.raises(ValueError):
+            builder.signature_algorithms([])
+        with pytest.raises(ValueError):
+            builder.public_key_algorithms([])
+        with pytest.raises(ValueError):
+            builder.ec_curves([])
+
+        with pytest.raises(ValueError, match="Unsupported signature"):
+            PolicyBuilder().signature_algorithms(
+                [SignatureAlgorithmOID.DSA_WITH_SHA256]
+            )
+        with pytest.raises(ValueError, match="Unsupported public key"):
+            PolicyBuilder().public_key_algorithms(
+                [PublicKeyAlgorithmOID.X25519]
+            )
+        with pytest.raises(TypeError):
+            PolicyBuilder().ec_curves(
+                [ec.SECP256R1]  # type: ignore[list-item]
+            )
+
+
+class TestNameConstraints:
+    def _verify(
+        self,
+        constraints: x509.NameConstraints,
+        leaf_subject: x509.Name,
+        leaf_sans: typing.Sequence[x509.GeneralName] = (),
+    ) -> None:
+        root_key = ec.generate_private_key(ec.SECP256R1())
+        root = _build_cert(
+            "root", "root", root_key.public_key(), root_key, ca=True
+        )
+        intermediate_key = ec.generate_private_key(ec.SECP256R1())
+        intermediate = _build_cert(
+            "intermediate",
+            "root",
+            intermediate_key.public_key(),
+            root_key,
+            ca=True,
+            extensions=[constraints],
+        )
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        leaf = (
+            x509.CertificateBuilder()
+            .subject_name(leaf_subject)
+            .issuer_name(_name("intermediate"))
+            .public_key(leaf_key.public_key())
+            .serial_number(x509.random_serial_number())
+            .not_valid_before(NOT_BEFORE)
+            .not_valid_after(NOT_AFTER)
+            .add_extension(
+                x509.AuthorityKeyIdentifier.from_issuer_public_key(
+                    intermediate_key.public_key()
+                ),
+                False,
+            )
+            .add_extension(
+                x509.SubjectAlternativeName(
+                    [DNSName("cryptography.io"), *leaf_sans]
+                ),
+                # Required by RFC 5280 4.2.1.6 when the subject is empty.
+                critical=len(leaf_subject) == 0,
+            )
+            .sign(intermediate_key, hashes.SHA256())
+        )
+        verifier = (
+            PolicyBuilder()
+            .store(Store([root]))
+            .time(VALIDATION_TIME)
+            .build_server_verifier(DNSName("cryptography.io"))
+        )
+        verifier.verify(leaf, [intermediate])
+
+    def _org(self, *common_names: str) -> x509.Name:
+        return x509.Name(
+            [
+                x509.NameAttribute(NameOID.COUNTRY_NAME, "US"),
+                x509.NameAttribute(NameOID.ORGANIZATION_NAME, "Example"),
+                *(
+                    x509.NameAttribute(NameOID.COMMON_NAME, cn)
+                    for cn in common_names
+                ),
+            ]
+        )
+
+    def test_directory_name_subject(self):
+        constraints = x509.NameConstraints(
+            permitted_subtrees=[DirectoryName(self._org())],
+            excluded_subtrees=[DirectoryName(self._org("excluded"))],
+        )
+        self._verify(constraints, self._org("leaf"))
+        # Directory strings are compared case-insensitively.
+        self._verify(
+            constraints,
+            x509.Name(
+                [
+                    x509.NameAttribute(NameOID.COUNTRY_NAME, "us"),
+                    x509.NameAttribute(NameOID.ORGANIZATION_NAME, "EXAMPLE"),
+                ]
+            ),
+        )
+
+        with pytest.raises(
+            VerificationError,
+            match="no permitted name constraints matched subject",
+        ):
+            self._verify(constraints, _name("leaf"))
+        with pytest.raises(
+            VerificationError,
+            match="excluded name constraint matched subject",
+        ):
+            self._verify(constraints, self._org("excluded", "leaf"))
+
+    def test_directory_name_san(self):
+        constraints = x509.NameConstraints(
+            permitted_subtrees=[
+                DNSName("cryptography.io"),
+                DirectoryName(self._org()),
+            ],
+            excluded_subtrees=None,
+        )
+        self._verify(
+            constraints, self._org("leaf"), [DirectoryName(self._org("san"))]
+        )
+        with pytest.raises(
+            VerificationError,
+            match="no permitted name constraints matched SAN",
+        ):
+            self._verify(
+                constraints, self._org("leaf"), [DirectoryName(_name("san"))]
+            )
+
+    def test_empty_subject_not_constrained(self):
+        constraints = x509.NameConstraints(
+            permitted_subtrees=[DirectoryName(self._org())],
+            excluded_subtrees=None,
+        )
+        self._verify(constraints, x509.Name([]))
+
+    def test_uri(self):
+        constraints = x509.NameConstraints(
+            permitted_subtrees=[
+                DNSName("cryptography.io"),
+                UniformResourceIdentifier("example.com"),
+                UniformResourceIdentifier(".example.net"),
+            ],
+            excluded_subtrees=[
+                UniformResourceIdentifier("excluded.example.net")
+            ],
+        )
+        for uri in [
+            "https://example.com/path",
+            "ldap://user@example.com:389",
+            "spiffe://workload.example.net/service",
+        ]:
+            self._verify(
+                constraints, _name("leaf"), [UniformResourceIdentifier(uri)]
+            )
+
+        for uri in [
+            "https://sub.example.com/",
+            "https://example.net/",
+            "https://example.org/",
+        ]:
+            with pytest.raises(
+                VerificationError,
+                match="no permitted name constraints matched SAN",
+            ):
+                self._verify(
+                    constraints,
+                    _name("leaf"),
+                    [UniformResourceIdentifier(uri)],
+                )
+
+        with pytest.raises(
+            VerificationError, match="excluded name constraint matched SAN"
+        ):
+            self._verify(
+                constraints,
+                _name("leaf"),
+                [UniformResourceIdentifier("https://excluded.example.net/")],
+            )
+
+        # URIs without a domain name host can't satisfy URI constraints.
+        for uri in ["urn:example:leaf", "https://127.0.0.1/"]:
+            with pytest.raises(
+                VerificationError, match="unsatisfiable URI name constraint"
+            ):
+                self._verify(
+                    constraints,
+                    _name("leaf"),
+                    [UniformResourceIdentifier(uri)],
+                )
+
+    def _smtp_utf8_mailbox(self, address: str) -> x509.OtherName:
+        encoded = address.encode()
+        # SmtpUTF8Mailbox ::= UTF8String
+        return x509.OtherName(
+            x509.ObjectIdentifier("1.3.6.1.5.5.7.8.9"),
+            b"\x0c" + bytes([len(encoded)]) + encoded,
+        )
+
+    def test_smtp_utf8_mailbox(self):
+        # rfc822Name constraints apply to SmtpUTF8Mailbox names, comparing
+        # internationalized domains in their A-label form.
+        constraints = x509.NameConstraints(
+            permitted_subtrees=[x509.RFC822Name("xn--bcher-kva.example")],
+            excluded_subtrees=[
+                x509.RFC822Name("excluded@xn--bcher-kva.example")
+            ],
+        )
+        for address in ["délice@bücher.example", "foo@BÜCHER.example"]:
+            self._verify(
+                constraints,
+                _name("leaf"),
+                [self._smtp_utf8_mailbox(address)],
+            )
+        with pytest.raises(
+            VerificationError,
+            match="no permitted name constraints matched SAN",
+        ):
+            self._verify(
+                constraints,
+                _name("leaf"),
+                [self._smtp_utf8_mailbox("délice@example.com")],
+            )
+        with pytest.raises(
+            VerificationError, match="excluded name constraint matched SAN"
+        ):
+            self._verify(
+                constraints,
+                _name("leaf"),
+                [self._smtp_utf8_mailbox("excluded@bücher.example")],
+            )
+
+        # SmtpUTF8Mailbox constraints apply to both SmtpUTF8Mailbox and
+        # rfc822Name names.
+        constraints = x509.NameConstraints(
+            permitted_subtrees=[self._smtp_utf8_mailbox(".例え.jp")],
+            excluded_subtrees=[self._smtp_utf8_mailbox("用户@mail.例え.jp")],
+        )
+        self._verify(
+            constraints,
+            _name("leaf"),
+            [
+                self._smtp_utf8_mailbox("délice@mail.例え.jp"),
+                x509.RFC822Name("foo@mail.xn--r8jz45g.jp"),
+            ],
+        )
+        with pytest.raises(
+            VerificationError,
+            match="no permitted name constraints matched SAN",
+        ):
+            self._verify(
+                constraints,
+                _name("leaf"),
+                [x509.RFC822Name("foo@example.jp")],
+            )
+        with pytest.raises(
+            VerificationError, match="excluded name constraint matched SAN"
+        ):
+            self._verify(
+                constraints,
+                _name("leaf"),
+                [self._smtp_utf8_mailbox("用户@mail.xn--r8jz45g.jp")],
+            )
+
+        with pytest.raises(
+            VerificationError,
+            match="unsatisfiable SmtpUTF8Mailbox name constraint",
+        ):
+            self._verify(
+                constraints,
+                _name("leaf"),
+                [self._smtp_utf8_mailbox("not an address")],
+            )
+
+
+class TestCertificateTransparency:
+    def _log(
+        self,
+        operator: str,
+        not_before: typing.Optional[datetime.datetime] = None,
+        not_after: typing.Optional[datetime.datetime] = None,
+    ) -> typing.Tuple[CertificateTransparencyLog, ec.EllipticCurvePrivateKey]:
+        key = ec.generate_private_key(ec.SECP256R1())
+        log = CertificateTransparencyLog(
+            _spki_digest(key.public_key()),
+            key.public_key(),
+            operator,
+            not_before=not_before,
+            not_after=not_after,
+        )
+        return log, key
+
+    def _octet_string(self, value: bytes) -> bytes:
+        if len(value) < 0x80:
+            return bytes([0x04, len(value)]) + value
+        length = len(value).to_bytes((len(value).bit_length() + 7) // 8, "big")
+        return bytes([0x04, 0x80 | len(length)]) + length + value
+
+    def _sct(
+        self,
+        log: CertificateTransparencyLog,
+        log_key: ec.EllipticCurvePrivateKey,
+        issuer_key: ec.EllipticCurvePrivateKey,
+        precert: x509.Certificate,
+        timestamp: datetime.datetime = NOT_BEFORE,
+    ) -> bytes:
+        ts = int(timestamp.timestamp() * 1000).to_bytes(8, "big")
+        tbs = precert.tbs_certificate_bytes
+        # RFC 6962 3.2: the digitally-signed struct for a precert_entry.
+        signed = (
+            b"\x00\x00"
+            + ts
+            + b"\x00\x01"
+            + _spki_digest(issuer_key.public_key())
+            + len(tbs).to_bytes(3, "big")
+            + tbs
+            + b"\x00\x00"
+        )
+        signature = log_key.sign(signed, ec.ECDSA(hashes.SHA256()))
+        return (
+            b"\x00"
+            + log.log_id
+            + ts
+            + b"\x00\x00"
+            + b"\x04\x03"
+            + len(signature).to_bytes(2, "big")
+            + signature
+        )
+
+    def _verify(
+        self,
+        logs: typing.Sequence[CertificateTransparencyLog],
+        signers: typing.Sequence[
+            typing.Tuple[
+                CertificateTransparencyLog,
+                ec.EllipticCurvePrivateKey,
+                datetime.datetime,
+            ]
+        ],
+        minimum_scts: int = 2,
+        minimum_operators: int = 2,
+    ) -> None:
+        root_key, root = _build_ca("root")
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        builder = (
+            x509.CertificateBuilder()
+            .subject_name(_name("leaf"))
+            .issuer_name(_name("root"))
+            .public_key(leaf_key.public_key())
+            .serial_number(x509.random_serial_number())
+            .not_valid_before(NOT_BEFORE)
+            .not_valid_after(NOT_AFTER)
+            .add_extension(
+                x509.AuthorityKeyIdentifier.from_issuer_public_key(
+                    root_key.public_key()
+                ),
+                False,
+            )
+            .add_extension(
+                x509.SubjectAlternativeName([DNSName("cryptography.io")]),
+                False,
+            )
+        )
+        # The SCTs sign over the leaf's TBSCertificate without the SCT list.
+        precert = builder.sign(root_key, hashes.SHA256())
+        scts = b"".join(
+            len(sct).to_bytes(2, "big") + sct
+            for sct in (
+                self._sct(log, log_key, root_key, precert, timestamp)
+                for log, log_key, timestamp in signers
+            )
+        )
+        sct_list = len(scts).to_bytes(2, "big") + scts
+        leaf = builder.add_extension(
+            x509.UnrecognizedExtension(
+                ExtensionOID.PRECERT_SIGNED_CERTIFICATE_TIMESTAMPS,
+                self._octet_string(sct_list),
+            ),
+            False,
+        ).sign(root_key, hashes.SHA256())
+
+        verifier = _server_verifier(
+            _builder(root).certificate_transparency(
+                list(logs),
+                minimum_scts=minimum_scts,
+                minimum_operators=minimum_operators,
+            )
+        )
+        verifier.verify(leaf, [])
+
+    def test_log(self):
+        log, key = self._log("Example", not_before=NOT_BEFORE)
+        assert log.key == key.public_key()
+        assert log.operator == "Example"
+        assert log.not_before == NOT_BEFORE.replace(tzinfo=None)
+        assert log.not_after is None
+
+        with pytest.raises(ValueError):
+            CertificateTransparencyLog(b"\x00" * 31, key.public_key(), "x")
+
+        ed_key = ed25519.Ed25519PrivateKey.generate().public_key()
+        with pytest.raises(TypeError):
+            CertificateTransparencyLog(
+                b"\x00" * 32,
+                ed_key,  # type: ignore[arg-type]
+                "x",
+            )
+
+    def test_builder(self):
+        log, _ = self._log("Example")
+        builder = PolicyBuilder().certificate_transparency([log])
+        with pytest.raises(ValueError):
+            builder.certificate_transparency([log])
+        with pytest.raises(ValueError):
+            PolicyBuilder().certificate_transparency(
+                [log], minimum_scts=1, minimum_operators=2
+            )
+        with pytest.raises(ValueError):
+            builder.store(dummy_store()).build_client_verifier()
+        with pytest.raises(ValueError):
+            builder.store(dummy_store()).build_code_signing_verifier()
+        with pytest.raises(ValueError):
+            builder.store(dummy_store()).build_time_stamping_verifier()
+        with pytest.raises(ValueError):
+            builder.store(dummy_store()).build_email_protection_verifier(
+                RFC822Name("user@cryptography.io")
+            )
+
+    def test_valid(self):
+        log1, key1 = self._log("A")
+        log2, key2 = self._log("B")
+        self._verify(
+            [log1, log2],
+            [(log1, key1, NOT_BEFORE), (log2, key2, NOT_BEFORE)],
+        )
+
+    def test_single_operator(self):
+        log1, key1 = self._log("A")
+        log2, key2 = self._log("A")
+        signers = [(log1, key1, NOT_BEFORE), (log2, key2, NOT_BEFORE)]
+        self._verify([log1, log2], signers, minimum_operators=1)
+        with pytest.raises(VerificationError) as exc:
+            self._verify([log1, log2], signers)
+        # The SCTs are checked once the leaf's issuer is known.
+        [candidate] = exc.value.diagnostic.candidates
+        assert (
+            candidate.reason
+            == FailureReason.CERTIFICATE_TRANSPARENCY_VIOLATION
+        )
+
+    @pytest.mark.parametrize(
+        "scenario", ["missing", "unknown_log", "future", "window", "forged"]
+    )
+    def test_invalid_sct(self, scenario):
+        log1, key1 = self._log("A")
+        log2, key2 = self._log(
+            "B", not_after=NOT_BEFORE + datetime.timedelta(days=1)
+        )
+        signers = [(log1, key1, NOT_BEFORE)]
+        if scenario == "unknown_log":
+            signers.append(self._log("C") + (NOT_BEFORE,))
+        elif scenario == "future":
+            future = VALIDATION_TIME + datetime.timedelta(seconds=1)
+            signers.append((log2, key2, future))
+        elif scenario == "window":
+            late = NOT_BEFORE + datetime.timedelta(days=1)
+            signers.append((log2, key2, late))
+        elif scenario == "forged":
+            signers.append((log2, key1, NOT_BEFORE))
+
+        with pytest.raises(VerificationError) as exc:
+            self._verify([log1, log2], signers)
+        # The SCTs are checked once the leaf's issuer is known.
+        [candidate] = exc.value.diagnostic.candidates
+        assert (
+            candidate.reason
+            == FailureReason.CERTIFICATE_TRANSPARENCY_VIOLATION
+        )
+
+
+def _aia(*uris: str) -> x509.AuthorityInformationAccess:
+    return x509.AuthorityInformationAccess(
+        [
+            x509.AccessDescription(
+                AuthorityInformationAccessOID.CA_ISSUERS,
+                UniformResourceIdentifier(uri),
+            )
+            for uri in uris
+        ]
+    )
+
+
+class TestIssuerFetcher:
+    URI = "http://ca.example.com/intermediate.der"
+
+    def _chain(
+        self, *uris: str
+    ) -> typing.Tuple[x509.Certificate, x509.Certificate, x509.Certificate]:
+        root_key = ec.generate_private_key(ec.SECP256R1())
+        root = _build_cert(
+            "root", "root", root_key.public_key(), root_key, ca=True
+        )
+        intermediate_key = ec.generate_private_key(ec.SECP256R1())
+        intermediate = _build_cert(
+            "intermediate",
+            "root",
+            intermediate_key.public_key(),
+            root_key,
+            ca=True,
+        )
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        leaf = _build_cert(
+            "leaf",
+            "intermediate",
+            leaf_key.public_key(),
+            intermediate_key,
+            ca=False,
+            extensions=[_aia(*(uris or (self.URI,)))],
+        )
+        return root, intermediate, leaf
+
+    def _verifier(
+        self,
+        root: x509.Certificate,
+        fetcher: typing.Callable[[str], typing.Optional[bytes]],
+        max_chain_depth: typing.Optional[int] = None,
+        builder: typing.Optional[PolicyBuilder] = None,
+    ) -> ServerVerifier:
+        builder = (
+            (builder or PolicyBuilder())
+            .store(Store([root]))
+            .time(VALIDATION_TIME)
+            .issuer_fetcher(fetcher)
+        )
+        if max_chain_depth is not None:
+            builder = builder.max_chain_depth(max_chain_depth)
+        return builder.build_server_verifier(DNSName("cryptography.io"))
+
+    def test_builder(self):
+        builder = PolicyBuilder().issuer_fetcher(lambda uri: None)
+        with pytest.raises(ValueError):
+            builder.issuer_fetcher(lambda uri: None)
+
+        builder = PolicyBuilder().max_issuer_fetches(1)
+        with pytest.raises(ValueError):
+            builder.max_issuer_fetches(1)
+
+    @pytest.mark.parametrize("encoding", ["certificate", "pkcs7"])
+    def test_fetch(self, encoding):
+        root, intermediate, leaf = self._chain()
+        if encoding == "certificate":
+            response = intermediate.public_bytes(serialization.Encoding.DER)
+        else:
+            response = pkcs7.serialize_certificates(
+                [intermediate], serialization.Encoding.DER
+            )
+
+        fetched = []
+
+        def fetcher(uri: str) -> bytes:
+            fetched.append(uri)
+            return response
+
+        chain = self._verifier(root, fetcher).verify(leaf, [])
+        assert chain == [leaf, intermediate, root]
+        assert fetched == [self.URI]
+
+    def test_not_fetched_when_supplied(self):
+        root, intermediate, leaf = self._chain()
+
+        def fetcher(uri: str) -> bytes:
+            raise AssertionError("unexpected fetch")
+
+        chain = self._verifier(root, fetcher).verify(leaf, [intermediate])
+        assert chain == [leaf, intermediate, root]
+
+    def test_fetch_once_per_uri(self):
+        root, _, leaf = self._chain(self.URI, self.URI)
+        fetched = []
+
+        def fetcher(uri: str) -> None:
+            fetched.append(uri)
+
+        with pytest.raises(VerificationError):
+            self._verifier(root, fetcher).verify(leaf, [])
+        assert fetched == [self.URI]
+
+    def test_fetch_failure(self):
+        root, _, leaf = self._chain()
+        error = ConnectionError("connection refused")
+
+        def fetcher(uri: str) -> bytes:
+            raise error
+
+        with pytest.raises(
+            VerificationError,
+            match="unable to fetch issuer certificates from "
+            "http://ca.example.com/intermediate.der: "
+            "ConnectionError: connection refused",
+        ) as exc_info:
+            self._verifier(root, fetcher).verify(leaf, [])
+        assert exc_info.value.__cause__ is error
+
+    def test_fetch_wrong_type(self):
+        root, intermediate, leaf = self._chain()
+        response = intermediate.public_bytes(serialization.Encoding.PEM)
+
+        with pytest.raises(
+            VerificationError, match="unable to fetch issuer certificates"
+        ) as exc_info:
+            self._verifier(
+                root,
+                lambda uri: response.decode(),  # type: ignore[arg-type]
+            ).verify(leaf, [])
+        assert isinstance(exc_info.value.__cause__, TypeError)
+
+    def test_fetch_interrupted(self):
+        root, intermediate, leaf = self._chain()
+        calls = []
+
+        def fetcher(uri: str) -> bytes:
+            calls.append(uri)
+            if len(calls) == 1:
+                raise KeyboardInterrupt
+            return intermediate.public_bytes(serialization.Encoding.DER)
+
+        # The interruption is re-raised, rather than reported as a failure
+        # to verify.
+        verifier = self._verifier(root, fetcher)
+        with pytest.raises(KeyboardInterrupt):
+            verifier.verify(leaf, [])
+        assert verifier.verify(leaf, []) == [leaf, intermediate, root]
+        assert calls == [self.URI, self.URI]
+
+    def test_fetch_unrelated(self):
+        root, _, leaf = self._chain()
+        # The fetched certificate didn't issue the leaf, so it isn't tried.
+        response = root.public_bytes(serialization.Encoding.DER)
+        with pytest.raises(VerificationError) as exc_info:
+            self._verifier(root, lambda uri: response).verify(leaf, [])
+        assert exc_info.value.diagnostic.candidates == []
+
+    def test_depth_limit(self):
+        root, intermediate, leaf = self._chain()
+        fetched = []
+
+        def fetcher(uri: str) -> bytes:
+            fetched.append(uri)
+            return intermediate.public_bytes(serialization.Encoding.DER)
+
+        verifier = self._verifier(root, fetcher, max_chain_depth=0)
+        with pytest.raises(VerificationError):
+            verifier.verify(leaf, [])
+        assert fetched == []
+
+    @pytest.mark.parametrize("limit", [None, 2])
+    def test_budget(self, limit):
+        uris = [f"http://ca.example.com/{i}.der" for i in range(9)]
+        root, _, leaf = self._chain(*uris)
+        fetched = []
+
+        def fetcher(uri: str) -> None:
+            fetched.append(uri)
+
+        builder = PolicyBuilder()
+        if limit is not None:
+            builder = builder.max_issuer_fetches(limit)
+        with pytest.raises(VerificationError) as exc_info:
+            self._verifier(root, fetcher, builder=builder).verify(leaf, [])
+        assert (
+            exc_info.value.diagnostic.reason == FailureReason.BUDGET_EXHAUSTED
+        )
+        assert fetched == uris[: limit or 8]
+
+    def test_deadline(self):
+        root, _, leaf = self._chain()
+
+        def fetcher(uri: str) -> bytes:
+            raise AssertionError("unexpected fetch")
+
+        builder = PolicyBuilder().max_path_building_time(datetime.timedelta())
+        with pytest.raises(VerificationError) as exc_info:
+            self._verifier(root, fetcher, builder=builder).verify(leaf, [])
+        assert (
+            exc_info.value.diagnostic.reason == FailureReason.BUDGET_EXHAUSTED
+        )
+
+
+def _key_usage(**kwargs: bool) -> x509.KeyUsage:
+    usages = {
+        "digital_signature": False,
+        "content_commitment": False,
+        "key_encipherment": False,
+        "data_encipherment": False,
+        "key_agreement": False,
+        "key_cert_sign": False,
+        "crl_sign": False,
+    }
+    usages.update(kwargs)
+    return x509.KeyUsage(encipher_only=False, decipher_only=False, **usages)
+
+
+class TestProfiles:
+    def _leaf(
+        self,
+        extensions: typing.Sequence[x509.ExtensionType] = (),
+        critical_extensions: typing.Sequence[x509.ExtensionType] = (),
+        sans: typing.Sequence[x509.GeneralName] = (),
+    ) -> typing.Tuple[x509.Certificate, x509.Certificate]:
+        root_key = ec.generate_private_key(ec.SECP256R1())
+        root = _build_cert(
+            "root", "root", root_key.public_key(), root_key, ca=True
+        )
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        leaf = _build_cert(
+            "leaf",
+            "root",
+            leaf_key.public_key(),
+            root_key,
+            ca=False,
+            extensions=extensions,
+            critical_extensions=critical_extensions,
+            sans=sans,
+        )
+        return root, leaf
+
+    def _builder(self, root: x509.Certificate) -> PolicyBuilder:
+        return PolicyBuilder().store(Store([root])).time(VALIDATION_TIME)
+
+    def test_missing_store(self):
+        with pytest.raises(
+            ValueError, match="A code signing verifier must have a trust"
+        ):
+            PolicyBuilder().build_code_signing_verifier()
+        with pytest.raises(
+            ValueError, match="A time stamping verifier must have a trust"
+        ):
+            PolicyBuilder().build_time_stamping_verifier()
+        with pytest.raises(
+            ValueError, match="An email protection verifier must have a"
+        ):
+            PolicyBuilder().build_email_protection_verifier(
+                RFC822Name("user@cryptography.io")
+            )
+
+    @pytest.mark.parametrize(
+        ("extensions", "valid"),
+        [
+            (
+                [
+                    _key_usage(digital_signature=True),
+                    x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CODE_SIGNING]),
+                ],
+                True,
+            ),
+            # The keyUsage and EKU are both required.
+            ([_key_usage(digital_signature=True)], False),
+            (
+                [x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CODE_SIGNING])],
+                False,
+            ),
+            # digitalSignature must be asserted.
+            (
+                [
+                    _key_usage(content_commitment=True),
+                    x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CODE_SIGNING]),
+                ],
+                False,
+            ),
+            # serverAuth and anyExtendedKeyUsage are forbidden.
+            (
+                [
+                    _key_usage(digital_signature=True),
+                    x509.ExtendedKeyUsage(
+                        [
+                            ExtendedKeyUsageOID.CODE_SIGNING,
+                            ExtendedKeyUsageOID.SERVER_AUTH,
+                        ]
+                    ),
+                ],
+                False,
+            ),
+            (
+                [
+                    _key_usage(digital_signature=True),
+                    x509.ExtendedKeyUsage(
+                        [
+                            ExtendedKeyUsageOID.CODE_SIGNING,
+                            ExtendedKeyUsageOID.ANY_EXTENDED_KEY_USAGE,
+                        ]
+                    ),
+                ],
+                False,
+            ),
+            (
+                [
+                    _key_usage(digital_signature=True),
+                    x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CLIENT_AUTH]),
+                ],
+                False,
+            ),
+        ],
+    )
+    def test_code_signing(self, extensions, valid):
+        root, leaf = self._leaf(extensions=extensions)
+        verifier = self._builder(root).build_code_signing_verifier()
+        if valid:
+            verified = verifier.verify(leaf, [])
+            assert verified.chain == [leaf, root]
+            assert verified.subjects is None
+        else:
+            with pytest.raises(VerificationError):
+                verifier.verify(leaf, [])
+
+    def test_code_signing_eku_mismatch(self):
+        root, leaf = self._leaf(
+            extensions=[
+                _key_usage(digital_signature=True),
+                x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CLIENT_AUTH]),
+            ]
+        )
+        verifier = self._builder(root).build_code_signing_verifier()
+        with pytest.raises(VerificationError) as exc_info:
+            verifier.verify(leaf, [])
+        assert (
+            exc_info.value.diagnostic.reason
+            == FailureReason.EXTENDED_KEY_USAGE_MISMATCH
+        )
+
+    @pytest.mark.parametrize(
+        ("extensions", "critical_extensions", "valid"),
+        [
+            (
+                [],
+                [x509.ExtendedKeyUsage([ExtendedKeyUsageOID.TIME_STAMPING])],
+                True,
+            ),
+            (
+                [_key_usage(digital_signature=True)],
+                [x509.ExtendedKeyUsage([ExtendedKeyUsageOID.TIME_STAMPING])],
+                True,
+            ),
+            # The EKU must be critical.
+            (
+                [x509.ExtendedKeyUsage([ExtendedKeyUsageOID.TIME_STAMPING])],
+                [],
+                False,
+            ),
+            # The EKU must contain only id-kp-timeStamping.
+            (
+                [],
+                [
+                    x509.ExtendedKeyUsage(
+                        [
+                            ExtendedKeyUsageOID.TIME_STAMPING,
+                            ExtendedKeyUsageOID.CODE_SIGNING,
+                        ]
+                    )
+                ],
+                False,
+            ),
+            # The keyUsage, if present, must permit signing.
+            (
+                [_key_usage(key_agreement=True)],
+                [x509.ExtendedKeyUsage([ExtendedKeyUsageOID.TIME_STAMPING])],
+                False,
+            ),
+            (
+                [_key_usage(digital_signature=True, crl_sign=True)],
+                [x509.ExtendedKeyUsage([ExtendedKeyUsageOID.TIME_STAMPING])],
+                False,
+            ),
+        ],
+    )
+    def test_time_stamping(self, extensions, critical_extensions, valid):
+        root, leaf = self._leaf(
+            extensions=extensions, critical_extensions=critical_extensions
+        )
+        verifier = self._builder(root).build_time_stamping_verifier()
+        if valid:
+            assert verifier.verify(leaf, []).chain == [leaf, root]
+        else:
+            with pytest.raises(VerificationError):
+                verifier.verify(leaf, [])
+
+    @pytest.mark.parametrize(
+        ("subject", "valid"),
+        [
+            ("user@cryptography.io", True),
+            # Domains are compared case-insensitively...
+            ("user@CRYPTOGRAPHY.io", True),
+            # ...but local parts are not.
+            ("User@cryptography.io", False),
+            ("other@cryptography.io", False),
+            ("user@example.com", False),
+        ],
+    )
+    def test_email_protection_subject(self, subject, valid):
+        root, leaf = self._leaf(
+            extensions=[
+                _key_usage(digital_signature=True),
+                x509.ExtendedKeyUsage([ExtendedKeyUsageOID.EMAIL_PROTECTION]),
+            ],
+            sans=[RFC822Name("user@cryptography.io")],
+        )
+        verifier = self._builder(root).build_email_protection_verifier(
+            RFC822Name(subject)
+        )
+        assert verifier.subject == RFC822Name(subject)
+        if valid:
+            assert verifier.verify(leaf, []) == [leaf, root]
+        else:
+            with pytest.raises(VerificationError):
+                verifier.verify(leaf, [])
+
+    @pytest.mark.parametrize(
+        ("extensions", "valid"),
+        [
+            (
+                [
+                    _key_usage(key_encipherment=True),
+                    x509.ExtendedKeyUsage(
+                        [
+                            ExtendedKeyUsageOID.EMAIL_PROTECTION,
+                            ExtendedKeyUsageOID.CLIENT_AUTH,
+                        ]
+                    ),
+                ],
+                True,
+            ),
+            # The keyUsage must permit signing or key management.
+            (
+                [
+                    _key_usage(data_encipherment=True),
+                    x509.ExtendedKeyUsage(
+                        [ExtendedKeyUsageOID.EMAIL_PROTECTION]
+                    ),
+                ],
+                False,
+            ),
+            (
+                [
+                    x509.ExtendedKeyUsage(
+                        [ExtendedKeyUsageOID.EMAIL_PROTECTION]
+                    )
+                ],
+                False,
+            ),
+            # Other profiles' EKUs are forbidden.
+            (
+                [
+                    _key_usage(digital_signature=True),
+                    x509.ExtendedKeyUsage(
+                        [
+                            ExtendedKeyUsageOID.EMAIL_PROTECTION,
+                            ExtendedKeyUsageOID.TIME_STAMPING,
+                        ]
+                    ),
+                ],
+                False,
+            ),
+            (
+                [
+                    _key_usage(digital_signature=True),
+                    x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CLIENT_AUTH]),
+                ],
+                False,
+            ),
+        ],
+    )
+    def test_email_protection_usages(self, extensions, valid):
+        root, leaf = self._leaf(
+            extensions=extensions, sans=[RFC822Name("user@cryptography.io")]
+        )
+        verifier = self._builder(root).build_email_protection_verifier(
+            RFC822Name("user@cryptography.io")
+        )
+        if valid:
+            assert verifier.verify(leaf, []) == [leaf, root]
+        else:
+            with pytest.raises(VerificationError):
+                verifier.verify(leaf, [])
+
+    def test_email_protection_invalid_subject(self):
+        builder = PolicyBuilder().store(dummy_store())
+        with pytest.raises(TypeError):
+            builder.build_email_protection_verifier(
+                DNSName("cryptography.io")  # type: ignore[arg-type]
+            )
+        with pytest.raises(ValueError, match="invalid email address"):
+            builder.build_email_protection_verifier(
+                RFC822Name("cryptography.io")
+            )
+
+
+def _build_intermediate_chain() -> typing.Tuple[
+    x509.Certificate, x509.Certificate, x509.Certificate
+]:
+    root_key, root = _build_ca("root")
+    intermediate_key, intermediate = _build_ca(
+        "intermediate", "root", root_key
+    )
+    leaf = _build_leaf("intermediate", intermediate_key)
+    return root, intermediate, leaf
+
+
+class TestPartialChain:
+    def _verifier(
+        self, store: Store, partial_chain: typing.Optional[bool] = None
+    ) -> ServerVerifier:
+        builder = PolicyBuilder().store(store).time(VALIDATION_TIME)
+        if partial_chain is not None:
+            builder = builder.partial_chain(partial_chain)
+        return builder.build_server_verifier(DNSName("cryptography.io"))
+
+    def test_builder(self):
+        builder = PolicyBuilder().partial_chain(True)
+        with pytest.raises(ValueError):
+            builder.partial_chain(False)
+
+    def test_intermediate_anchor_rejected(self):
+        root, intermediate, leaf = _build_intermediate_chain()
+        verifier = self._verifier(Store([intermediate]), False)
+        with pytest.raises(VerificationError):
+            verifier.verify(leaf, [])
+
+        # An untrusted root doesn't help.
+        with pytest.raises(VerificationError):
+            verifier.verify(leaf, [root])
+
+    @pytest.mark.parametrize("partial_chain", [None, True])
+    def test_intermediate_anchor(self, partial_chain):
+        # Partial chains are allowed by default.
+        _, intermediate, leaf = _build_intermediate_chain()
+        verifier = self._verifier(Store([intermediate]), partial_chain)
+        assert verifier.verify(leaf, []) == [leaf, intermediate]
+
+    def test_intermediate_in_store(self):
+        root, intermediate, leaf = _build_intermediate_chain()
+        store = Store([root, intermediate])
+
+        # With partial chains, the intermediate is the anchor...
+        verifier = self._verifier(store)
+        assert verifier.verify(leaf, []) == [leaf, intermediate]
+
+        # ...and without them, it leads to the root.
+        verifier = self._verifier(store, False)
+        assert verifier.verify(leaf, []) == [leaf, intermediate, root]
+
+    def test_leaf_in_store(self):
+        _, _, leaf = _build_intermediate_chain()
+        verifier = self._verifier(Store([leaf]))
+        assert verifier.verify(leaf, []) == [leaf]
+
+
+class TestTrustAnchor:
+    def test_attributes(self):
+        root, _, _ = _build_intermediate_chain()
+        anchor = TrustAnchor(root)
+        assert anchor.certificate == root
+        assert anchor.name_constraints is None
+        assert anchor.max_path_length is None
+        assert anchor.extended_key_usages is None
+
+        nc = x509.NameConstraints(
+            permitted_subtrees=[DNSName("cryptography.io")],
+            excluded_subtrees=None,
+        )
+        anchor = TrustAnchor(
+            root,
+            name_constraints=nc,
+            max_path_length=1,
+            extended_key_usages=[ExtendedKeyUsageOID.SERVER_AUTH],
+        )
+        assert anchor.certificate == root
+        assert anchor.name_constraints == nc
+        assert anchor.max_path_length == 1
+        assert anchor.extended_key_usages == [ExtendedKeyUsageOID.SERVER_AUTH]
+
+    def test_invalid_name_constraints(self):
+        root, _, _ = _build_intermediate_chain()
+        bc = x509.BasicConstraints(ca=True, path_length=None)
+        with pytest.raises(TypeError):
+            TrustAnchor(
+                root,
+                name_constraints=bc,  # type: ignore[arg-type]
+            )
+
+    def _verify(self, anchor: TrustAnchor, *certs: x509.Certificate):
+        verifier = (
+            PolicyBuilder()
+            .store(Store([anchor]))
+            .time(VALIDATION_TIME)
+            .build_server_verifier(DNSName("cryptography.io"))
+        )
+        return verifier.verify(certs[0], list(certs[1:]))
+
+    def test_unconstrained(self):
+        root, intermediate, leaf = _build_intermediate_chain()
+        assert self._verify(TrustAnchor(root), leaf, intermediate) == [
+            leaf,
+            intermediate,
+            root,
+        ]
+
+    def test_max_path_length(self):
+        root, intermediate, leaf = _build_intermediate_chain()
+        assert self._verify(
+            TrustAnchor(root, max_path_length=1), leaf, intermediate
+        ) == [leaf, intermediate, root]
+
+        with pytest.raises(VerificationError) as exc_info:
+            self._verify(
+                TrustAnchor(root, max_path_length=0), leaf, intermediate
+            )
+        [attempt] = exc_info.value.diagnostic.candidates[0].candidates
+        assert attempt.certificate == root
+        assert attempt.reason == FailureReason.DEPTH_EXCEEDED
+
+    def test_extended_key_usages(self):
+        root, intermediate, leaf = _build_intermediate_chain()
+        for ekus in [
+            [ExtendedKeyUsageOID.SERVER_AUTH],
+            [ExtendedKeyUsageOID.ANY_EXTENDED_KEY_USAGE],
+        ]:
+            anchor = TrustAnchor(root, extended_key_usages=ekus)
+            assert self._verify(anchor, leaf, intermediate) == [
+                leaf,
+                intermediate,
+                root,
+            ]
+
+        anchor = TrustAnchor(
+            root, extended_key_usages=[ExtendedKeyUsageOID.CLIENT_AUTH]
+        )
+        with pytest.raises(VerificationError) as exc_info:
+            self._verify(anchor, leaf, intermediate)
+        [attempt] = exc_info.value.diagnostic.candidates[0].candidates
+        assert attempt.reason == FailureReason.EXTENDED_KEY_USAGE_MISMATCH
+
+    def test_name_constraints(self):
+        root, intermediate, leaf = _build_intermediate_chain()
+        anchor = TrustAnchor(
+            root,
+            name_constraints=x509.NameConstraints(
+                permitted_subtrees=[DNSName("cryptography.io")],
+                excluded_subtrees=None,
+            ),
+        )
+        assert self._verify(anchor, leaf, intermediate) == [
+            leaf,
+            intermediate,
+            root,
+        ]
+
+        anchor = TrustAnchor(
+            root,
+            name_constraints=x509.NameConstraints(
+                permitted_subtrees=None,
+                excluded_subtrees=[DNSName("cryptography.io")],
+            ),
+        )
+        with pytest.raises(VerificationError) as exc_info:
+            self._verify(anchor, leaf, intermediate)
+        [attempt] = exc_info.value.diagnostic.candidates[0].candidates
+        assert attempt.reason == FailureReason.NAME_CONSTRAINT_VIOLATION
+
+    def test_rejected_anchor_has_issuers(self):
+        # An anchor whose constraints reject the path isn't a dead end: path
+        # building continues through its own issuers.
+        root, intermediate, leaf = _build_intermediate_chain()
+        anchor = TrustAnchor(
+            intermediate,
+            extended_key_usages=[ExtendedKeyUsageOID.CLIENT_AUTH],
+        )
+        verifier = _server_verifier(_builder(anchor, root))
+        assert verifier.verify(leaf, []) == [leaf, intermediate, root]
+
+        verifier = _server_verifier(_builder(anchor))
+        with pytest.raises(VerificationError) as exc_info:
+            verifier.verify(leaf, [])
+        [attempt] = exc_info.value.diagnostic.candidates
+        assert attempt.certificate == intermediate
+        assert attempt.reason == FailureReason.EXTENDED_KEY_USAGE_MISMATCH
+
+    def test_partial_chain_anchor(self):
+        # Constraints apply to intermediates used as anchors, too.
+        _, intermediate, leaf = _build_intermediate_chain()
+        builder = (
+            PolicyBuilder()
+            .store(
+                Store(
+                    [
+                        TrustAnchor(
+                            intermediate,
+                            extended_key_usages=[
+                                ExtendedKeyUsageOID.CLIENT_AUTH
+                            ],
+                        )
+                    ]
+                )
+            )
+            .time(VALIDATION_TIME)
+            .partial_chain(True)
+        )
+        assert builder.build_client_verifier().verify(leaf, []).chain == [
+            leaf,
+            intermediate,
+        ]
+        with pytest.raises(VerificationError):
+            builder.build_server_verifier(DNSName("cryptography.io")).verify(
+                leaf, []
+            )
+
+
+def _build_mesh(
+    width: int, depth: int
+) -> typing.Tuple[
+    x509.Certificate, typing.List[x509.Certificate], x509.Certificate
+]:
+    # Builds a leaf under `depth` layers of `width` same-subject CAs, in
+    # which every CA in each layer cross-signs every CA in the layer below,
+    # so that there are `width ** depth` candidate paths to the root.
+    root_key = ec.generate_private_key(ec.SECP256R1())
+    root = _build_cert(
+        "root", "root", root_key.public_key(), root_key, ca=True
+    )
+    layers = [
+        [ec.generate_private_key(ec.SECP256R1()) for _ in range(width)]
+        for _ in range(depth)
+    ]
+    intermediates = []
+    for i, layer in enumerate(layers):
+        if i + 1 < depth:
+            issuer, issuer_keys = f"mesh{i + 1}", layers[i + 1]
+        else:
+            issuer, issuer_keys = "root", [root_key]
+        for key in layer:
+            for issuer_key in issuer_keys:
+                intermediates.append(
+                    _build_cert(
+                        f"mesh{i}", issuer, key.public_key(), issuer_key, True
+                    )
+                )
+    leaf_key = ec.generate_private_key(ec.SECP256R1())
+    leaf = _build_cert(
+        "leaf", "mesh0", leaf_key.public_key(), layers[0][0], ca=False
+    )
+    return root, intermediates, leaf
+
+
+class TestBudgets:
+    def test_builder(self):
+        builder = (
+            PolicyBuilder()
+            .max_signature_checks(10)
+            .max_candidates(10)
+            .max_path_building_time(datetime.timedelta(seconds=1))
+        )
+        with pytest.raises(ValueError):
+            builder.max_signature_checks(10)
+        with pytest.raises(ValueError):
+            builder.max_candidates(10)
+        with pytest.raises(ValueError):
+            builder.max_path_building_time(datetime.timedelta(seconds=1))
+
+    def _verify(
+        self,
+        root: x509.Certificate,
+        intermediates: typing.List[x509.Certificate],
+        leaf: x509.Certificate,
+        builder: typing.Optional[PolicyBuilder] = None,
+    ) -> typing.List[x509.Certificate]:
+        if builder is None:
+            builder = PolicyBuilder()
+        verifier = (
+            builder.store(Store([root]))
+            .time(VALIDATION_TIME)
+            .build_server_verifier(DNSName("cryptography.io"))
+        )
+        return verifier.verify(leaf, intermediates)
+
+    def _assert_exhausted(self, exc_info, message: str) -> None:
+        assert (
+            exc_info.value.diagnostic.reason == FailureReason.BUDGET_EXHAUSTED
+        )
+        assert message in str(exc_info.value)
+
+    def test_mesh(self):
+        root, intermediates, leaf = _build_mesh(3, 4)
+        chain = self._verify(root, intermediates, leaf)
+        assert chain[0] == leaf
+        assert chain[-1] == root
+        assert len(chain) == 6
+
+    def test_mesh_without_anchor(self):
+        # With no path to a trusted root, every path through the mesh is
+        # explored until the signature check budget runs out.
+        _, intermediates, leaf = _build_mesh(3, 4)
+        other_root, _, _ = _build_mesh(1, 1)
+        with pytest.raises(VerificationError) as exc_info:
+            self._verify(other_root, intermediates, leaf)
+        self._assert_exhausted(exc_info, "signature check limit")
+
+    def test_signature_checks(self):
+        root, intermediates, leaf = _build_mesh(2, 2)
+        with pytest.raises(VerificationError) as exc_info:
+            self._verify(
+                root,
+                intermediates,
+                leaf,
+                PolicyBuilder().max_signature_checks(1),
+            )
+        self._assert_exhausted(exc_info, "signature check limit")
+
+        chain = self._verify(
+            root,
+            intermediates,
+            leaf,
+            PolicyBuilder().max_signature_checks(3),
+        )
+        assert len(chain) == 4
+
+    def test_candidates(self):
+        root, intermediates, leaf = _build_mesh(2, 2)
+        # A path through the mesh considers at least one candidate issuer
+        # for each of its three links.
+        with pytest.raises(VerificationError) as exc_info:
+            self._verify(
+                root,
+                intermediates,
+                leaf,
+                PolicyBuilder().max_candidates(2),
+            )
+        self._assert_exhausted(exc_info, "candidate issuer limit")
+
+        chain = self._verify(
+            root,
+            intermediates,
+            leaf,
+            PolicyBuilder().max_candidates(64),
+        )
+        assert len(chain) == 4
+
+    def test_path_building_time(self):
+        root, intermediates, leaf = _build_mesh(2, 2)
+        with pytest.raises(VerificationError) as exc_info:
+            self._verify(
+                root,
+                intermediates,
+                leaf,
+                PolicyBuilder().max_path_building_time(datetime.timedelta()),
+            )
+        self._assert_exhausted(exc_info, "path building time")
+
+        chain = self._verify(
+            root,
+            intermediates,
+            leaf,
+            PolicyBuilder().max_path_building_time(
+                datetime.timedelta(seconds=60)
+            ),
+        )
+        assert len(chain) == 4
+
+    @pytest.mark.parametrize("source", ["crl", "ocsp"])
+    def test_revocation_signature_checks(self, source):
+        # Checking the leaf's revocation status verifies a second signature,
+        # on top of the leaf's own.
+        root_key, root, leaf = _build_chain()
+        builder = PolicyBuilder()
+        ocsp_responses = []
+        if source == "crl":
+            builder = builder.crls([_build_crl(root_key, "root")])
+        else:
+            ocsp_responses = [_build_ocsp_response(leaf, root, root, root_key)]
+
+        verifier = (
+            builder.store(Store([root]))
+            .time(VALIDATION_TIME)
+            .max_signature_checks(1)
+            .build_server_verifier(DNSName("cryptography.io"))
+        )
+        with pytest.raises(VerificationError) as exc_info:
+            verifier.verify(leaf, [], ocsp_responses)
+        [attempt] = exc_info.value.diagnostic.candidates
+        assert attempt.reason == FailureReason.BUDGET_EXHAUSTED
+
+        verifier = (
+            builder.store(Store([root]))
+            .time(VALIDATION_TIME)
+            .max_signature_checks(2)
+            .build_server_verifier(DNSName("cryptography.io"))
+        )
+        assert verifier.verify(leaf, [], ocsp_responses) == [leaf, root]
+
+
+class TestSignatureCache:
+    def test_builder(self):
+        builder = PolicyBuilder().signature_cache_size(16)
+        with pytest.raises(ValueError):
+            builder.signature_cache_size(16)
+        with pytest.raises(ValueError):
+            PolicyBuilder().signature_cache_size(0)
+
+    def _verifier(self, builder: PolicyBuilder, root: x509.Certificate):
+        return (
+            builder.store(Store([root]))
+            .time(VALIDATION_TIME)
+            .max_signature_checks(1)
+            .build_server_verifier(DNSName("cryptography.io"))
+        )
+
+    def test_cache_across_calls(self):
+        # The chain needs two signature checks, but only one is allowed per
+        # verification. With a cache, the signature checked by the first
+        # (failed) verification doesn't need to be checked by the second.
+        root, intermediates, leaf = _build_mesh(1, 1)
+
+        verifier = self._verifier(PolicyBuilder(), root)
+        for _ in range(2):
+            with pytest.raises(VerificationError) as exc_info:
+                verifier.verify(leaf, intermediates)
+            assert (
+                exc_info.value.diagnostic.reason
+                == FailureReason.BUDGET_EXHAUSTED
+            )
+
+        verifier = self._verifier(
+            PolicyBuilder().signature_cache_size(16), root
+        )
+        with pytest.raises(VerificationError):
+            verifier.verify(leaf, intermediates)
+        assert verifier.verify(leaf, intermediates) == [
+            leaf,
+            intermediates[0],
+            root,
+        ]
+
+    def test_cache_verify_many(self):
+        root, intermediates, leaf = _build_mesh(1, 1)
+        verifier = self._verifier(
+            PolicyBuilder().signature_cache_size(16), root
+        )
+
+        results = verifier.verify_many(
+            [(leaf, intermediates)] * 2, max_workers=1
+        )
+        assert isinstance(results[0], VerificationError)
+        assert results[1] == [leaf, intermediates[0], root]
+        # The verifier's cache outlives the batch.
+        assert verifier.verify(leaf, intermediates) == results[1]
+
+    def test_batch_cache(self):
+        # Without a cache of its own, a verifier still shares the signatures
+        # on intermediates between the items of a batch.
+        root_key, root = _build_ca("root")
+        ca1_key, ca1 = _build_ca("ca1", "root", root_key)
+        ca2_key, ca2 = _build_ca("ca2", "ca1", ca1_key)
+        leaf1 = _build_leaf("ca2", ca2_key)
+        leaf2 = _build_leaf("ca2", ca2_key)
+        verifier = (
+            PolicyBuilder()
+            .store(Store([root]))
+            .time(VALIDATION_TIME)
+            .max_signature_checks(2)
+            .build_server_verifier(DNSName("cryptography.io"))
+        )
+
+        results = verifier.verify_many(
+            [(leaf1, [ca1, ca2]), (leaf2, [ca1, ca2])], max_workers=1
+        )
+        assert isinstance(results[0], VerificationError)
+        assert results[1] == [leaf2, ca2, ca1, root]
+
+        # The signatures on leaves aren't shared, since a batch rarely
+        # checks the same one twice.
+        root, intermediates, leaf = _build_mesh(1, 1)
+        verifier = self._verifier(PolicyBuilder(), root)
+        results = verifier.verify_many(
+            [(leaf, intermediates)] * 2, max_workers=1
+        )
+        assert all(isinstance(r, VerificationError) for r in results)
+
+
+class TestValidationModel:
+    def test_builder(self):
+        builder = PolicyBuilder().validation_model(ValidationModel.CHAIN)
+        with pytest.raises(ValueError):
+            builder.validation_model(ValidationModel.CHAIN)
+        with pytest.raises(TypeError):
+            PolicyBuilder().validation_model("chain")  # type: ignore[arg-type]
+
+        builder = PolicyBuilder().clock_skew(datetime.timedelta(minutes=5))
+        with pytest.raises(ValueError):
+            builder.clock_skew(datetime.timedelta(minutes=5))
+
+    def _chain(
+        self, leaf_not_before: datetime.datetime
+    ) -> typing.Tuple[x509.Certificate, x509.Certificate, x509.Certificate]:
+        # The intermediate expires before the validation time.
+        root_key = ec.generate_private_key(ec.SECP256R1())
+        root = _build_cert(
+            "root", "root", root_key.public_key(), root_key, ca=True
+        )
+        intermediate_key = ec.generate_private_key(ec.SECP256R1())
+        intermediate = _build_cert(
+            "intermediate",
+            "root",
+            intermediate_key.public_key(),
+            root_key,
+            ca=True,
+            not_after=datetime.datetime(
+                2024, 3, 1, tzinfo=datetime.timezone.utc
+            ),
+        )
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        leaf = _build_cert(
+            "leaf",
+            "intermediate",
+            leaf_key.public_key(),
+            intermediate_key,
+            ca=False,
+            not_before=leaf_not_before,
+        )
+        return root, intermediate, leaf
+
+    def _verifier(
+        self, builder: PolicyBuilder, root: x509.Certificate
+    ) -> ServerVerifier:
+        return (
+            builder.store(Store([root]))
+            .time(VALIDATION_TIME)
+            .build_server_verifier(DNSName("cryptography.io"))
+        )
+
+    def test_shell_model(self):
+        root, intermediate, leaf = self._chain(
+            datetime.datetime(2024, 2, 1, tzinfo=datetime.timezone.utc)
+        )
+
+        for builder in [
+            PolicyBuilder(),
+            PolicyBuilder().validation_model(ValidationModel.SHELL),
+        ]:
+            verifier = self._verifier(builder, root)
+            with pytest.raises(VerificationError) as exc_info:
+                verifier.verify(leaf, [intermediate])
+            assert (
+                exc_info.value.diagnostic.candidates[0].reason
+                == FailureReason.EXPIRED
+            )
+
+    def test_chain_model(self):
+        root, intermediate, leaf = self._chain(
+            datetime.datetime(2024, 2, 1, tzinfo=datetime.timezone.utc)
+        )
+        verifier = self._verifier(
+            PolicyBuilder().validation_model(ValidationModel.CHAIN), root
+        )
+
+        assert verifier.verify(leaf, [intermediate]) == [
+            leaf,
+            intermediate,
+            root,
+        ]
+
+    def test_chain_model_issued_after_expiry(self):
+        root, intermediate, leaf = self._chain(
+            datetime.datetime(2024, 4, 1, tzinfo=datetime.timezone.utc)
+        )
+        verifier = self._verifier(
+            PolicyBuilder().validation_model(ValidationModel.CHAIN), root
+        )
+
+        with pytest.raises(VerificationError):
+            verifier.verify(leaf, [intermediate])
+
+    def test_chain_model_expired_leaf(self):
+        root, intermediate, leaf = self._chain(
+            datetime.datetime(2024, 2, 1, tzinfo=datetime.timezone.utc)
+        )
+        verifier = (
+            PolicyBuilder()
+            .validation_model(ValidationModel.CHAIN)
+            .store(Store([root]))
+            .time(NOT_AFTER + datetime.timedelta(days=1))
+            .build_server_verifier(DNSName("cryptography.io"))
+        )
+
+        with pytest.raises(VerificationError, match="validation time"):
+            verifier.verify(leaf, [intermediate])
+
+    @pytest.mark.parametrize(
+        ("skew", "valid"),
+        [
+            (None, False),
+            (datetime.timedelta(minutes=30), False),
+            (datetime.timedelta(hours=1), True),
+        ],
+    )
+    def test_clock_skew(
+        self, skew: typing.Optional[datetime.timedelta], valid: bool
+    ):
+        root_key = ec.generate_private_key(ec.SECP256R1())
+        root = _build_cert(
+            "root", "root", root_key.public_key(), root_key, ca=True
+        )
+        leaf_key = ec.generate_private_key(ec.SECP256R1())
+        leaf = _build_cert(
+            "leaf",
+            "root",
+            leaf_key.public_key(),
+            root_key,
+            ca=False,
+            not_before=VALIDATION_TIME + datetime.timedelta(hours=1),
+        )
+        builder = PolicyBuilder()
+        if skew is not None:
+            builder = builder.clock_skew(skew)
+        verifier = self._verifier(builder, root)
+
+        if valid:
+            assert verifier.verify(leaf, []) == [leaf, root]
+        else:
+            with pytest.raises(VerificationError, match="validation time"):
+                verifier.verify(leaf, [])
+
+
+def _build_svid_chain(
+    leaf_extensions: typing.Sequence[x509.ExtensionType] = (),
+    sans: typing.Sequence[x509.GeneralName] = (
+        UniformResourceIdentifier("spiffe://example.org/web"),
+    ),
+) -> typing.Tuple[
+    ec.EllipticCurvePrivateKey, x509.Certificate, x509.Certificate
+]:
+    # A chain whose leaf is an X.509-SVID for `sans`.
+    return _build_chain(
+        leaf_extensions,
+        sans=sans,
+        critical_extensions=[_key_usage(digital_signature=True)],
+    )
+
+
+class TestSpiffe:
+    def test_builder(self):
+        uri = UniformResourceIdentifier("spiffe://example.org/web")
+        with pytest.raises(ValueError, match="A SPIFFE verifier must have"):
+            PolicyBuilder().build_spiffe_server_verifier(uri)
+        with pytest.raises(ValueError, match="A SPIFFE verifier must have"):
+            PolicyBuilder().build_spiffe_client_verifier()
+
+        builder = PolicyBuilder().store(dummy_store())
+        with pytest.raises(TypeError):
+            builder.build_spiffe_server_verifier(
+                DNSName("example.org")  # type: ignore[arg-type]
+            )
+        with pytest.raises(TypeError):
+            builder.build_spiffe_client_verifier(
+                DNSName("example.org")  # type: ignore[arg-type]
+            )
+        for invalid in [
+            "https://example.org/web",
+            "spiffe://Example.org/web",
+            "spiffe://example.org/web/",
+        ]:
+            with pytest.raises(ValueError, match="invalid SPIFFE ID"):
+                builder.build_spiffe_server_verifier(
+                    UniformResourceIdentifier(invalid)
+                )
+
+        verifier = builder.build_spiffe_server_verifier(uri)
+        assert verifier.subject == uri
+
+    @pytest.mark.parametrize(
+        ("sans", "extensions", "critical_extensions", "valid"),
+        [
+            (
+                [UniformResourceIdentifier("spiffe://example.org/web")],
+                [],
+                [_key_usage(digital_signature=True)],
+                True,
+            ),
+            # Other kinds of SANs are permitted alongside the SPIFFE ID.
+            (
+                [
+                    DNSName("web.example.org"),
+                    UniformResourceIdentifier("spiffe://example.org/web"),
+                ],
+                [],
+                [_key_usage(digital_signature=True, key_agreement=True)],
+                True,
+            ),
+            (
+                [UniformResourceIdentifier("spiffe://example.org/web")],
+                [x509.ExtendedKeyUsage([ExtendedKeyUsageOID.SERVER_AUTH])],
+                [_key_usage(digital_signature=True)],
+                True,
+            ),
+            # Exactly one URI SAN, which is a workload's SPIFFE ID.
+            ([DNSName("web.example.org")], [], [], False),
+            (
+                [
+                    UniformResourceIdentifier("spiffe://example.org/web"),
+                    UniformResourceIdentifier("spiffe://example.org/db"),
+                ],
+                [],
+                [_key_usage(digital_signature=True)],
+                False,
+            ),
+            (
+                [UniformResourceIdentifier("https://example.org/web")],
+                [],
+                [_key_usage(digital_signature=True)],
+                False,
+            ),
+            (
+                [UniformResourceIdentifier("spiffe://example.org")],
+                [],
+                [_key_usage(digital_signature=True)],
+                False,
+            ),
+            # The keyUsage is required, critical, and asserts
+            # digitalSignature.
+            (
+                [UniformResourceIdentifier("spiffe://example.org/web")],
+                [],
+                [],
+                False,
+            ),
+            (
+                [UniformResourceIdentifier("spiffe://example.org/web")],
+                [_key_usage(digital_signature=True)],
+                [],
+                False,
+            ),
+            (
+                [UniformResourceIdentifier("spiffe://example.org/web")],
+                [],
+                [_key_usage(key_encipherment=True)],
+                False,
+            ),
+            # An EKU must permit server authentication.
+            (
+                [UniformResourceIdentifier("spiffe://example.org/web")],
+                [x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CLIENT_AUTH])],
+                [_key_usage(digital_signature=True)],
+                False,
+            ),
+        ],
+    )
+    def test_svid_profile(self, sans, extensions, critical_extensions, valid):
+        _, root, leaf = _build_chain(
+            extensions, sans=sans, critical_extensions=critical_extensions
+        )
+        verifier = _builder(root).build_spiffe_server_verifier(
+            UniformResourceIdentifier("spiffe://example.org")
+        )
+        if valid:
+            assert verifier.verify(leaf, []) == [leaf, root]
+        else:
+            with pytest.raises(VerificationError):
+                verifier.verify(leaf, [])
+
+    @pytest.mark.parametrize(
+        ("subject", "valid"),
+        [
+            ("spiffe://example.org/web", True),
+            ("spiffe://example.org", True),
+            ("spiffe://example.org/db", False),
+            ("spiffe://example.org/web/v2", False),
+            ("spiffe://example.com", False),
+            ("spiffe://example.com/web", False),
+        ],
+    )
+    def test_subject(self, subject, valid):
+        _, root, leaf = _build_svid_chain()
+        builder = _builder(root)
+        subject = UniformResourceIdentifier(subject)
+        verifiers = [
+            builder.build_spiffe_server_verifier(subject),
+            builder.build_spiffe_client_verifier(subject),
+        ]
+        for verifier in verifiers:
+            if valid:
+                verifier.verify(leaf, [])
+            else:
+                with pytest.raises(
+                    VerificationError, match="no matching subjectAltName"
+                ):
+                    verifier.verify(leaf, [])
+
+    def test_client(self):
+        _, root, leaf = _build_svid_chain(
+            [x509.ExtendedKeyUsage([ExtendedKeyUsageOID.CLIENT_AUTH])]
+        )
+        verifier = _builder(root).build_spiffe_client_verifier()
+
+        verified = verifier.verify(leaf, [])
+        assert verified.chain == [leaf, root]
+        assert verified.subjects == [
+            UniformResourceIdentifier("spiffe://example.org/web")
+        ]
+
+        with pytest.raises(VerificationError):
+            _builder(root).build_spiffe_server_verifier(
+                UniformResourceIdentifier("spiffe://example.org")
+            ).verify(leaf, [])
+
+    def test_trust_domain_bundles(self):
+        _, root, leaf = _build_svid_chain()
+        _, other_root, other_leaf = _build_svid_chain(
+            sans=[UniformResourceIdentifier("spiffe://example.com/web")]
+        )
+        anchor = TrustAnchor(root, spiffe_trust_domain="example.org")
+        assert anchor.spiffe_trust_domain == "example.org"
+        assert TrustAnchor(root).spiffe_trust_domain is None
+
+        verifier = _builder(
+            anchor,
+            TrustAnchor(other_root, spiffe_trust_domain="example.com"),
+        ).build_spiffe_client_verifier()
+        verifier.verify(leaf, [])
+        verifier.verify(other_leaf, [])
+
+        # A bundle only validates SVIDs in its own trust domain, even if its
+        # certificate issued an SVID for another trust domain.
+        verifier = _builder(
+            TrustAnchor(root, spiffe_trust_domain="example.com")
+        ).build_spiffe_client_verifier()
+        with pytest.raises(VerificationError) as exc_info:
+            verifier.verify(leaf, [])
+        assert (
+            exc_info.value.diagnostic.candidates[0].reason
+            == FailureReason.NAME_CONSTRAINT_VIOLATION
+        )
+
+    def test_invalid_trust_domain(self):
+        _, root, _ = _build_svid_chain()
+        for invalid in ["", "Example.org", "spiffe://example.org"]:
+            with pytest.raises(ValueError, match="invalid SPIFFE trust"):
+                TrustAnchor(root, spiffe_trust_domain=invalid)
+
+    def test_uri_subject(self):
+        # Outside of the SPIFFE profile, URI subjects are matched exactly.
+        _, root, leaf = _build_svid_chain(
+            sans=[UniformResourceIdentifier("https://example.org/web")]
+        )
+        builder = _builder(root)
+
+        verifier = builder.build_server_verifier(
+            UniformResourceIdentifier("https://example.org/web")
+        )
+        assert verifier.verify(leaf, []) == [leaf, root]
+
+        verifier = builder.build_server_verifier(
+            UniformResourceIdentifier("https://example.org/db")
+        )
+        with pytest.raises(VerificationError):
+            verifier.verify(leaf, [])
+
+
+def _subject_hash(cert: x509.Certificate) -> str:
+    # OpenSSL's hash of the certificate's subject, as used by `openssl
+    # rehash`: the first four bytes (little-endian) of the SHA-1 digest of
+    # its canonical RDNs. Our subjects are already in canonical form.
+    der = cert.subject.public_bytes()
+    header_len = 2 if der[1] < 0x80 else 2 + (der[1] & 0x7F)
+    digest = hashes.Hash(hashes.SHA1())
+    digest.update(der[header_len:])
+    return f"{int.from_bytes(digest.finalize()[:4], 'little'):08x}"
+
+
+def _pem(*certs: x509.Certificate) -> bytes:
+    return b"".join(c.public_bytes(serialization.Encoding.PEM) for c in certs)
+
+
+class TestStoreLoading:
+    def test_pem_bundle(self, tmp_path):
+        root, intermediate, leaf = _build_intermediate_chain()
+        other_root, _, _ = _build_intermediate_chain()
+        path = tmp_path / "bundle.pem"
+        path.write_bytes(_pem(other_root, root))
+
+        for p in [path, str(path)]:
+            store = Store.from_pem_bundle(p)
+            verifier = _server_verifier(_builder(store=store))
+            chain = verifier.verify(leaf, [intermediate])
+            assert chain == [leaf, intermediate, root]
+
+    def test_pem_bundle_invalid(self, tmp_path):
+        path = tmp_path / "bundle.pem"
+        path.write_bytes(b"")
+        with pytest.raises(ValueError):
+            Store.from_pem_bundle(path)
+
+        with pytest.raises(OSError):
+            Store.from_pem_bundle(tmp_path / "missing.pem")
+
+    def test_hashed_directory(self, tmp_path):
+        root, intermediate, leaf = _build_intermediate_chain()
+        # A different root with the same subject, and so the same hash.
+        other_root, _, _ = _build_intermediate_chain()
+        # The directory isn't read until it's needed.
+        store = Store.from_hashed_directory(tmp_path)
+        verifier = _server_verifier(_builder(store=store))
+
+        h = _subject_hash(root)
+        (tmp_path / f"{h}.0").write_bytes(_pem(other_root))
+        (tmp_path / f"{h}.1").write_bytes(_pem(root))
+        assert verifier.verify(leaf, [intermediate]) == [
+            leaf,
+            intermediate,
+            root,
+        ]
+
+        # Once loaded, the certificates are cached.
+        for name in os.listdir(tmp_path):
+            os.remove(tmp_path / name)
+        assert verifier.verify(leaf, [intermediate]) == [
+            leaf,
+            intermediate,
+            root,
+        ]
+
+    def test_hashed_directory_gap(self, tmp_path):
+        # Lookups stop at the first missing file, as with OpenSSL.
+        root, intermediate, leaf = _build_intermediate_chain()
+        h = _subject_hash(root)
+        (tmp_path / f"{h}.1").write_bytes(_pem(root))
+
+        store = Store.from_hashed_directory(str(tmp_path))
+        verifier = _server_verifier(_builder(store=store))
+        with pytest.raises(VerificationError):
+            verifier.verify(leaf, [intermediate])
+
+    def test_hashed_directory_load_error(self, tmp_path):
+        root, intermediate, leaf = _build_intermediate_chain()
+        h = _subject_hash(root)
+        (tmp_path / f"{h}.0").write_bytes(b"not a certificate")
+        (tmp_path / f"{h}.1").write_bytes(_pem(root))
+
+        store = Store.from_hashed_directory(tmp_path)
+        verifier = _server_verifier(_builder(store=store))
+        with pytest.raises(
+            VerificationError, match="not a PEM certificate file"
+        ) as exc:
+            verifier.verify(leaf, [intermediate])
+        assert f"{h}.0" in str(exc.value)
+
+        # Failed loads aren't cached, so fixing the file is enough.
+        (tmp_path / f"{h}.0").write_bytes(_pem(root))
+        assert verifier.verify(leaf, [intermediate]) == [
+            leaf,
+            intermediate,
+            root,
+        ]
+
+    def test_hashed_directory_invalid(self, tmp_path):
+        path = tmp_path / "bundle.pem"
+        path.write_bytes(b"")
+        with pytest.raises(ValueError):
+            Store.from_hashed_directory(path)
+
+        with pytest.raises(OSError):
+            Store.from_hashed_directory(tmp_path / "missing")
+
+
+def _must_staple() -> x509.TLSFeature:
+    return x509.TLSFeature([x509.TLSFeatureType.status_request])
+
+
+class TestMustStaple:
+    def test_missing_response(self):
+        _, root, leaf = _build_chain(leaf_extensions=[_must_staple()])
+        with pytest.raises(VerificationError, match="stapled OCSP") as exc:
+            _server_verifier(_builder(root)).verify(leaf, [])
+        assert (
+            exc.value.diagnostic.candidates[0].reason
+            == FailureReason.MUST_STAPLE_VIOLATION
+        )
+
+    def test_stapled_response(self):
+        root_key, root, leaf = _build_chain(leaf_extensions=[_must_staple()])
+        response = _build_ocsp_response(leaf, root, root, root_key)
+        assert _server_verifier(_builder(root)).verify(
+            leaf, [], ocsp_responses=[response]
+        ) == [leaf, root]
+
+    def test_revoked_response(self):
+        root_key, root, leaf = _build_chain(leaf_extensions=[_must_staple()])
+        response = _build_ocsp_response(
+            leaf, root, root, root_key, status=ocsp.OCSPCertStatus.REVOKED
+        )
+        verifier = _server_verifier(_builder(root))
+        with pytest.raises(VerificationError, match="revoked"):
+            verifier.verify(leaf, [], ocsp_responses=[response])
+
+    def test_other_features(self):
+        # Only `status_request` requires a stapled response.
+        _, root, leaf = _build_chain(
+            leaf_extensions=[
+                x509.TLSFeature([x509.TLSFeatureType.status_request_v2])
+            ]
+        )
+        verifier = _server_verifier(_builder(root))
+        assert verifier.verify(leaf, []) == [leaf, root]
+
+    def test_client_verifier(self):
+        _, root, leaf = _build_chain(leaf_extensions=[_must_staple()])
+        _builder(root).build_client_verifier().verify(leaf, [])
+
+    def test_intermediate(self):
+        root_key, root = _build_ca("root")
+        intermediate_key, intermediate = _build_ca(
+            "intermediate", "root", root_key, extensions=[_must_staple()]
+        )
+        leaf = _build_leaf("intermediate", intermediate_key)
+        verifier = _server_verifier(_builder(root))
+
+        leaf_response = _build_ocsp_response(
+            leaf, intermediate, intermediate, intermediate_key
+        )
+        with pytest.raises(VerificationError, match="stapled OCSP"):
+            verifier.verify(
+                leaf, [intermediate], ocsp_responses=[leaf_response]
+            )
+
+        intermediate_response = _build_ocsp_response(
+            intermediate, root, root, root_key
+        )
+        assert verifier.verify(
+            leaf, [intermediate], ocsp_responses=[intermediate_response]
+        ) == [leaf, intermediate, root]
+
+
+def _tlsa(
+    usage: TLSAUsage,
+    cert: x509.Certificate,
+    selector: TLSASelector = TLSASelector.SPKI,
+    matching_type: TLSAMatchingType = TLSAMatchingType.SHA256,
+) -> TLSARecord:
+    if selector == TLSASelector.CERT:
+        data = cert.public_bytes(serialization.Encoding.DER)
+    else:
+        data = cert.public_key().public_bytes(
+            serialization.Encoding.DER,
+            serialization.PublicFormat.SubjectPublicKeyInfo,
+        )
+    if matching_type != TLSAMatchingType.FULL:
+        algorithm = (
+            hashes.SHA256()
+            if matching_type == TLSAMatchingType.SHA256
+            else hashes.SHA512()
+        )
+        digest = hashes.Hash(algorithm)
+        digest.update(data)
+        data = digest.finalize()
+    return TLSARecord(usage, selector, matching_type, data)
+
+
+class TestDane:
+    def test_record(self):
+        record = TLSARecord(
+            TLSAUsage.DANE_EE,
+            TLSASelector.CERT,
+            TLSAMatchingType.FULL,
+            b"data",
+        )
+        assert record.usage == TLSAUsage.DANE_EE
+        assert record.selector == TLSASelector.CERT
+        assert record.matching_type == TLSAMatchingType.FULL
+        assert record.data == b"data"
+
+    def test_record_invalid(self):
+        with pytest.raises(TypeError, match="usage"):
+            TLSARecord(
+                3,  # type: ignore[arg-type]
+                TLSASelector.CERT,
+                TLSAMatchingType.FULL,
+                b"",
+            )
+        with pytest.raises(TypeError, match="selector"):
+            TLSARecord(
+                TLSAUsage.DANE_EE,
+                0,  # type: ignore[arg-type]
+                TLSAMatchingType.FULL,
+                b"",
+            )
+        with pytest.raises(TypeError, match="matching type"):
+            TLSARecord(
+                TLSAUsage.DANE_EE,
+                TLSASelector.CERT,
+                0,  # type: ignore[arg-type]
+                b"",
+            )
+
+    @pytest.mark.parametrize("selector", list(TLSASelector))
+    @pytest.mark.parametrize("matching_type", list(TLSAMatchingType))
+    def test_matches(self, selector, matching_type):
+        _, root, leaf = _build_chain()
+        record = _tlsa(TLSAUsage.DANE_EE, leaf, selector, matching_type)
+        assert record.matches(leaf)
+        assert not record.matches(root)
+
+    def test_matches_wrong_length(self):
+        _, _, leaf = _build_chain()
+        record = _tlsa(TLSAUsage.DANE_EE, leaf)
+        truncated = TLSARecord(
+            record.usage,
+            record.selector,
+            record.matching_type,
+            record.data[:-1],
+        )
+        assert not truncated.matches(leaf)
+
+    def test_dane_ee(self):
+        _, _, leaf = _build_chain()
+        # The leaf needn't chain to anything in the store...
+        unrelated, _, _ = _build_intermediate_chain()
+        verifier = _server_verifier(_builder(unrelated))
+        records = [_tlsa(TLSAUsage.DANE_EE, leaf)]
+        assert verifier.verify_dane(leaf, [], records) == [leaf]
+
+        # ...and isn't otherwise checked.
+        root_key = ec.generate_private_key(ec.SECP256R1())
+        expired = _build_cert(
+            "leaf",
+            "leaf",
+            root_key.public_key(),
+            root_key,
+            ca=False,
+            not_after=NOT_BEFORE + datetime.timedelta(days=1),
+        )
+        records = [_tlsa(TLSAUsage.DANE_EE, expired)]
+        assert verifier.verify_dane(expired, [], records) == [expired]
+
+    def test_dane_ta(self):
+        root, intermediate, leaf = _build_intermediate_chain()
+        unrelated, _, _ = _build_intermediate_chain()
+        verifier = _server_verifier(_builder(unrelated))
+
+        records = [_tlsa(TLSAUsage.DANE_TA, intermediate)]
+        assert verifier.verify_dane(leaf, [intermediate], records) == [
+            leaf,
+            intermediate,
+        ]
+
+        # A digest can't stand in for an anchor the server didn't present.
+        with pytest.raises(VerificationError):
+            verifier.verify_dane(leaf, [], records)
+
+        # A matched root is an anchor too.
+        records = [_tlsa(TLSAUsage.DANE_TA, root)]
+        assert verifier.verify_dane(leaf, [intermediate, root], records) == [
+            leaf,
+            intermediate,
+            root,
+        ]
+
+    def test_dane_ta_published_in_dns(self):
+        root, intermediate, leaf = _build_intermediate_chain()
+        unrelated, _, _ = _build_intermediate_chain()
+        verifier = _server_verifier(_builder(unrelated))
+
+        # A full certificate is an anchor without being presented.
+        records = [
+            _tlsa(
+                TLSAUsage.DANE_TA,
+                intermediate,
+                TLSASelector.CERT,
+                TLSAMatchingType.FULL,
+            )
+        ]
+        assert verifier.verify_dane(leaf, [], records) == [leaf, intermediate]
+
+        # It can also complete a chain that the server left short.
+        records = [
+            _tlsa(
+                TLSAUsage.DANE_TA,
+                root,
+                TLSASelector.CERT,
+                TLSAMatchingType.FULL,
+            )
+        ]
+        assert verifier.verify_dane(leaf, [intermediate], records) == [
+            leaf,
+            intermediate,
+            root,
+        ]
+
+        # A full public key is an anchor without a certificate, so the chain
+        # ends with the certificate that it signed.
+        for cert, chain in [
+            (intermediate, [leaf]),
+            (root, [leaf, intermediate]),
+        ]:
+            records = [
+                _tlsa(
+                    TLSAUsage.DANE_TA,
+                    cert,
+                    TLSASelector.SPKI,
+                    TLSAMatchingType.FULL,
+                )
+            ]
+            assert verifier.verify_dane(leaf, [intermediate], records) == chain
+
+        # Data that doesn't parse makes the record unusable.
+        for selector in TLSASelector:
+            record = TLSARecord(
+                TLSAUsage.DANE_TA, selector, TLSAMatchingType.FULL, b"\x30\x00"
+            )
+            with pytest.raises(VerificationError):
+                verifier.verify_dane(leaf, [intermediate], [record])
+
+    def test_dane_ta_checks_subject(self):
+        _, root, leaf = _build_chain(sans=[DNSName("example.com")])
+        verifier = _server_verifier(_builder(root))
+        with pytest.raises(VerificationError):
+            verifier.verify_dane(
+                leaf, [root], [_tlsa(TLSAUsage.DANE_TA, root)]
+            )
+
+    def test_pkix_ta(self):
+        root, intermediate, leaf = _build_intermediate_chain()
+        verifier = _server_verifier(_builder(root))
+
+        for cert in (root, intermediate):
+            records = [_tlsa(TLSAUsage.PKIX_TA, cert)]
+            assert verifier.verify_dane(leaf, [intermediate], records) == [
+                leaf,
+                intermediate,
+                root,
+            ]
+
+        # The leaf isn't a CA.
+        with pytest.raises(VerificationError, match="PKIX-TA"):
+            verifier.verify_dane(
+                leaf, [intermediate], [_tlsa(TLSAUsage.PKIX_TA, leaf)]
+            )
+
+    def test_pkix_ta_untrusted(self):
+        root, intermediate, leaf = _build_intermediate_chain()
+        unrelated, _, _ = _build_intermediate_chain()
+        verifier = _server_verifier(_builder(unrelated))
+        with pytest.raises(VerificationError):
+            verifier.verify_dane(
+                leaf, [intermediate], [_tlsa(TLSAUsage.PKIX_TA, root)]
+            )
+
+    def test_pkix_ee(self):
+        _, root, leaf = _build_chain()
+        verifier = _server_verifier(_builder(root))
+        records = [_tlsa(TLSAUsage.PKIX_EE, leaf)]
+        assert verifier.verify_dane(leaf, [], records) == [leaf, root]
+
+        with pytest.raises(VerificationError) as exc:
+            verifier.verify_dane(leaf, [], [_tlsa(TLSAUsage.PKIX_EE, root)])
+        assert (
+            exc.value.diagnostic.candidates[0].reason
+            == FailureReason.DANE_MISMATCH
+        )
+
+    def test_pkix_next_path(self):
+        # Only the path through the matching root is accepted.
+        other, root, intermediate_a, intermediate_b, leaf = (
+            _build_cross_signed()
+        )
+        verifier = _server_verifier(_builder(other, root))
+        chain = verifier.verify_dane(
+            leaf,
+            [intermediate_a, intermediate_b],
+            [_tlsa(TLSAUsage.PKIX_TA, root)],
+        )
+        assert chain == [leaf, intermediate_b, root]
+
+    def test_no_match(self):
+        _, root, leaf = _build_chain()
+        verifier = _server_verifier(_builder(root))
+
+        with pytest.raises(VerificationError, match="no TLSA record"):
+            verifier.verify_dane(leaf, [], [])
+
+        records = [_tlsa(TLSAUsage.DANE_EE, root)]
+        with pytest.raises(VerificationError, match="no TLSA record"):
+            verifier.verify_dane(leaf, [], records)
+
+
+def _spki_pin(cert: x509.Certificate) -> bytes:
+    return _spki_digest(cert.public_key())
+
+
+class TestSpkiPins:
+    def test_builder(self):
+        _, root, _ = _build_chain()
+        builder = PolicyBuilder().spki_pins([_spki_pin(root)])
+        with pytest.raises(ValueError, match="only be set once"):
+            builder.spki_pins([_spki_pin(root)])
+
+        with pytest.raises(ValueError, match="At least one"):
+            PolicyBuilder().spki_pins([])
+        with pytest.raises(ValueError, match="SHA-256"):
+            PolicyBuilder().spki_pins([b"\x00" * 20])
+
+    def test_pinned(self):
+        root, intermediate, leaf = _build_intermediate_chain()
+        for cert in (leaf, intermediate, root):
+            builder = _builder(root).spki_pins([_spki_pin(cert)])
+            assert _server_verifier(builder).verify(leaf, [intermediate]) == [
+                leaf,
+                intermediate,
+                root,
+            ]
+
+    def test_unpinned(self):
+        _, root, leaf = _build_chain()
+        _, other, _ = _build_chain()
+        builder = _builder(root).spki_pins([_spki_pin(other)])
+        with pytest.raises(VerificationError, match="pinned") as exc:
+            _server_verifier(builder).verify(leaf, [])
+        assert (
+            exc.value.diagnostic.candidates[0].reason
+            == FailureReason.PIN_MISMATCH
+        )
+
+    def test_next_path(self):
+        root_a, root_b, intermediate_a, intermediate_b, leaf = (
+            _build_cross_signed()
+        )
+        intermediates = [intermediate_a, intermediate_b]
+        for pinned, intermediate in [
+            (root_a, intermediate_a),
+            (root_b, intermediate_b),
+        ]:
+            builder = _builder(root_a, root_b).spki_pins([_spki_pin(pinned)])
+            assert _server_verifier(builder).verify(leaf, intermediates) == [
+                leaf,
+                intermediate,
+                pinned,
+            ]
+
+    def test_client_verifier(self):
+        _, root, leaf = _build_chain()
+        _, other, _ = _build_chain()
+        builder = _builder(root).spki_pins([_spki_pin(root)])
+        verified = builder.build_client_verifier().verify(leaf, [])
+        assert verified.chain == [leaf, root]
+
+        builder = _builder(root).spki_pins([_spki_pin(other)])
+        with pytest.raises(VerificationError, match="pinned"):
+            builder.build_client_verifier().verify(leaf, [])
+
+
+def _key_anchor(cert: x509.Certificate, **kwargs: typing.Any) -> TrustAnchor:
+    # A trust anchor with `cert`'s name and key, but not `cert` itself.
+    return TrustAnchor.from_public_key(
+        cert.subject, cert.public_key(), **kwargs
+    )
+
+
+class TestKeyAnchor:
+    def test_attributes(self):
+        root_key, root, _ = _build_chain()
+        anchor = TrustAnchor.from_public_key(
+            _name("root"), root_key.public_key(), max_path_length=1
+        )
+        assert anchor.certificate is None
+        assert anchor.name == _name("root")
+        assert anchor.public_key() == root_key.public_key()
+        assert anchor.max_path_length == 1
+
+        anchor = TrustAnchor(root)
+        assert anchor.name == root.subject
+        assert anchor.public_key() == root.public_key()
+
+    def test_invalid(self):
+        root_key = ec.generate_private_key(ec.SECP256R1())
+        with pytest.raises(TypeError, match="Name"):
+            TrustAnchor.from_public_key(
+                "root",  # type: ignore[arg-type]
+                root_key.public_key(),
+            )
+        with pytest.raises(TypeError):
+            TrustAnchor.from_public_key(
+                _name("root"),
+                root_key,  # type: ignore[arg-type]
+            )
+
+    def test_leaf(self):
+        _, root, leaf = _build_chain()
+        anchor = _key_anchor(root)
+        verifier = _server_verifier(_builder(anchor))
+        assert verifier.verify(leaf, []) == [leaf]
+
+        verified = verifier.verify_chain(leaf, [])
+        assert verified.chain == [leaf]
+        assert verified.trust_anchor is anchor
+
+    def test_intermediate(self):
+        root, intermediate, leaf = _build_intermediate_chain()
+        anchor = _key_anchor(root)
+        verifier = _server_verifier(_builder(anchor))
+        verified = verifier.verify_chain(leaf, [intermediate])
+        assert verified.chain == [leaf, intermediate]
+        assert verified.trust_anchor is anchor
+
+        # An intermediate can be an anchor, too.
+        anchor = _key_anchor(intermediate)
+        verified = _server_verifier(_builder(anchor)).verify_chain(leaf, [])
+        assert verified.chain == [leaf]
+        assert verified.trust_anchor is anchor
+
+    def test_certificate_anchor(self):
+        _, root, leaf = _build_chain()
+        verifier = _server_verifier(_builder(TrustAnchor(root)))
+        verified = verifier.verify_chain(leaf, [])
+        assert verified.chain == [leaf, root]
+        assert verified.trust_anchor is None
+
+    def test_mismatch(self):
+        root_key, _, leaf = _build_chain()
+        other_key = ec.generate_private_key(ec.SECP256R1())
+        for anchor in [
+            TrustAnchor.from_public_key(_name("root"), other_key.public_key()),
+            TrustAnchor.from_public_key(
+                _name("other"), root_key.public_key()
+            ),
+        ]:
+            with pytest.raises(VerificationError):
+                _server_verifier(_builder(anchor)).verify(leaf, [])
+
+    @pytest.mark.parametrize(
+        "constraints",
+        [
+            {"max_path_length": 0},
+            {"extended_key_usages": [ExtendedKeyUsageOID.CLIENT_AUTH]},
+            {
+                "name_constraints": x509.NameConstraints(
+                    permitted_subtrees=None,
+                    excluded_subtrees=[DNSName("cryptography.io")],
+                )
+            },
+        ],
+    )
+    def test_constraints(self, constraints):
+        root, intermediate, leaf = _build_intermediate_chain()
+        verifier = _server_verifier(
+            _builder(_key_anchor(root, **constraints))
+        )
+        with pytest.raises(VerificationError):
+            verifier.verify(leaf, [intermediate])
+
+    def test_spki_pins(self):
+        _, root, leaf = _build_chain()
+        builder = _builder(_key_anchor(root)).spki_pins([_spki_pin(root)])
+        assert _server_verifier(builder).verify(leaf, []) == [leaf]
+
+    def test_revocation(self):
+        root_key, root, revoked = _build_chain()
+        leaf = _build_leaf("root", root_key)
+        crl = _build_crl(root_key, "root", revoked=[revoked])
+
+        # The CRL is matched by the anchor's name, and checked with its key.
+        verifier = _server_verifier(_builder(_key_anchor(root)).crls([crl]))
+        assert verifier.verify(leaf, []) == [leaf]
+        with pytest.raises(VerificationError, match="revoked"):
+            verifier.verify(revoked, [])
+
+        # A CRL with the anchor's name but another key doesn't apply.
+        other_key, _ = _build_ca("root")
+        crl = _build_crl(other_key, "root", revoked=[revoked])
+        verifier = _server_verifier(_builder(_key_anchor(root)).crls([crl]))
+        with pytest.raises(VerificationError, match="revocation"):
+            verifier.verify(leaf, [])
+
+    def test_ocsp(self):
+        root_key, root, leaf = _build_chain()
+        crl = _build_crl(root_key, "root")
+        verifier = _server_verifier(_builder(_key_anchor(root)).crls([crl]))
+
+        for encoding in ocsp.OCSPResponderEncoding:
+            response = _build_ocsp_response(
+                leaf,
+                root,
+                root,
+                root_key,
+                status=ocsp.OCSPCertStatus.REVOKED,
+                encoding=encoding,
+            )
+            with pytest.raises(VerificationError, match="revoked"):
+                verifier.verify(leaf, [], ocsp_responses=[response])
+
+    def test_must_staple(self):
+        root_key, root, leaf = _build_chain(leaf_extensions=[_must_staple()])
+        verifier = _server_verifier(_builder(_key_anchor(root)))
+        with pytest.raises(VerificationError, match="stapled OCSP"):
+            verifier.verify(leaf, [])
+
+        response = _build_ocsp_response(leaf, root, root, root_key)
+        assert verifier.verify(leaf, [], ocsp_responses=[response]) == [leaf]
+
+    def test_client_verifier(self):
+        _, root, leaf = _build_chain()
+        anchor = _key_anchor(root)
+        verified = _builder(anchor).build_client_verifier().verify(leaf, [])
+        assert verified.chain == [leaf]
+        assert verified.trust_anchor is anchor